@@ -1,3 +1,6 @@
 fn main() {
-    slint_build::compile("ui/main.slint").expect("Failed to compile Slint UI");
+    // Bundle the gettext catalogs from lang/<language>/LC_MESSAGES/autoqac.po for @tr
+    let config = slint_build::CompilerConfiguration::new().with_bundled_translations("lang");
+    slint_build::compile_with_config("ui/main.slint", config).expect("Failed to compile Slint UI");
+    println!("cargo:rerun-if-changed=lang");
 }
@@ -1,19 +1,71 @@
-use crate::models::{IgnoreConfig, MainConfig, UserConfig};
+use crate::models::{CleaningHistory, IgnoreConfig, MainConfig, SessionCheckpoint, UserConfig};
+use crate::services::session::SESSIONS_DIR;
 use anyhow::{Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
 use std::fs;
 
+/// Folder with the configuration files, next to the executable
+pub const CONFIG_DIR: &str = "AutoQAC Data";
+
+/// Folder with the application logs, next to the executable
+pub const LOGS_DIR: &str = "logs";
+
+/// Folders AutoQAC keeps its files in
+///
+/// They are found from the executable, not the working directory: Explorer starts
+/// "Open with" and drops on the executable in the plugin's folder or in System32.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppPaths {
+    /// Configuration files ([`CONFIG_DIR`]), also holding the instance lock
+    pub config: Utf8PathBuf,
+
+    /// Application logs ([`LOGS_DIR`])
+    pub logs: Utf8PathBuf,
+
+    /// Session folders of cleaning runs ([`SESSIONS_DIR`])
+    pub sessions: Utf8PathBuf,
+}
+
+impl AppPaths {
+    /// Folders under a root directory
+    pub fn new(root: &Utf8Path) -> Self {
+        Self {
+            config: root.join(CONFIG_DIR),
+            logs: root.join(LOGS_DIR),
+            sessions: root.join(SESSIONS_DIR),
+        }
+    }
+
+    /// Folders next to the running executable
+    ///
+    /// # Errors
+    /// Returns an error if the executable's path cannot be determined or is not UTF-8
+    pub fn beside_executable() -> Result<Self> {
+        let exe = std::env::current_exe().context("Failed to locate the AutoQAC executable")?;
+        let exe = Utf8PathBuf::try_from(exe).context("AutoQAC executable path is not UTF-8")?;
+        let root = exe
+            .parent()
+            .with_context(|| format!("Executable has no parent folder: {}", exe))?;
+        Ok(Self::new(root))
+    }
+}
+
 /// Configuration manager for loading and saving YAML configuration files.
 ///
 /// Manages two primary configuration files:
 /// - Main config (`AutoQAC Main.yaml`): Game configurations, skip lists
 /// - User config (`AutoQAC Config.yaml` or `PACT Settings.yaml`): User settings, paths
+///
+/// It also keeps the cleaning history (`AutoQAC History.yaml`) with recent run times, and the
+/// checkpoint of the current run (`AutoQAC Session.yaml`).
 #[derive(Debug, Clone)]
 pub struct ConfigManager {
     config_dir: Utf8PathBuf,
     main_config_path: Utf8PathBuf,
     user_config_path: Utf8PathBuf,
     ignore_config_path: Utf8PathBuf,
+    history_path: Utf8PathBuf,
+    checkpoint_path: Utf8PathBuf,
 }
 
 impl ConfigManager {
@@ -37,6 +89,8 @@ impl ConfigManager {
             main_config_path: config_dir.join("AutoQAC Main.yaml"),
             user_config_path: config_dir.join("AutoQAC Config.yaml"),
             ignore_config_path: config_dir.join("PACT Ignore.yaml"),
+            history_path: config_dir.join("AutoQAC History.yaml"),
+            checkpoint_path: config_dir.join("AutoQAC Session.yaml"),
             config_dir,
         })
     }
@@ -51,7 +105,7 @@ impl ConfigManager {
                 "Main config file not found at {}, using defaults",
                 self.main_config_path
             );
-            return Ok(self.create_default_main_config()?);
+            return self.create_default_main_config();
         }
 
         let file_contents = fs::read_to_string(&self.main_config_path)
@@ -167,6 +221,101 @@ impl ConfigManager {
         Ok(())
     }
 
+    /// Load the cleaning history.
+    ///
+    /// # Returns
+    /// The loaded CleaningHistory, or an empty one if the file doesn't exist
+    pub fn load_cleaning_history(&self) -> Result<CleaningHistory> {
+        if !self.history_path.exists() {
+            return Ok(CleaningHistory::default());
+        }
+
+        let file_contents = fs::read_to_string(&self.history_path)
+            .with_context(|| format!("Failed to read cleaning history: {}", self.history_path))?;
+
+        serde_yaml_ng::from_str(&file_contents)
+            .with_context(|| format!("Failed to parse cleaning history: {}", self.history_path))
+    }
+
+    /// Save the cleaning history.
+    ///
+    /// # Arguments
+    /// * `history` - The CleaningHistory to save
+    pub fn save_cleaning_history(&self, history: &CleaningHistory) -> Result<()> {
+        let yaml_string = serde_yaml_ng::to_string(history)
+            .context("Failed to serialize cleaning history to YAML")?;
+
+        fs::write(&self.history_path, yaml_string)
+            .with_context(|| format!("Failed to write cleaning history: {}", self.history_path))?;
+
+        tracing::debug!("Saved cleaning history to {}", self.history_path);
+        Ok(())
+    }
+
+    /// Load the checkpoint of an interrupted cleaning run.
+    ///
+    /// # Returns
+    /// The checkpoint, or None if no run was interrupted
+    pub fn load_checkpoint(&self) -> Result<Option<SessionCheckpoint>> {
+        if !self.checkpoint_path.exists() {
+            return Ok(None);
+        }
+
+        let file_contents = fs::read_to_string(&self.checkpoint_path).with_context(|| {
+            format!(
+                "Failed to read session checkpoint: {}",
+                self.checkpoint_path
+            )
+        })?;
+
+        serde_yaml_ng::from_str(&file_contents)
+            .map(Some)
+            .with_context(|| {
+                format!(
+                    "Failed to parse session checkpoint: {}",
+                    self.checkpoint_path
+                )
+            })
+    }
+
+    /// Save the checkpoint of the current cleaning run.
+    ///
+    /// The file is written next to the checkpoint and renamed over it, so a crash while
+    /// saving leaves the previous checkpoint intact.
+    ///
+    /// # Arguments
+    /// * `checkpoint` - The SessionCheckpoint to save
+    pub fn save_checkpoint(&self, checkpoint: &SessionCheckpoint) -> Result<()> {
+        let yaml_string = serde_yaml_ng::to_string(checkpoint)
+            .context("Failed to serialize session checkpoint to YAML")?;
+
+        let temp_path = self.checkpoint_path.with_extension("yaml.tmp");
+        fs::write(&temp_path, yaml_string)
+            .with_context(|| format!("Failed to write session checkpoint: {}", temp_path))?;
+        fs::rename(&temp_path, &self.checkpoint_path).with_context(|| {
+            format!(
+                "Failed to write session checkpoint: {}",
+                self.checkpoint_path
+            )
+        })?;
+
+        tracing::debug!("Saved session checkpoint to {}", self.checkpoint_path);
+        Ok(())
+    }
+
+    /// Remove the checkpoint once a run has ended or is not going to be resumed.
+    pub fn clear_checkpoint(&self) -> Result<()> {
+        if self.checkpoint_path.exists() {
+            fs::remove_file(&self.checkpoint_path).with_context(|| {
+                format!(
+                    "Failed to remove session checkpoint: {}",
+                    self.checkpoint_path
+                )
+            })?;
+        }
+        Ok(())
+    }
+
     /// Create a default main configuration with full skip lists from the existing config.
     ///
     /// This is used when the main config file doesn't exist.
@@ -175,6 +324,10 @@ impl ConfigManager {
         use indexmap::IndexMap;
 
         let mut xedit_lists = IndexMap::new();
+        xedit_lists.insert(
+            "Oblivion".to_string(),
+            vec!["TES4Edit.exe".to_string(), "TES4Edit64.exe".to_string()],
+        );
         xedit_lists.insert(
             "FO3".to_string(),
             vec!["FO3Edit.exe".to_string(), "FO3Edit64.exe".to_string()],
@@ -195,7 +348,22 @@ impl ConfigManager {
             "FO4VR".to_string(),
             vec!["FO4VREdit.exe".to_string(), "FO4VREdit64.exe".to_string()],
         );
+        xedit_lists.insert(
+            "SkyrimLE".to_string(),
+            vec!["TES5Edit.exe".to_string(), "TES5Edit64.exe".to_string()],
+        );
         xedit_lists.insert("SkyrimVR".to_string(), vec!["TES5VREdit.exe".to_string()]);
+        xedit_lists.insert(
+            "Starfield".to_string(),
+            vec!["SF1Edit.exe".to_string(), "SF1Edit64.exe".to_string()],
+        );
+        xedit_lists.insert(
+            "Enderal".to_string(),
+            vec![
+                "EnderalSEEdit.exe".to_string(),
+                "EnderalSEEdit64.exe".to_string(),
+            ],
+        );
         xedit_lists.insert(
             "Universal".to_string(),
             vec![
@@ -286,6 +454,113 @@ impl ConfigManager {
             ],
         );
 
+        // Oblivion skip list
+        skip_lists.insert(
+            "Oblivion".to_string(),
+            vec![
+                "".to_string(),
+                "Oblivion.esm".to_string(),
+                "DLCShiveringIsles.esp".to_string(),
+                "Knights.esp".to_string(),
+                "DLCBattlehornCastle.esp".to_string(),
+                "DLCFrostcrag.esp".to_string(),
+                "DLCHorseArmor.esp".to_string(),
+                "DLCMehrunesRazor.esp".to_string(),
+                "DLCOrrery.esp".to_string(),
+                "DLCSpellTomes.esp".to_string(),
+                "DLCThievesDen.esp".to_string(),
+                "DLCVileLair.esp".to_string(),
+                "Unofficial Oblivion Patch.esp".to_string(),
+            ],
+        );
+
+        // SkyrimLE skip list
+        skip_lists.insert(
+            "SkyrimLE".to_string(),
+            vec![
+                "".to_string(),
+                "Skyrim.esm".to_string(),
+                "Update.esm".to_string(),
+                "Dawnguard.esm".to_string(),
+                "HearthFires.esm".to_string(),
+                "Dragonborn.esm".to_string(),
+                "HighResTexturePack01.esp".to_string(),
+                "HighResTexturePack02.esp".to_string(),
+                "HighResTexturePack03.esp".to_string(),
+                "Unofficial Skyrim Legendary Edition Patch.esp".to_string(),
+            ],
+        );
+
+        // FO4VR skip list
+        skip_lists.insert(
+            "FO4VR".to_string(),
+            vec![
+                "".to_string(),
+                "Fallout4.esm".to_string(),
+                "Fallout4_VR.esm".to_string(),
+                "Unofficial Fallout 4 Patch.esp".to_string(),
+            ],
+        );
+
+        // SkyrimVR skip list
+        skip_lists.insert(
+            "SkyrimVR".to_string(),
+            vec![
+                "".to_string(),
+                "Skyrim.esm".to_string(),
+                "Update.esm".to_string(),
+                "HearthFires.esm".to_string(),
+                "Dragonborn.esm".to_string(),
+                "Dawnguard.esm".to_string(),
+                "SkyrimVR.esm".to_string(),
+                "Unofficial Skyrim Special Edition Patch.esp".to_string(),
+            ],
+        );
+
+        // Starfield skip list
+        skip_lists.insert(
+            "Starfield".to_string(),
+            vec![
+                "".to_string(),
+                "Starfield.esm".to_string(),
+                "Constellation.esm".to_string(),
+                "OldMars.esm".to_string(),
+                "BlueprintShips-Starfield.esm".to_string(),
+                "SFBGS003.esm".to_string(),
+                "SFBGS004.esm".to_string(),
+                "SFBGS006.esm".to_string(),
+                "SFBGS007.esm".to_string(),
+                "SFBGS008.esm".to_string(),
+                "ShatteredSpace.esm".to_string(),
+            ],
+        );
+
+        // Total conversions; the base game's skip list applies as well
+        skip_lists.insert(
+            "TTW".to_string(),
+            vec![
+                "".to_string(),
+                "TaleOfTwoWastelands.esm".to_string(),
+                "YUPTTW.esm".to_string(),
+                "TTWInteriors_Core.esm".to_string(),
+                "TTWInteriors_Core_Hotfix.esm".to_string(),
+                "TTWInteriorsProject_Combo.esm".to_string(),
+                "TTWInteriorsProject_ComboHotfix.esm".to_string(),
+                "TTWInteriorsProject_Merged.esm".to_string(),
+            ],
+        );
+        skip_lists.insert(
+            "Enderal".to_string(),
+            vec![
+                "".to_string(),
+                "Enderal - Forgotten Stories.esm".to_string(),
+            ],
+        );
+        skip_lists.insert(
+            "London".to_string(),
+            vec!["".to_string(), "LondonWorldSpace.esm".to_string()],
+        );
+
         let pact_data = PactData {
             version: "3.0.0".to_string(),
             version_date: "25.01.14".to_string(),
@@ -295,6 +570,7 @@ impl ConfigManager {
             skip_lists,
             errors: IndexMap::new(),
             warnings: IndexMap::new(),
+            games: Vec::new(),
         };
 
         Ok(MainConfig { pact_data })
@@ -323,6 +599,15 @@ mod tests {
         let (_manager, _temp_dir) = create_test_config_manager();
     }
 
+    #[test]
+    fn test_app_paths_beside_executable() {
+        let paths = AppPaths::beside_executable().unwrap();
+        let exe = Utf8PathBuf::try_from(std::env::current_exe().unwrap()).unwrap();
+        assert_eq!(paths, AppPaths::new(exe.parent().unwrap()));
+        assert!(paths.config.is_absolute());
+        assert!(paths.sessions.starts_with(&paths.logs));
+    }
+
     #[test]
     fn test_load_save_user_config() {
         let (manager, _temp_dir) = create_test_config_manager();
@@ -334,6 +619,21 @@ mod tests {
         assert_eq!(loaded.pact_settings.cleaning_timeout, 300);
     }
 
+    #[test]
+    fn test_load_save_cleaning_history() {
+        let (manager, _temp_dir) = create_test_config_manager();
+        assert_eq!(
+            manager.load_cleaning_history().unwrap(),
+            CleaningHistory::default()
+        );
+
+        let mut history = CleaningHistory::default();
+        history.record("MyPlugin.esp", std::time::Duration::from_secs(42));
+        manager.save_cleaning_history(&history).unwrap();
+
+        assert_eq!(manager.load_cleaning_history().unwrap(), history);
+    }
+
     #[test]
     fn test_load_save_ignore_config() {
         let (manager, _temp_dir) = create_test_config_manager();
@@ -357,5 +657,13 @@ mod tests {
         let fo4_skip = config.pact_data.skip_lists.get("FO4").unwrap();
         assert!(fo4_skip.contains(&"Fallout4.esm".to_string()));
         assert!(fo4_skip.contains(&"DLCCoast.esm".to_string()));
+
+        // VR games have their own skip lists
+        let vr_skip = config.pact_data.skip_lists.get("SkyrimVR").unwrap();
+        assert!(vr_skip.contains(&"SkyrimVR.esm".to_string()));
+        assert!(config.pact_data.skip_lists.contains_key("FO4VR"));
+        for conversion in ["TTW", "Enderal", "London"] {
+            assert!(config.pact_data.skip_lists.contains_key(conversion));
+        }
     }
 }
@@ -0,0 +1,390 @@
+//! Translations for text shown by the Rust side of the GUI.
+//!
+//! The Slint UI is translated with `@tr` and the same gettext catalogs, bundled at build time
+//! from `lang/<language>/LC_MESSAGES/autoqac.po`. This module reads those catalogs for the
+//! status text and other strings built in Rust, so both sides switch language together.
+//!
+//! Messages from the `Errors` and `Warnings` of `AutoQAC Main.yaml` are not translated
+//! here; they are edited in that file instead.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::i18n;
+//!
+//! i18n::set_language("de");
+//! assert_eq!(i18n::tr("GuiController", "Starting cleaning..."), "Reinigung startet...");
+//! println!("{}", i18n::tr_format("GuiController", "Completed: {}", &[&"MyPlugin.esp"]));
+//! ```
+
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, RwLock};
+
+/// Language used when the setting and the system locale are not supported
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Supported languages as (code, native name), in the order the GUI lists them
+pub const LANGUAGES: &[(&str, &str)] = &[("en", "English"), ("de", "Deutsch")];
+
+/// Catalogs compiled into the binary (English is the source language and has none)
+const BUNDLED_CATALOGS: &[(&str, &str)] =
+    &[("de", include_str!("../lang/de/LC_MESSAGES/autoqac.po"))];
+
+static CATALOGS: LazyLock<HashMap<&'static str, Catalog>> = LazyLock::new(|| {
+    BUNDLED_CATALOGS
+        .iter()
+        .filter_map(|(language, po)| match Catalog::parse(po) {
+            Ok(catalog) => Some((*language, catalog)),
+            Err(e) => {
+                tracing::error!("Failed to parse {} translations: {}", language, e);
+                None
+            }
+        })
+        .collect()
+});
+
+static CURRENT_LANGUAGE: RwLock<&'static str> = RwLock::new(DEFAULT_LANGUAGE);
+
+/// Translated strings from one gettext `.po` file, keyed by (context, source text)
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    messages: HashMap<(String, String), String>,
+}
+
+impl Catalog {
+    /// Parse the singular entries of a `.po` file.
+    ///
+    /// Plural entries, the header, untranslated and fuzzy entries are skipped.
+    ///
+    /// # Errors
+    /// Returns an error if a keyword or string line is malformed
+    pub fn parse(po: &str) -> Result<Self> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Field {
+            None,
+            Context,
+            Id,
+            Str,
+            Plural,
+        }
+
+        let mut messages = HashMap::new();
+        let (mut context, mut id, mut text) = (String::new(), String::new(), String::new());
+        let mut field = Field::None;
+        let mut has_id = false;
+        // Flags are written above the entry they belong to, before its msgctxt or msgid
+        let (mut fuzzy, mut next_fuzzy) = (false, false);
+
+        let mut finish = |context: &mut String, id: &mut String, text: &mut String, fuzzy: bool| {
+            if !id.is_empty() && !text.is_empty() && !fuzzy {
+                messages.insert(
+                    (std::mem::take(context), std::mem::take(id)),
+                    std::mem::take(text),
+                );
+            }
+            context.clear();
+            id.clear();
+            text.clear();
+        };
+
+        for (number, line) in po.lines().enumerate() {
+            let line = line.trim();
+            if let Some(flags) = line.strip_prefix("#,") {
+                next_fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (keyword, value) = match line.split_once(char::is_whitespace) {
+                Some((keyword, value)) if !line.starts_with('"') => (keyword, value.trim()),
+                _ => ("", line),
+            };
+            let value = unquote(value).ok_or_else(|| {
+                anyhow::anyhow!("Invalid string on line {}: {}", number + 1, line)
+            })?;
+
+            match keyword {
+                "msgctxt" => {
+                    finish(&mut context, &mut id, &mut text, fuzzy);
+                    fuzzy = std::mem::take(&mut next_fuzzy);
+                    has_id = false;
+                    field = Field::Context;
+                    context = value;
+                }
+                "msgid" => {
+                    if has_id {
+                        finish(&mut context, &mut id, &mut text, fuzzy);
+                    }
+                    if field != Field::Context {
+                        fuzzy = std::mem::take(&mut next_fuzzy);
+                    }
+                    has_id = true;
+                    field = Field::Id;
+                    id = value;
+                }
+                "msgstr" => {
+                    field = Field::Str;
+                    text = value;
+                }
+                "" => match field {
+                    Field::Context => context.push_str(&value),
+                    Field::Id => id.push_str(&value),
+                    Field::Str => text.push_str(&value),
+                    Field::Plural => {}
+                    Field::None => bail!("String without keyword on line {}", number + 1),
+                },
+                keyword
+                    if keyword.starts_with("msgid_plural") || keyword.starts_with("msgstr[") =>
+                {
+                    // Plural forms are only used by the Slint side
+                    field = Field::Plural;
+                    text.clear();
+                    id.clear();
+                }
+                keyword => bail!("Unknown keyword on line {}: {}", number + 1, keyword),
+            }
+        }
+        finish(&mut context, &mut id, &mut text, fuzzy);
+
+        Ok(Self { messages })
+    }
+
+    /// Translate a string, or return it unchanged if the catalog has no translation
+    pub fn translate<'a>(&'a self, context: &str, text: &'a str) -> &'a str {
+        self.messages
+            .get(&(context.to_string(), text.to_string()))
+            .map_or(text, String::as_str)
+    }
+
+    /// Number of translated entries
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether the catalog has no translations
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+/// Catalog bundled for a language (None for English and unsupported languages)
+pub fn bundled(language: &str) -> Option<&'static Catalog> {
+    CATALOGS.get(language)
+}
+
+/// Pick the supported language for a `Language` setting.
+///
+/// An empty setting follows the system locale. Regions are ignored (`de-AT` is `de`), and
+/// unsupported languages fall back to English.
+pub fn resolve_language(setting: &str) -> &'static str {
+    let requested = match setting.trim() {
+        "" => sys_locale::get_locale().unwrap_or_default(),
+        setting => setting.to_string(),
+    };
+    let primary = requested
+        .split(['-', '_', '.'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    LANGUAGES
+        .iter()
+        .find(|(code, _)| *code == primary)
+        .map_or(DEFAULT_LANGUAGE, |(code, _)| code)
+}
+
+/// Switch the language for Rust-side text and return the language that was selected
+pub fn set_language(setting: &str) -> &'static str {
+    let language = resolve_language(setting);
+    *CURRENT_LANGUAGE
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = language;
+
+    tracing::info!("UI language: {}", language);
+    language
+}
+
+/// Currently selected language code
+pub fn language() -> &'static str {
+    *CURRENT_LANGUAGE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Translate a string into the current language
+pub fn tr(context: &str, text: &str) -> String {
+    match bundled(language()) {
+        Some(catalog) => catalog.translate(context, text).to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// Translate a string and fill in its `{}` / `{0}` placeholders, like Slint's `@tr`
+pub fn tr_format(context: &str, text: &str, args: &[&dyn fmt::Display]) -> String {
+    format_placeholders(&tr(context, text), args)
+}
+
+/// Replace `{}` (next argument) and `{n}` (argument n) placeholders; `{{` and `}}` escape
+fn format_placeholders(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    let mut next = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '{' => {
+                let mut index = String::new();
+                while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                    index.push(d);
+                    chars.next();
+                }
+                if chars.peek() != Some(&'}') {
+                    out.push('{');
+                    out.push_str(&index);
+                    continue;
+                }
+                chars.next();
+
+                let arg = match index.parse::<usize>() {
+                    Ok(i) => args.get(i),
+                    Err(_) => {
+                        next += 1;
+                        args.get(next - 1)
+                    }
+                };
+                if let Some(arg) = arg {
+                    out.push_str(&arg.to_string());
+                }
+            }
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Decode a quoted `.po` string with C escapes
+fn unquote(value: &str) -> Option<String> {
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            other => out.push(other),
+        }
+    }
+
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PO: &str = r#"
+# Header
+msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+
+msgctxt "MainWindow"
+msgid "Browse"
+msgstr "Durchsuchen"
+
+msgctxt "GuiController"
+msgid ""
+"Ready to clean "
+"{} plugins"
+msgstr "Bereit, {} Plugins zu reinigen"
+
+#, fuzzy
+msgctxt "GuiController"
+msgid "Untranslated"
+msgstr ""
+
+msgctxt "MainWindow"
+msgid "{n} plugin"
+msgid_plural "{n} plugins"
+msgstr[0] "{n} Plugin"
+msgstr[1] "{n} Plugins"
+
+msgid "Quote \"me\"\n"
+msgstr "Zitat \"mich\"\n"
+
+#, c-format, fuzzy
+msgid "Needs review"
+msgstr "Muss geprüft werden"
+"#;
+
+    #[test]
+    fn test_parse_po_catalog() {
+        let catalog = Catalog::parse(PO).unwrap();
+
+        assert_eq!(catalog.len(), 3);
+        assert_eq!(catalog.translate("MainWindow", "Browse"), "Durchsuchen");
+        assert_eq!(
+            catalog.translate("GuiController", "Ready to clean {} plugins"),
+            "Bereit, {} Plugins zu reinigen"
+        );
+        assert_eq!(catalog.translate("", "Quote \"me\"\n"), "Zitat \"mich\"\n");
+
+        // Other contexts and untranslated entries keep the source text
+        assert_eq!(catalog.translate("GuiController", "Browse"), "Browse");
+        assert_eq!(
+            catalog.translate("GuiController", "Untranslated"),
+            "Untranslated"
+        );
+
+        // Fuzzy entries are not used even when they have a translation
+        assert_eq!(catalog.translate("", "Needs review"), "Needs review");
+
+        assert!(Catalog::parse("msgid \"unterminated").is_err());
+    }
+
+    #[test]
+    fn test_format_placeholders() {
+        assert_eq!(format_placeholders("{} of {}", &[&1, &"two"]), "1 of two");
+        assert_eq!(format_placeholders("{1} vor {0}", &[&"a", &"b"]), "b vor a");
+        assert_eq!(format_placeholders("{{}} {x}", &[&1]), "{} {x}");
+    }
+
+    #[test]
+    fn test_resolve_language() {
+        assert_eq!(resolve_language("de"), "de");
+        assert_eq!(resolve_language("de-AT"), "de");
+        assert_eq!(resolve_language("de_DE.UTF-8"), "de");
+        assert_eq!(resolve_language("EN"), "en");
+        assert_eq!(resolve_language("tlh"), DEFAULT_LANGUAGE);
+    }
+
+    #[test]
+    fn test_bundled_german_catalog() {
+        let catalog = bundled("de").expect("German catalog is bundled");
+        assert!(!catalog.is_empty());
+        assert_ne!(
+            catalog.translate("GuiController", "Starting cleaning..."),
+            "Starting cleaning..."
+        );
+        assert!(bundled("en").is_none());
+    }
+}
@@ -19,6 +19,7 @@
 //! - [`services`]: Pure business logic for plugin cleaning (framework-agnostic)
 //! - [`ui`]: Slint GUI integration and event loop coordination
 //! - [`logging`]: Structured logging setup with file rotation
+//! - [`i18n`]: Translations for Rust-side UI text and the selected language
 //!
 //! # Threading Model
 //!
@@ -32,7 +33,7 @@
 //! ## Basic Usage (Library)
 //!
 //! ```ignore
-//! use autoqac::{StateManager, ConfigManager, services::CleaningService};
+//! use autoqac::{StateManager, ConfigManager, services::{CleaningService, HangDetector, Runner}};
 //! use std::sync::Arc;
 //!
 //! #[tokio::main]
@@ -59,7 +60,9 @@
 //!
 //!     let exit_code = service.execute_cleaning_command(
 //!         &command,
+//!         &Runner::default(), // Native; Wine or Proton on Linux
 //!         std::time::Duration::from_secs(300),
+//!         HangDetector::new(5, std::time::Duration::from_secs(120)),
 //!     ).await?;
 //!
 //!     println!("xEdit exited with code: {}", exit_code);
@@ -125,12 +128,20 @@
 //!
 //! # Supported Games
 //!
+//! - Oblivion (Oblivion)
 //! - Fallout 3 (FO3)
 //! - Fallout New Vegas (FNV)
 //! - Fallout 4 (FO4)
+//! - Skyrim Legendary Edition (SkyrimLE)
 //! - Skyrim Special Edition (SSE)
 //! - Fallout 4 VR (FO4VR)
 //! - Skyrim VR (SkyrimVR)
+//! - Starfield (Starfield)
+//! - Tale of Two Wastelands (TTW), Enderal (Enderal) and Fallout: London (London), layered
+//!   on their base game
+//!
+//! Game knowledge lives in [`GameRegistry`](models::GameRegistry); additional games can be
+//! defined under `Games` in `AutoQAC Main.yaml`.
 //!
 //! # xEdit Integration
 //!
@@ -165,6 +176,7 @@
 //! - **Logging**: [`tracing`] with file rotation and JSON support
 
 pub mod config;
+pub mod i18n;
 pub mod logging;
 pub mod metrics;
 pub mod models;
@@ -173,7 +185,7 @@ pub mod state;
 pub mod ui;
 
 // Re-export commonly used types for convenience
-pub use config::ConfigManager;
+pub use config::{AppPaths, ConfigManager};
 pub use metrics::Metrics;
 pub use models::{AppState, IgnoreConfig, MainConfig, UserConfig};
 pub use state::{StateChange, StateManager};
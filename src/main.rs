@@ -18,19 +18,24 @@
 //!
 //! # Execution Flow
 //!
-//! 1. Initialize logging → logs/autoqac_<timestamp>.log
+//! 1. Find the `AutoQAC Data`, `logs` and `logs/sessions` folders next to the executable
+//!    ([`AppPaths`]) and initialize logging → logs/autoqac_<timestamp>.log
 //! 2. Create tokio runtime with 4 worker threads
 //! 3. Create StateManager (Arc<RwLock<AppState>>)
 //! 4. Load YAML configurations from AutoQAC Data/
 //!    - AutoQAC Main.yaml → Game configs, skip lists
 //!    - AutoQAC Config.yaml or PACT Settings.yaml → User settings
-//! 5. Create GuiController (wires Slint UI to state and runtime)
-//! 6. Run Slint event loop (blocks until window closed)
-//! 7. Shutdown tokio runtime with 5s timeout
+//! 5. With `--check`, print the configuration diagnostics and exit without the GUI; with
+//!    `--dry-run`, print which plugins a cleaning run would clean and exit
+//! 6. Create GuiController (wires Slint UI to state and runtime); with a plugin path or name
+//!    as argument, the GUI cleans just that plugin as soon as the window shows, or shows
+//!    why it cannot be cleaned
+//! 7. Run Slint event loop (blocks until window closed)
+//! 8. Shutdown tokio runtime with 5s timeout
 //!
 //! # Configuration Files
 //!
-//! Expected in `AutoQAC Data/` directory:
+//! Expected in the `AutoQAC Data/` directory next to the executable:
 //! - `AutoQAC Main.yaml`: Game configurations, xEdit paths, skip lists
 //! - `AutoQAC Config.yaml` or `PACT Settings.yaml`: User preferences
 //! - `PACT Ignore.yaml`: Additional plugin ignore list (optional)
@@ -40,11 +45,19 @@
 //! Primary platform: Windows 10/11 (x86_64)
 //! Secondary: Cross-platform via Slint and tokio
 
-use anyhow::Result;
+use anyhow::{Result, bail};
+use autoqac::models::{GameRegistry, IgnoreConfig};
+use autoqac::services::validation::{ConfigPaths, validate_command, validate_configuration};
 use autoqac::ui::GuiController;
-use autoqac::{APP_NAME, ConfigManager, StateManager, VERSION};
+use autoqac::{APP_NAME, AppPaths, ConfigManager, MainConfig, StateManager, VERSION, i18n};
 use std::sync::Arc;
 
+/// Command-line flag that validates the configuration instead of opening the GUI
+const CHECK_FLAG: &str = "--check";
+
+/// Command-line flag that prints the cleaning plan instead of opening the GUI
+const DRY_RUN_FLAG: &str = "--dry-run";
+
 /// Main entry point for the AutoQAC GUI application
 ///
 /// This function orchestrates the complete application lifecycle:
@@ -70,7 +83,10 @@ use std::sync::Arc;
 fn main() -> Result<()> {
     // Setup logging with both file and console output
     // CRITICAL: Must hold _log_guard for the entire program lifetime to keep logging active
-    let _log_guard = autoqac::logging::setup_logging_with_console("logs", "autoqac", false, true)?;
+    // Files live next to the executable; Explorer may start it in any working directory
+    let paths = AppPaths::beside_executable()?;
+    let _log_guard =
+        autoqac::logging::setup_logging_with_console(paths.logs.as_str(), "autoqac", false, true)?;
 
     tracing::info!("Starting {} v{}", APP_NAME, VERSION);
 
@@ -86,10 +102,11 @@ fn main() -> Result<()> {
 
     // Create state manager for application state
     let state_manager = Arc::new(StateManager::new());
+    state_manager.update(|s| s.sessions_dir = paths.sessions.clone());
     tracing::info!("State manager initialized");
 
     // Create configuration manager
-    let config_manager = ConfigManager::new("AutoQAC Data")?;
+    let config_manager = ConfigManager::new(&paths.config)?;
 
     // Load configurations
     let main_config = config_manager.load_main_config()?;
@@ -101,10 +118,38 @@ fn main() -> Result<()> {
         main_config.pact_data.xedit_lists.len()
     );
 
+    // Pick the UI language before any text is built
+    autoqac::i18n::set_language(&user_config.pact_settings.language);
+
     // Load user config into state manager
     state_manager.load_from_user_config(&user_config);
     tracing::info!("User configuration loaded into state manager");
 
+    // A plugin path or name (command line, "Open with" or a file dropped on the executable)
+    // is cleaned on its own instead of the load order
+    let single_plugin_error = std::env::args()
+        .skip(1)
+        .find(|arg| !arg.starts_with("--"))
+        .and_then(|target| {
+            GuiController::select_single_plugin(&state_manager, &target)
+                .err()
+                .map(|e| (target, e))
+        });
+
+    let check = std::env::args().skip(1).any(|arg| arg == CHECK_FLAG);
+    let dry_run = std::env::args().skip(1).any(|arg| arg == DRY_RUN_FLAG);
+    if (check || dry_run)
+        && let Some((_, e)) = single_plugin_error
+    {
+        return Err(e);
+    }
+    if check {
+        return check_configuration(&state_manager, &main_config);
+    }
+    if dry_run {
+        return print_cleaning_plan(&state_manager, &config_manager, &main_config);
+    }
+
     // Create GUI controller
     // This wires up the Slint UI with state management and the tokio runtime
     let gui_controller = GuiController::new(
@@ -114,6 +159,12 @@ fn main() -> Result<()> {
         runtime.handle().clone(),
     )?;
 
+    // Without a console ("Open with", a drop on the executable) a refused plugin is only
+    // seen in the window
+    if let Some((target, e)) = &single_plugin_error {
+        gui_controller.report_single_plugin_error(target, e);
+    }
+
     tracing::info!("GUI controller initialized, launching window");
 
     // Run the GUI (blocks until window is closed)
@@ -142,3 +193,107 @@ fn main() -> Result<()> {
         anyhow::anyhow!("GUI error: {}", e)
     })
 }
+
+/// Print the configuration diagnostics for `--check`
+///
+/// # Errors
+///
+/// Returns an error (and so a non-zero exit code) if any diagnostic is an error
+fn check_configuration(state_manager: &StateManager, main_config: &MainConfig) -> Result<()> {
+    let registry = GameRegistry::from_main_config(main_config);
+    let paths = state_manager.read(|s| ConfigPaths {
+        xedit: s.xedit_exe_path.clone(),
+        load_order: s.load_order_path.clone(),
+        data: s.data_path.clone(),
+    });
+
+    let mut diagnostics = validate_configuration(&registry, main_config, &paths);
+    let options = state_manager.read(|s| s.command_options.clone());
+    if let Some(diagnostic) = validate_command(main_config, &options) {
+        diagnostics.insert(0, diagnostic);
+    }
+    if diagnostics.is_empty() {
+        println!("{}", i18n::tr("GuiController", "Configuration OK"));
+        return Ok(());
+    }
+
+    for diagnostic in &diagnostics {
+        println!("{}\n{}\n", diagnostic, diagnostic.message);
+    }
+
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    if errors > 0 {
+        bail!(i18n::tr_format(
+            "GuiController",
+            "{} configuration error(s) found",
+            &[&errors]
+        ));
+    }
+    Ok(())
+}
+
+/// Print which plugins a cleaning run would clean, and why the others are left out, for `--dry-run`
+///
+/// # Errors
+///
+/// Returns an error if no load order file is configured or found, or it cannot be read
+fn print_cleaning_plan(
+    state_manager: &StateManager,
+    config_manager: &ConfigManager,
+    main_config: &MainConfig,
+) -> Result<()> {
+    let registry = GameRegistry::from_main_config(main_config);
+    let ignore_config = config_manager.load_ignore_config().unwrap_or_else(|e| {
+        tracing::warn!("Failed to load ignore config, using defaults: {}", e);
+        IgnoreConfig::default()
+    });
+
+    let (game, plan) = GuiController::plan_cleaning(
+        &registry,
+        state_manager,
+        main_config,
+        &ignore_config,
+        config_manager,
+    )?;
+
+    let single = state_manager.read(|s| s.single_plugin.is_some());
+    let heading = match game {
+        Some(game) if single => i18n::tr_format("GuiController", "{} plugin:", &[&game.name]),
+        Some(game) => i18n::tr_format("GuiController", "{} load order:", &[&game.name]),
+        None if single => i18n::tr(
+            "GuiController",
+            "Plugin (game not detected, skip and ignore lists not applied):",
+        ),
+        None => i18n::tr(
+            "GuiController",
+            "Load order (game not detected, skip and ignore lists not applied):",
+        ),
+    };
+    println!("{}", heading);
+
+    let (clean, skip) = (
+        i18n::tr("GuiController", "clean"),
+        i18n::tr("GuiController", "skip"),
+    );
+    let width = clean.chars().count().max(skip.chars().count());
+    for entry in &plan.entries {
+        match entry.exclusion {
+            None => println!("  {:<width$}  {}", clean, entry.plugin),
+            Some(ref exclusion) => println!(
+                "  {:<width$}  {} ({})",
+                skip,
+                entry.plugin,
+                GuiController::exclusion_text(exclusion)
+            ),
+        }
+    }
+    println!(
+        "{}",
+        i18n::tr_format(
+            "GuiController",
+            "{} of {} plugin(s) will be cleaned",
+            &[&plan.plugins_to_clean().len(), &plan.entries.len()],
+        )
+    );
+    Ok(())
+}
@@ -1,5 +1,10 @@
+use super::{AdaptiveTimeout, CheckpointSettings, CleaningHistory, SessionCheckpoint};
+use crate::services::{
+    CleaningPlan, CleaningStats, CommandOptions, Exclusion, NavmeshReport, PluginQueue,
+    RetryPolicy, Runner, session::SESSIONS_DIR,
+};
 use camino::Utf8PathBuf;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
 /// Maximum number of concurrent xEdit subprocesses.
@@ -18,6 +23,59 @@ use std::time::Duration;
 /// - [`crate::ui::GuiController`] - Orchestrates the cleaning workflow with semaphore enforcement
 pub const MAX_CONCURRENT_XEDIT_PROCESSES: usize = 1;
 
+/// Status filter of the GUI plugin list, in the order the list offers them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PluginFilter {
+    #[default]
+    All,
+    Selected,
+    NotSelected,
+    /// Left out by the cleaning plan
+    LeftOut,
+    Cleaned,
+    Failed,
+    Skipped,
+}
+
+impl PluginFilter {
+    /// Every filter, in list order
+    pub const ALL: [PluginFilter; 7] = [
+        PluginFilter::All,
+        PluginFilter::Selected,
+        PluginFilter::NotSelected,
+        PluginFilter::LeftOut,
+        PluginFilter::Cleaned,
+        PluginFilter::Failed,
+        PluginFilter::Skipped,
+    ];
+
+    /// Filter at a list index; out-of-range indices show everything
+    pub fn from_index(index: i32) -> Self {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| Self::ALL.get(index).copied())
+            .unwrap_or_default()
+    }
+}
+
+/// A row of the GUI plugin list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginListItem<'a> {
+    pub plugin: &'a str,
+
+    /// Whether the next run cleans it
+    pub selected: bool,
+
+    /// Why the plan leaves it out; None for plugins in the queue
+    pub exclusion: Option<&'a Exclusion>,
+
+    /// Result of this session's run ("cleaned", "failed" or "skipped")
+    pub result: Option<&'static str>,
+
+    /// Mod the plugin was deployed from (Vortex mode)
+    pub source: Option<&'a str>,
+}
+
 /// Single source of truth for all application state.
 ///
 /// This struct mirrors the Python AppState dataclass and contains all
@@ -45,6 +103,10 @@ pub struct AppState {
     pub mo2_install_path: Option<Utf8PathBuf>,
     pub xedit_exe_path: Option<Utf8PathBuf>,
     pub xedit_install_path: Option<Utf8PathBuf>,
+    pub data_path: Option<Utf8PathBuf>,
+
+    // Folder the session folders of cleaning runs are created in
+    pub sessions_dir: Utf8PathBuf,
 
     // Configuration validity flags
     pub is_load_order_configured: bool,
@@ -61,11 +123,31 @@ pub struct AppState {
     pub total_plugins: usize,
     pub plugins_to_clean: Vec<String>,
 
+    // Latest cleaning plan, and the user's selection and order of the plugins it cleans
+    pub cleaning_plan: CleaningPlan,
+    pub plugin_queue: PluginQueue,
+
+    // Plugin cleaned on its own instead of the load order (command line or dropped file)
+    pub single_plugin: Option<String>,
+
+    // Checkpoint of the current run, or of an interrupted run the next start resumes
+    pub checkpoint: Option<SessionCheckpoint>,
+
     // Results
     pub cleaned_plugins: HashSet<String>,
     pub failed_plugins: HashSet<String>,
     pub skipped_plugins: HashSet<String>,
 
+    // Source mod of each plugin (Vortex mode only)
+    pub plugin_sources: HashMap<String, String>,
+
+    // Statistics of each QAC pass, for plugins cleaned in more than one pass
+    pub plugin_passes: HashMap<String, Vec<CleaningStats>>,
+
+    // Deleted navmeshes per plugin, and the plugins marked as needing manual attention
+    pub navmesh_reports: HashMap<String, NavmeshReport>,
+    pub needs_attention: HashSet<String>,
+
     // Per-plugin record statistics (reset for each plugin)
     pub current_undeleted: usize, // UDRs (Undeleted References)
     pub current_removed: usize,   // ITMs (Identical To Master)
@@ -83,10 +165,30 @@ pub struct AppState {
     // Settings
     pub journal_expiration: u32,
     pub cleaning_timeout: Duration,
+    pub adaptive_timeout: AdaptiveTimeout,
+    pub cleaning_passes: u32,
     pub cpu_threshold: u32,
+    pub hang_window: Duration,
     pub mo2_mode: bool,
+    pub vortex_mode: bool,
     pub partial_forms_enabled: bool,
+    pub flag_navmeshes: bool,
+    pub skip_unchanged: bool,
+    pub skip_loot_clean: bool,
+    pub loot_masterlist: Option<Utf8PathBuf>,
     pub game_type: Option<String>,
+
+    // How xEdit is launched (native, Wine or Proton)
+    pub runner: Runner,
+
+    // Extra xEdit arguments and the argument template
+    pub command_options: CommandOptions,
+
+    // When a failed xEdit run is retried
+    pub retry_policy: RetryPolicy,
+
+    // Recent run times per plugin, for adaptive timeouts
+    pub cleaning_history: CleaningHistory,
 }
 
 impl Default for AppState {
@@ -98,6 +200,8 @@ impl Default for AppState {
             mo2_install_path: None,
             xedit_exe_path: None,
             xedit_install_path: None,
+            data_path: None,
+            sessions_dir: Utf8PathBuf::from(SESSIONS_DIR),
 
             // Configuration validity
             is_load_order_configured: false,
@@ -114,11 +218,21 @@ impl Default for AppState {
             total_plugins: 0,
             plugins_to_clean: Vec::new(),
 
+            cleaning_plan: CleaningPlan::default(),
+            plugin_queue: PluginQueue::default(),
+            single_plugin: None,
+            checkpoint: None,
+
             // Results
             cleaned_plugins: HashSet::new(),
             failed_plugins: HashSet::new(),
             skipped_plugins: HashSet::new(),
 
+            plugin_sources: HashMap::new(),
+            plugin_passes: HashMap::new(),
+            navmesh_reports: HashMap::new(),
+            needs_attention: HashSet::new(),
+
             // Per-plugin record statistics
             current_undeleted: 0,
             current_removed: 0,
@@ -136,10 +250,23 @@ impl Default for AppState {
             // Settings with defaults matching Python version
             journal_expiration: 7,
             cleaning_timeout: Duration::from_secs(300),
+            adaptive_timeout: AdaptiveTimeout::default(),
+            cleaning_passes: 1,
             cpu_threshold: 5,
+            hang_window: Duration::from_secs(120),
             mo2_mode: false,
+            vortex_mode: false,
             partial_forms_enabled: false,
+            flag_navmeshes: true,
+            skip_unchanged: false,
+            skip_loot_clean: false,
+            loot_masterlist: None,
             game_type: None,
+
+            runner: Runner::default(),
+            command_options: CommandOptions::default(),
+            retry_policy: RetryPolicy::default(),
+            cleaning_history: CleaningHistory::default(),
         }
     }
 }
@@ -152,6 +279,71 @@ impl AppState {
         self.is_load_order_configured && self.is_mo2_configured && self.is_xedit_configured
     }
 
+    /// Check if a run can start: cleaning a single plugin or resuming an interrupted run does
+    /// not need the load order.
+    pub fn is_ready_to_clean(&self) -> bool {
+        self.is_fully_configured()
+            || (self.needs_no_load_order() && self.is_mo2_configured && self.is_xedit_configured)
+    }
+
+    /// Whether the next run cleans a single plugin or resumes an interrupted run, neither of
+    /// which reads the load order
+    pub fn needs_no_load_order(&self) -> bool {
+        self.single_plugin.is_some() || self.checkpoint.is_some()
+    }
+
+    /// Settings of the current run, saved with its checkpoint
+    pub fn checkpoint_settings(&self) -> CheckpointSettings {
+        CheckpointSettings {
+            game_type: self.game_type.clone(),
+            xedit_exe: self.xedit_exe_path.as_ref().map(ToString::to_string),
+            mo2_mode: self.mo2_mode,
+            mo2_exe: self.mo2_exe_path.as_ref().map(ToString::to_string),
+            data_path: self.data_path.as_ref().map(ToString::to_string),
+            vortex_mode: self.vortex_mode,
+            partial_forms: self.partial_forms_enabled,
+            cleaning_passes: self.cleaning_passes,
+        }
+    }
+
+    /// Go back to the settings an interrupted run was started with
+    pub fn apply_checkpoint_settings(&mut self, settings: &CheckpointSettings) {
+        if let Some(ref game_type) = settings.game_type {
+            self.game_type = Some(game_type.clone());
+        }
+        if let Some(ref xedit) = settings.xedit_exe {
+            self.xedit_exe_path = Some(Utf8PathBuf::from(xedit));
+            self.is_xedit_configured = true;
+        }
+        if let Some(ref mo2) = settings.mo2_exe {
+            self.mo2_exe_path = Some(Utf8PathBuf::from(mo2));
+            self.is_mo2_configured = true;
+        }
+        if let Some(ref data_path) = settings.data_path {
+            self.data_path = Some(Utf8PathBuf::from(data_path));
+        }
+        self.mo2_mode = settings.mo2_mode;
+        self.vortex_mode = settings.vortex_mode;
+        self.partial_forms_enabled = settings.partial_forms;
+        self.cleaning_passes = settings.cleaning_passes.max(1);
+    }
+
+    /// Put back the results of the plugins an interrupted run finished
+    pub fn restore_results(&mut self, checkpoint: &SessionCheckpoint) {
+        for (plugin, outcome) in &checkpoint.results {
+            self.add_result(plugin.clone(), &outcome.status);
+            if let Some(ref stats) = outcome.stats {
+                self.record_stats(stats);
+            }
+            if outcome.needs_attention {
+                self.needs_attention.insert(plugin.clone());
+            }
+            if let Some(ref source) = outcome.source {
+                self.plugin_sources.insert(plugin.clone(), source.clone());
+            }
+        }
+    }
+
     /// Get current cleaning statistics.
     ///
     /// Returns a tuple of (cleaned, failed, skipped, total).
@@ -177,6 +369,10 @@ impl AppState {
         self.cleaned_plugins.clear();
         self.failed_plugins.clear();
         self.skipped_plugins.clear();
+        self.plugin_sources.clear();
+        self.plugin_passes.clear();
+        self.navmesh_reports.clear();
+        self.needs_attention.clear();
 
         // Reset statistics
         self.reset_current_stats();
@@ -208,6 +404,17 @@ impl AppState {
         self.total_records_processed += self.current_total_processed;
     }
 
+    /// Make a plugin's statistics the current ones and add them to the totals.
+    pub fn record_stats(&mut self, stats: &CleaningStats) {
+        self.current_undeleted = stats.undeleted;
+        self.current_removed = stats.removed;
+        self.current_skipped = stats.skipped;
+        self.current_partial_forms = stats.partial_forms;
+        self.current_total_processed =
+            stats.undeleted + stats.removed + stats.skipped + stats.partial_forms;
+        self.aggregate_current_stats();
+    }
+
     /// Increment a specific statistic counter.
     ///
     /// This is used during xEdit output parsing to track individual operations.
@@ -293,6 +500,77 @@ impl AppState {
         }
     }
 
+    /// Get the mod a plugin was deployed from, if known.
+    ///
+    /// Only populated in Vortex mode, where the deployment manifest maps plugins to mods.
+    pub fn plugin_source(&self, plugin: &str) -> Option<&str> {
+        self.plugin_sources.get(plugin).map(String::as_str)
+    }
+
+    /// Plugins marked as needing manual attention, in cleaning order
+    pub fn attention_plugins(&self) -> Vec<String> {
+        self.plugins_to_clean
+            .iter()
+            .filter(|plugin| self.needs_attention.contains(*plugin))
+            .cloned()
+            .collect()
+    }
+
+    /// Result of a plugin in this session ("cleaned", "failed" or "skipped")
+    pub fn plugin_result(&self, plugin: &str) -> Option<&'static str> {
+        if self.cleaned_plugins.contains(plugin) {
+            Some("cleaned")
+        } else if self.failed_plugins.contains(plugin) {
+            Some("failed")
+        } else if self.skipped_plugins.contains(plugin) {
+            Some("skipped")
+        } else {
+            None
+        }
+    }
+
+    /// Rows of the GUI plugin list: the queue in cleaning order, then the plugins the plan
+    /// leaves out in load order, limited to names containing `name` (case-insensitive) and
+    /// to the status filter
+    pub fn plugin_list(&self, name: &str, filter: PluginFilter) -> Vec<PluginListItem<'_>> {
+        let queued = self
+            .plugin_queue
+            .entries
+            .iter()
+            .map(|entry| PluginListItem {
+                plugin: &entry.plugin,
+                selected: entry.selected,
+                exclusion: None,
+                result: self.plugin_result(&entry.plugin),
+                source: self.plugin_source(&entry.plugin),
+            });
+        let left_out = self
+            .cleaning_plan
+            .excluded()
+            .map(|(plugin, exclusion)| PluginListItem {
+                plugin,
+                selected: false,
+                exclusion: Some(exclusion),
+                result: self.plugin_result(plugin),
+                source: self.plugin_source(plugin),
+            });
+
+        let name = name.trim().to_lowercase();
+        queued
+            .chain(left_out)
+            .filter(|item| item.plugin.to_lowercase().contains(&name))
+            .filter(|item| match filter {
+                PluginFilter::All => true,
+                PluginFilter::Selected => item.selected,
+                PluginFilter::NotSelected => !item.selected && item.exclusion.is_none(),
+                PluginFilter::LeftOut => item.exclusion.is_some(),
+                PluginFilter::Cleaned => item.result == Some("cleaned"),
+                PluginFilter::Failed => item.result == Some("failed"),
+                PluginFilter::Skipped => item.result == Some("skipped"),
+            })
+            .collect()
+    }
+
     /// Add a plugin processing result.
     ///
     /// This mirrors the Python method `add_result`.
@@ -328,6 +606,63 @@ mod tests {
         assert_eq!(MAX_CONCURRENT_XEDIT_PROCESSES, 1);
     }
 
+    #[test]
+    fn test_single_plugin_needs_no_load_order() {
+        let mut state = AppState {
+            is_xedit_configured: true,
+            is_mo2_configured: true,
+            ..AppState::default()
+        };
+        assert!(!state.is_ready_to_clean());
+
+        state.single_plugin = Some("MyPlugin.esp".to_string());
+        assert!(state.is_ready_to_clean());
+        assert!(!state.is_fully_configured());
+    }
+
+    #[test]
+    fn test_restore_results_of_interrupted_run() {
+        use crate::models::PluginOutcome;
+
+        let mut checkpoint = SessionCheckpoint {
+            queue: vec!["A.esp".into(), "B.esp".into(), "C.esp".into()],
+            ..SessionCheckpoint::default()
+        };
+        checkpoint.record(
+            "A.esp",
+            PluginOutcome {
+                status: "cleaned".into(),
+                stats: Some(CleaningStats {
+                    removed: 4,
+                    skipped: 1,
+                    ..CleaningStats::default()
+                }),
+                needs_attention: true,
+                source: Some("Armor Mod".into()),
+                ..PluginOutcome::default()
+            },
+        );
+        checkpoint.record(
+            "B.esp",
+            PluginOutcome {
+                status: "failed".into(),
+                ..PluginOutcome::default()
+            },
+        );
+
+        let mut state = AppState {
+            plugins_to_clean: checkpoint.queue.clone(),
+            ..AppState::default()
+        };
+        state.restore_results(&checkpoint);
+        assert_eq!(state.progress, 2);
+        assert_eq!(state.plugin_result("A.esp"), Some("cleaned"));
+        assert_eq!(state.plugin_result("B.esp"), Some("failed"));
+        assert_eq!(state.total_removed, 4);
+        assert_eq!(state.attention_plugins(), ["A.esp"]);
+        assert_eq!(state.plugin_source("A.esp"), Some("Armor Mod"));
+    }
+
     #[test]
     fn test_is_fully_configured() {
         let mut state = AppState::default();
@@ -341,8 +676,10 @@ mod tests {
 
     #[test]
     fn test_cleaning_stats() {
-        let mut state = AppState::default();
-        state.total_plugins = 10;
+        let mut state = AppState {
+            total_plugins: 10,
+            ..Default::default()
+        };
         state.cleaned_plugins.insert("plugin1.esp".to_string());
         state.failed_plugins.insert("plugin2.esp".to_string());
         state.skipped_plugins.insert("plugin3.esp".to_string());
@@ -369,12 +706,17 @@ mod tests {
 
     #[test]
     fn test_reset_cleaning_state() {
-        let mut state = AppState::default();
-        state.is_cleaning = true;
-        state.current_plugin = Some("test.esp".to_string());
-        state.progress = 5;
-        state.total_plugins = 10;
+        let mut state = AppState {
+            is_cleaning: true,
+            current_plugin: Some("test.esp".to_string()),
+            progress: 5,
+            total_plugins: 10,
+            ..Default::default()
+        };
         state.cleaned_plugins.insert("plugin1.esp".to_string());
+        state
+            .plugin_sources
+            .insert("plugin1.esp".to_string(), "Plugin Mod".to_string());
 
         // Add some statistics
         state.current_removed = 10;
@@ -390,17 +732,20 @@ mod tests {
 
         // Verify statistics are reset
         assert_eq!(state.current_removed, 0);
+        assert!(state.plugin_sources.is_empty());
         assert_eq!(state.total_removed, 0);
     }
 
     #[test]
     fn test_reset_current_stats() {
-        let mut state = AppState::default();
-        state.current_undeleted = 5;
-        state.current_removed = 10;
-        state.current_skipped = 2;
-        state.current_partial_forms = 1;
-        state.current_total_processed = 18;
+        let mut state = AppState {
+            current_undeleted: 5,
+            current_removed: 10,
+            current_skipped: 2,
+            current_partial_forms: 1,
+            current_total_processed: 18,
+            ..Default::default()
+        };
 
         state.reset_current_stats();
 
@@ -413,14 +758,15 @@ mod tests {
 
     #[test]
     fn test_aggregate_current_stats() {
-        let mut state = AppState::default();
-
         // First plugin
-        state.current_undeleted = 5;
-        state.current_removed = 10;
-        state.current_skipped = 2;
-        state.current_partial_forms = 1;
-        state.current_total_processed = 18;
+        let mut state = AppState {
+            current_undeleted: 5,
+            current_removed: 10,
+            current_skipped: 2,
+            current_partial_forms: 1,
+            current_total_processed: 18,
+            ..Default::default()
+        };
         state.aggregate_current_stats();
 
         assert_eq!(state.total_undeleted, 5);
@@ -528,4 +874,86 @@ mod tests {
             "Total: 8 undeleted, 15 removed, 3 skipped, 4 partial forms"
         );
     }
+
+    #[test]
+    fn test_attention_plugins_in_cleaning_order() {
+        let mut state = AppState {
+            plugins_to_clean: vec![
+                "b.esp".to_string(),
+                "a.esp".to_string(),
+                "c.esp".to_string(),
+            ],
+            ..Default::default()
+        };
+        state.needs_attention.insert("c.esp".to_string());
+        state.needs_attention.insert("b.esp".to_string());
+
+        assert_eq!(state.attention_plugins(), ["b.esp", "c.esp"]);
+
+        state.reset_cleaning_state();
+        assert!(state.attention_plugins().is_empty());
+        assert!(state.needs_attention.is_empty());
+    }
+
+    #[test]
+    fn test_plugin_list_filters() {
+        use crate::services::PlanEntry;
+
+        let mut state = AppState {
+            cleaning_plan: CleaningPlan {
+                entries: vec![
+                    PlanEntry {
+                        plugin: "Skyrim.esm".to_string(),
+                        exclusion: Some(Exclusion::SkipList),
+                    },
+                    PlanEntry {
+                        plugin: "Armor.esp".to_string(),
+                        exclusion: None,
+                    },
+                    PlanEntry {
+                        plugin: "Weapons.esp".to_string(),
+                        exclusion: None,
+                    },
+                ],
+            },
+            ..AppState::default()
+        };
+        state.plugin_queue.sync(&state.cleaning_plan.clone());
+        state.plugin_queue.move_to("Weapons.esp", "Armor.esp");
+        state.plugin_queue.set_selected("Armor.esp", false);
+        state.add_result("Weapons.esp".to_string(), "cleaned");
+        state
+            .plugin_sources
+            .insert("Weapons.esp".to_string(), "Weapon Pack".to_string());
+
+        let names = |items: Vec<PluginListItem>| -> Vec<String> {
+            items.iter().map(|item| item.plugin.to_string()).collect()
+        };
+        assert_eq!(
+            names(state.plugin_list("", PluginFilter::All)),
+            ["Weapons.esp", "Armor.esp", "Skyrim.esm"]
+        );
+        assert_eq!(
+            names(state.plugin_list(" ARM", PluginFilter::All)),
+            ["Armor.esp"]
+        );
+        assert_eq!(
+            names(state.plugin_list("", PluginFilter::NotSelected)),
+            ["Armor.esp"]
+        );
+        assert_eq!(
+            names(state.plugin_list("", PluginFilter::LeftOut)),
+            ["Skyrim.esm"]
+        );
+        assert_eq!(
+            names(state.plugin_list("", PluginFilter::Cleaned)),
+            ["Weapons.esp"]
+        );
+        assert_eq!(
+            state.plugin_list("", PluginFilter::Cleaned)[0].source,
+            Some("Weapon Pack")
+        );
+        assert_eq!(PluginFilter::from_index(3), PluginFilter::LeftOut);
+        assert_eq!(PluginFilter::from_index(-1), PluginFilter::All);
+    }
 }
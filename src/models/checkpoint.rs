@@ -0,0 +1,137 @@
+use crate::services::cleaning::CleaningStats;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// Progress of a cleaning run, from AutoQAC Session.yaml
+///
+/// Saved after every plugin and removed when the run ends, so a checkpoint found at startup
+/// belongs to a run that was interrupted (a crash, a killed process or a power loss) and can
+/// be resumed from the next pending plugin.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionCheckpoint {
+    /// Session folder with the run's logs
+    #[serde(rename = "Session")]
+    pub session: String,
+
+    /// Plugins of the run, in the order they are cleaned
+    #[serde(rename = "Queue")]
+    pub queue: Vec<String>,
+
+    /// Outcome of each finished plugin, in the order they finished
+    #[serde(rename = "Results", default)]
+    pub results: IndexMap<String, PluginOutcome>,
+
+    #[serde(rename = "Settings", default)]
+    pub settings: CheckpointSettings,
+}
+
+/// Outcome of one plugin of a run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginOutcome {
+    /// "cleaned", "failed" or "skipped"
+    #[serde(rename = "Status")]
+    pub status: String,
+
+    #[serde(rename = "Message", default)]
+    pub message: String,
+
+    #[serde(rename = "Stats", default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<CleaningStats>,
+
+    /// Deleted navmeshes need a manual fix
+    #[serde(rename = "Needs Attention", default)]
+    pub needs_attention: bool,
+
+    /// Mod the plugin was deployed from (Vortex mode)
+    #[serde(
+        rename = "Source Mod",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub source: Option<String>,
+}
+
+/// Settings a run was started with, so the rest of it is cleaned the same way
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointSettings {
+    #[serde(rename = "Game Type", default)]
+    pub game_type: Option<String>,
+
+    #[serde(rename = "XEDIT EXE", default)]
+    pub xedit_exe: Option<String>,
+
+    #[serde(rename = "MO2 Mode", default)]
+    pub mo2_mode: bool,
+
+    #[serde(rename = "MO2 EXE", default)]
+    pub mo2_exe: Option<String>,
+
+    #[serde(rename = "Game Data Path", default)]
+    pub data_path: Option<String>,
+
+    #[serde(rename = "Vortex Mode", default)]
+    pub vortex_mode: bool,
+
+    #[serde(rename = "Partial Forms", default)]
+    pub partial_forms: bool,
+
+    #[serde(rename = "Cleaning Passes", default)]
+    pub cleaning_passes: u32,
+}
+
+impl SessionCheckpoint {
+    /// Remember the outcome of a plugin
+    pub fn record(&mut self, plugin: &str, outcome: PluginOutcome) {
+        self.results.insert(plugin.to_string(), outcome);
+    }
+
+    /// Plugins of the queue without an outcome yet, in queue order
+    pub fn pending(&self) -> Vec<String> {
+        self.queue
+            .iter()
+            .filter(|plugin| !self.results.contains_key(*plugin))
+            .cloned()
+            .collect()
+    }
+
+    /// Name of the session folder, which is the time the run started
+    pub fn session_name(&self) -> &str {
+        self.session
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(&self.session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let mut checkpoint = SessionCheckpoint {
+            session: "C:/AutoQAC/logs/sessions/2025-01-31_18-04-22".to_string(),
+            queue: vec!["A.esp".into(), "B.esp".into(), "C.esp".into()],
+            ..SessionCheckpoint::default()
+        };
+        checkpoint.record(
+            "B.esp",
+            PluginOutcome {
+                status: "cleaned".into(),
+                message: "Cleaned: 3 ITMs".into(),
+                stats: Some(CleaningStats {
+                    removed: 3,
+                    ..CleaningStats::default()
+                }),
+                needs_attention: false,
+                source: Some("Better Armor".into()),
+            },
+        );
+        assert_eq!(checkpoint.pending(), ["A.esp", "C.esp"]);
+        assert_eq!(checkpoint.session_name(), "2025-01-31_18-04-22");
+
+        let yaml = serde_yaml_ng::to_string(&checkpoint).unwrap();
+        let loaded: SessionCheckpoint = serde_yaml_ng::from_str(&yaml).unwrap();
+        assert_eq!(loaded, checkpoint);
+    }
+}
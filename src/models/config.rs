@@ -1,16 +1,18 @@
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
+use super::GameDefinition;
+
 /// Main configuration from AutoQAC Main.yaml
 ///
 /// Contains game configurations, skip lists, and XEdit executable lists.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MainConfig {
     #[serde(rename = "PACT_Data")]
     pub pact_data: PactData,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PactData {
     pub version: String,
     pub version_date: String,
@@ -29,12 +31,16 @@ pub struct PactData {
 
     #[serde(rename = "Warnings")]
     pub warnings: IndexMap<String, String>,
+
+    /// Game definitions that replace or extend the built-in ones
+    #[serde(rename = "Games", default, skip_serializing_if = "Vec::is_empty")]
+    pub games: Vec<GameDefinition>,
 }
 
 /// User configuration from AutoQAC Config.yaml or PACT Settings.yaml
 ///
 /// Contains user-specific settings and file paths.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UserConfig {
     #[serde(rename = "PACT_Settings")]
     pub pact_settings: PactSettings,
@@ -51,9 +57,37 @@ pub struct PactSettings {
     #[serde(rename = "Cleaning Timeout", default = "default_cleaning_timeout")]
     pub cleaning_timeout: u32,
 
+    /// How each plugin's timeout is sized from `Cleaning Timeout`
+    #[serde(rename = "Adaptive Timeout", default)]
+    pub adaptive_timeout: AdaptiveTimeout,
+
+    /// Most QAC passes per plugin; another pass runs while the previous one fixed records
+    #[serde(rename = "Cleaning Passes", default = "default_cleaning_passes")]
+    pub cleaning_passes: u32,
+
+    /// CPU usage in percent of one core below which xEdit counts as idle; 0 turns hang detection off
+    #[serde(rename = "CPU Threshold", default = "default_cpu_threshold")]
+    pub cpu_threshold: u32,
+
+    /// Seconds xEdit may stay idle before it is stopped as hung
+    #[serde(rename = "Hang Window", default = "default_hang_window")]
+    pub hang_window: u32,
+
     #[serde(rename = "Journal Expiration", default = "default_journal_expiration")]
     pub journal_expiration: u32,
 
+    /// Attempts per plugin, including the first; 1 turns retries off
+    #[serde(rename = "Retry Attempts", default = "default_retry_attempts")]
+    pub retry_attempts: u32,
+
+    /// Seconds to wait before the first retry, doubled for each further one
+    #[serde(rename = "Retry Delay", default = "default_retry_delay")]
+    pub retry_delay: u32,
+
+    /// Failures that are retried
+    #[serde(rename = "Retry On", default = "default_retry_on")]
+    pub retry_on: Vec<RetryReason>,
+
     #[serde(rename = "LoadOrder TXT", default)]
     pub loadorder_txt: String,
 
@@ -63,9 +97,63 @@ pub struct PactSettings {
     #[serde(rename = "MO2 EXE", default)]
     pub mo2_exe: String,
 
+    #[serde(rename = "Vortex Mode", default)]
+    pub vortex_mode: bool,
+
+    #[serde(rename = "Game Data Path", default)]
+    pub game_data_path: String,
+
     #[serde(rename = "Partial Forms", default)]
     pub partial_forms: bool,
 
+    /// Mark plugins with deleted navmeshes as needing manual attention
+    #[serde(rename = "Flag Navmeshes", default = "default_flag_navmeshes")]
+    pub flag_navmeshes: bool,
+
+    /// Leave out plugins that have not changed since AutoQAC last cleaned them (opt-in)
+    #[serde(rename = "Skip Unchanged", default)]
+    pub skip_unchanged: bool,
+
+    /// Leave out plugins that LOOT's masterlist lists as clean (opt-in)
+    #[serde(rename = "Skip LOOT Clean", default)]
+    pub skip_loot_clean: bool,
+
+    /// LOOT masterlist.yaml; empty uses the one LOOT keeps for the game
+    #[serde(rename = "LOOT Masterlist", default)]
+    pub loot_masterlist: String,
+
+    /// Extra xEdit options for every game (e.g. "-cache -nobuildrefs")
+    #[serde(rename = "XEdit Arguments", default)]
+    pub xedit_arguments: String,
+
+    /// Extra xEdit options for one game, keyed by game id (e.g. SSE)
+    #[serde(rename = "XEdit Game Arguments", default)]
+    pub xedit_game_arguments: IndexMap<String, String>,
+
+    /// xEdit argument template; empty uses the built-in `-QAC -autoexit -autoload` template
+    #[serde(rename = "Command Template", default)]
+    pub command_template: String,
+
+    /// How xEdit is launched: Native, Wine or Proton
+    #[serde(rename = "Runner", default)]
+    pub runner: RunnerKind,
+
+    #[serde(rename = "Wine Binary", default)]
+    pub wine_binary: String,
+
+    #[serde(rename = "Wine Prefix", default)]
+    pub wine_prefix: String,
+
+    #[serde(rename = "Proton Path", default)]
+    pub proton_path: String,
+
+    #[serde(rename = "Compat Data Path", default)]
+    pub compat_data_path: String,
+
+    /// UI language code (e.g. "de"); empty follows the system language
+    #[serde(rename = "Language", default)]
+    pub language: String,
+
     #[serde(rename = "Debug Mode", default)]
     pub debug_mode: bool,
 }
@@ -76,32 +164,133 @@ impl Default for PactSettings {
             update_check: true,
             stat_logging: true,
             cleaning_timeout: 300,
+            adaptive_timeout: AdaptiveTimeout::Floor,
+            cleaning_passes: default_cleaning_passes(),
+            cpu_threshold: default_cpu_threshold(),
+            hang_window: default_hang_window(),
             journal_expiration: 7,
+            retry_attempts: default_retry_attempts(),
+            retry_delay: default_retry_delay(),
+            retry_on: default_retry_on(),
             loadorder_txt: String::new(),
             xedit_exe: String::new(),
             mo2_exe: String::new(),
+            vortex_mode: false,
+            game_data_path: String::new(),
             partial_forms: false,
+            flag_navmeshes: default_flag_navmeshes(),
+            skip_unchanged: false,
+            skip_loot_clean: false,
+            loot_masterlist: String::new(),
+            xedit_arguments: String::new(),
+            xedit_game_arguments: IndexMap::new(),
+            command_template: String::new(),
+            runner: RunnerKind::Native,
+            wine_binary: String::new(),
+            wine_prefix: String::new(),
+            proton_path: String::new(),
+            compat_data_path: String::new(),
+            language: String::new(),
             debug_mode: false,
         }
     }
 }
 
-impl Default for UserConfig {
-    fn default() -> Self {
-        Self {
-            pact_settings: PactSettings::default(),
-        }
-    }
+/// How xEdit and MO2 are launched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunnerKind {
+    /// Run the executable directly (Windows)
+    #[default]
+    Native,
+    /// Run through `wine` with a `WINEPREFIX`
+    Wine,
+    /// Run through Proton's `proton run` with a Steam compat data folder
+    Proton,
+}
+
+/// How per-plugin timeouts relate to `Cleaning Timeout`
+///
+/// Each plugin's timeout is estimated from its size, record count and earlier run times.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AdaptiveTimeout {
+    /// Always use `Cleaning Timeout`
+    Off,
+    /// Use the estimate, but never less than `Cleaning Timeout`
+    #[default]
+    Floor,
+    /// Use the estimate, but never more than `Cleaning Timeout`
+    Ceiling,
+}
+
+/// xEdit failures that may be transient and can be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RetryReason {
+    /// xEdit did not finish within the cleaning timeout
+    Timeout,
+    /// xEdit stopped using CPU and was stopped
+    Hang,
+    /// xEdit exited with a non-zero code
+    #[serde(rename = "Exit Code")]
+    ExitCode,
+    /// xEdit exited without writing its log
+    #[serde(rename = "Log Not Found")]
+    LogNotFound,
+}
+
+impl RetryReason {
+    /// Every retry reason
+    pub const ALL: [RetryReason; 4] = [
+        RetryReason::Timeout,
+        RetryReason::Hang,
+        RetryReason::ExitCode,
+        RetryReason::LogNotFound,
+    ];
+
+    /// Reasons retried by default. A hang is left out: xEdit sitting idle for the whole
+    /// `Hang Window` tends to happen again for the same plugin.
+    pub const DEFAULT: [RetryReason; 3] = [
+        RetryReason::Timeout,
+        RetryReason::ExitCode,
+        RetryReason::LogNotFound,
+    ];
 }
 
 fn default_cleaning_timeout() -> u32 {
     300
 }
 
+fn default_flag_navmeshes() -> bool {
+    true
+}
+
+fn default_cleaning_passes() -> u32 {
+    1
+}
+
+fn default_cpu_threshold() -> u32 {
+    5
+}
+
+fn default_hang_window() -> u32 {
+    120
+}
+
 fn default_journal_expiration() -> u32 {
     7
 }
 
+fn default_retry_attempts() -> u32 {
+    2
+}
+
+fn default_retry_delay() -> u32 {
+    10
+}
+
+fn default_retry_on() -> Vec<RetryReason> {
+    RetryReason::DEFAULT.to_vec()
+}
+
 /// Additional ignore file structure for PACT Ignore.yaml
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IgnoreConfig {
@@ -116,6 +305,16 @@ pub struct IgnoreConfig {
 
     #[serde(rename = "PACT_Ignore_SSE", default)]
     pub sse: Vec<String>,
+
+    #[serde(rename = "PACT_Ignore_FO4VR", default)]
+    pub fo4vr: Vec<String>,
+
+    #[serde(rename = "PACT_Ignore_SkyrimVR", default)]
+    pub skyrimvr: Vec<String>,
+
+    /// Ignore lists for any other game, keyed by the game's ignore key
+    #[serde(flatten)]
+    pub other: IndexMap<String, Vec<String>>,
 }
 
 impl Default for IgnoreConfig {
@@ -125,8 +324,32 @@ impl Default for IgnoreConfig {
             fnv: vec!["Example Plugin.esp".to_string()],
             fo4: vec!["Example Plugin.esp".to_string()],
             sse: vec!["Example Plugin.esp".to_string()],
+            fo4vr: vec!["Example Plugin.esp".to_string()],
+            skyrimvr: vec!["Example Plugin.esp".to_string()],
+            other: IndexMap::new(),
+        }
+    }
+}
+
+impl IgnoreConfig {
+    /// Get the ignore list for a game's ignore key (e.g. "PACT_Ignore_FO4")
+    pub fn get(&self, ignore_key: &str) -> Option<&Vec<String>> {
+        match ignore_key {
+            "PACT_Ignore_FO3" => Some(&self.fo3),
+            "PACT_Ignore_FNV" => Some(&self.fnv),
+            "PACT_Ignore_FO4" => Some(&self.fo4),
+            "PACT_Ignore_SSE" => Some(&self.sse),
+            "PACT_Ignore_FO4VR" => Some(&self.fo4vr),
+            "PACT_Ignore_SkyrimVR" => Some(&self.skyrimvr),
+            _ => self.other.get(ignore_key),
         }
     }
+
+    /// Check if a plugin is on the ignore list for the given ignore key
+    pub fn is_ignored(&self, ignore_key: &str, plugin: &str) -> bool {
+        self.get(ignore_key)
+            .is_some_and(|list| list.iter().any(|p| p.eq_ignore_ascii_case(plugin)))
+    }
 }
 
 impl MainConfig {
@@ -161,6 +384,28 @@ mod tests {
         assert_eq!(settings.journal_expiration, 7);
         assert!(settings.update_check);
         assert!(!settings.partial_forms);
+        assert!(!settings.vortex_mode);
+        assert_eq!(settings.retry_attempts, 2);
+        assert_eq!(settings.retry_on, RetryReason::DEFAULT);
+        assert_eq!(settings.cleaning_passes, 1);
+        assert!(settings.flag_navmeshes);
+        assert!(!settings.skip_unchanged);
+        assert!(!settings.skip_loot_clean);
+        assert_eq!(settings.cpu_threshold, 5);
+        assert_eq!(settings.hang_window, 120);
+    }
+
+    #[test]
+    fn test_retry_settings_from_yaml() {
+        let yaml = "Retry Attempts: 2\nRetry Delay: 30\nRetry On: [Timeout, Log Not Found]\n";
+        let settings: PactSettings = serde_yaml_ng::from_str(yaml).unwrap();
+
+        assert_eq!(settings.retry_attempts, 2);
+        assert_eq!(settings.retry_delay, 30);
+        assert_eq!(
+            settings.retry_on,
+            vec![RetryReason::Timeout, RetryReason::LogNotFound]
+        );
     }
 
     #[test]
@@ -175,4 +420,15 @@ mod tests {
         assert_eq!(config.fo3.len(), 1);
         assert_eq!(config.fo3[0], "Example Plugin.esp");
     }
+
+    #[test]
+    fn test_ignore_config_lookup_by_key() {
+        let yaml = "PACT_Ignore_FO4:\n  - MyMod.esp\nPACT_Ignore_Custom:\n  - Other.esm\n";
+        let config: IgnoreConfig = serde_yaml_ng::from_str(yaml).unwrap();
+
+        assert!(config.is_ignored("PACT_Ignore_FO4", "mymod.esp"));
+        assert!(config.is_ignored("PACT_Ignore_Custom", "Other.esm"));
+        assert!(!config.is_ignored("PACT_Ignore_SSE", "MyMod.esp"));
+        assert!(config.get("PACT_Ignore_Unknown").is_none());
+    }
 }
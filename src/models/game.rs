@@ -0,0 +1,669 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+use super::MainConfig;
+
+/// `XEdit_Lists` key holding the universal xEdit executables (xEdit.exe, xfoedit.exe, ...)
+pub const UNIVERSAL_XEDIT_KEY: &str = "Universal";
+
+/// Everything AutoQAC needs to know about a supported game
+///
+/// Definitions come from the optional `Games` list in `AutoQAC Main.yaml`, layered over
+/// the built-in defaults from [`GameRegistry::builtin`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameDefinition {
+    /// Game id used as the key for `XEdit_Lists` and `Skip_Lists` (e.g. "FO4", "SSE")
+    pub id: String,
+
+    /// Display name (e.g. "Fallout 4")
+    #[serde(default)]
+    pub name: String,
+
+    /// Game a total conversion is built on (e.g. "FNV" for TTW).
+    ///
+    /// The base game's skip list, implicit masters and ignore list also apply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_game: Option<String>,
+
+    /// Game-specific xEdit executables (e.g. FO4Edit.exe, FO4Edit64.exe)
+    #[serde(default)]
+    pub xedit_executables: Vec<String>,
+
+    /// Executables of the game and its editor (e.g. Fallout4.exe, CreationKit.exe), which
+    /// hold its plugins open while they run
+    #[serde(default)]
+    pub game_executables: Vec<String>,
+
+    /// Game mode flag passed to universal xEdit (e.g. "-fo4")
+    pub mode_flag: String,
+
+    /// Oldest xEdit release that supports this game (e.g. "4.1.5" for Starfield), checked
+    /// before cleaning. None when any xEdit that runs Quick Auto Clean will do.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_xedit_version: Option<String>,
+
+    /// Master file that identifies the game in a load order (e.g. "Fallout4.esm")
+    pub base_master: String,
+
+    /// Masters the game always loads, even when they are missing from plugins.txt
+    #[serde(default)]
+    pub implicit_masters: Vec<String>,
+
+    /// Expected form version of the base master's header, used to tell apart games that
+    /// share a base master (43 for Skyrim LE, 44 for Skyrim SE)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_version: Option<u16>,
+
+    /// Plugins that identify this game over another sharing the same base master
+    /// (e.g. SkyrimVR.esm for Skyrim VR)
+    #[serde(default)]
+    pub markers: Vec<String>,
+
+    /// `TES4` header flag marking light (small) masters, 0 if the game has none
+    #[serde(default)]
+    pub light_flag: u32,
+
+    /// `TES4` header flag marking medium masters, 0 if the game has none
+    #[serde(default)]
+    pub medium_flag: u32,
+
+    /// Key of this game's list in `PACT Ignore.yaml` (e.g. "PACT_Ignore_FO4")
+    pub ignore_key: String,
+
+    /// Folder under `%LOCALAPPDATA%` holding the game's `plugins.txt` (e.g. "Fallout4")
+    #[serde(default)]
+    pub appdata_folder: String,
+}
+
+impl GameDefinition {
+    /// Check whether an xEdit executable is one of this game's dedicated executables.
+    ///
+    /// Matches the exact file name first, then falls back to the executable stem so
+    /// renamed copies like `FO4Edit - QAC.exe` are still recognized.
+    pub fn owns_xedit(&self, xedit_path: &str) -> bool {
+        self.xedit_match_len(xedit_path).is_some()
+    }
+
+    /// Check whether a plugin is the base master or one of the implicit masters
+    pub fn is_implicit_master(&self, plugin: &str) -> bool {
+        self.base_master.eq_ignore_ascii_case(plugin)
+            || self
+                .implicit_masters
+                .iter()
+                .any(|master| master.eq_ignore_ascii_case(plugin))
+    }
+
+    /// Length of the longest executable stem matching the given xEdit path.
+    ///
+    /// Exact file name matches rank above every stem match.
+    fn xedit_match_len(&self, xedit_path: &str) -> Option<usize> {
+        let file_name = xedit_file_name(xedit_path).to_lowercase();
+        let stem = exe_stem(&file_name);
+
+        self.xedit_executables
+            .iter()
+            .filter_map(|exe| {
+                let exe = exe.to_lowercase();
+                if exe == file_name {
+                    Some(usize::MAX)
+                } else {
+                    let exe_stem = exe_stem(&exe);
+                    stem.contains(exe_stem).then_some(exe_stem.len())
+                }
+            })
+            .max()
+    }
+}
+
+/// Registry of all games AutoQAC knows how to clean
+///
+/// Every lookup by game id, xEdit executable or master file goes through the registry,
+/// so adding a game only needs a new [`GameDefinition`].
+#[derive(Debug, Clone)]
+pub struct GameRegistry {
+    games: IndexMap<String, GameDefinition>,
+    universal_xedit: Vec<String>,
+}
+
+static BUILTIN_REGISTRY: LazyLock<GameRegistry> = LazyLock::new(GameRegistry::builtin);
+
+impl GameRegistry {
+    /// Create a registry with the built-in game definitions
+    pub fn builtin() -> Self {
+        let mut registry = Self {
+            games: IndexMap::new(),
+            universal_xedit: strings(&["xEdit.exe", "xEdit64.exe", "xfoedit.exe", "xfoedit64.exe"]),
+        };
+
+        for game in builtin_games() {
+            registry.insert(game);
+        }
+
+        registry
+    }
+
+    /// Shared instance of the built-in registry
+    pub fn builtin_ref() -> &'static Self {
+        &BUILTIN_REGISTRY
+    }
+
+    /// Build the registry for a main configuration.
+    ///
+    /// Starts from the built-in definitions, replaces or adds the games listed under
+    /// `Games`, then merges each game's `XEdit_Lists` entry into its executables.
+    pub fn from_main_config(config: &MainConfig) -> Self {
+        let mut registry = Self::builtin();
+
+        for game in &config.pact_data.games {
+            registry.insert(game.clone());
+        }
+
+        for (key, executables) in &config.pact_data.xedit_lists {
+            let known = if key == UNIVERSAL_XEDIT_KEY {
+                Some(&mut registry.universal_xedit)
+            } else {
+                registry
+                    .games
+                    .get_mut(key)
+                    .map(|game| &mut game.xedit_executables)
+            };
+
+            match known {
+                Some(list) => {
+                    for exe in executables {
+                        if !list.iter().any(|e| e.eq_ignore_ascii_case(exe)) {
+                            list.push(exe.clone());
+                        }
+                    }
+                }
+                None => tracing::debug!("No game definition for XEdit_Lists entry: {}", key),
+            }
+        }
+
+        registry
+    }
+
+    /// Add a game definition, replacing any existing definition with the same id
+    pub fn insert(&mut self, game: GameDefinition) {
+        self.games.insert(game.id.clone(), game);
+    }
+
+    /// Get a game definition by id (case-insensitive)
+    pub fn get(&self, id: &str) -> Option<&GameDefinition> {
+        self.games.get(id).or_else(|| {
+            self.games
+                .values()
+                .find(|game| game.id.eq_ignore_ascii_case(id))
+        })
+    }
+
+    /// Iterate over all game definitions in registration order
+    pub fn games(&self) -> impl Iterator<Item = &GameDefinition> {
+        self.games.values()
+    }
+
+    /// Find the game a dedicated xEdit executable belongs to
+    pub fn find_by_xedit(&self, xedit_path: &str) -> Option<&GameDefinition> {
+        // Prefer the most specific match (FO4VREdit over FO4Edit); reversed so that
+        // ties go to the game registered first
+        self.games
+            .values()
+            .rev()
+            .filter_map(|game| game.xedit_match_len(xedit_path).map(|len| (len, game)))
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, game)| game)
+    }
+
+    /// Find the game whose base master matches a load order entry, ignoring case
+    pub fn find_by_master(&self, plugin: &str) -> Option<&GameDefinition> {
+        self.games()
+            .find(|game| game.base_master.eq_ignore_ascii_case(plugin))
+    }
+
+    /// Find the game identified by a marker plugin (e.g. Fallout4_VR.esm), ignoring case
+    pub fn find_by_marker(&self, plugin: &str) -> Option<&GameDefinition> {
+        self.games().find(|game| {
+            game.markers
+                .iter()
+                .any(|marker| marker.eq_ignore_ascii_case(plugin))
+        })
+    }
+
+    /// Get a game followed by the games it is layered on (TTW, then FNV).
+    ///
+    /// Unknown ids yield an empty list; cycles in `base_game` are cut off.
+    pub fn lineage(&self, id: &str) -> Vec<&GameDefinition> {
+        let mut lineage: Vec<&GameDefinition> = Vec::new();
+        let mut next = self.get(id);
+
+        while let Some(game) = next {
+            if lineage.iter().any(|g| g.id == game.id) {
+                tracing::warn!("Game definition {} has a base_game cycle", id);
+                break;
+            }
+            lineage.push(game);
+            next = game.base_game.as_deref().and_then(|base| self.get(base));
+        }
+
+        lineage
+    }
+
+    /// Check whether a game is the given base game or layered on top of it
+    pub fn is_based_on(&self, id: &str, base_id: &str) -> bool {
+        self.lineage(id)
+            .iter()
+            .any(|game| game.id.eq_ignore_ascii_case(base_id))
+    }
+
+    /// Check whether a plugin must never be cleaned for a game.
+    ///
+    /// Protected plugins are those in the `Skip_Lists` entry or the implicit masters of the
+    /// game or any game it is layered on.
+    pub fn is_protected(&self, config: &MainConfig, id: &str, plugin: &str) -> bool {
+        self.lineage(id).iter().any(|game| {
+            config.should_skip_plugin(&game.id, plugin) || game.is_implicit_master(plugin)
+        })
+    }
+
+    /// Ignore-list keys for a game and every game it is layered on
+    pub fn ignore_keys(&self, id: &str) -> Vec<&str> {
+        self.lineage(id)
+            .iter()
+            .map(|game| game.ignore_key.as_str())
+            .collect()
+    }
+
+    /// Every known xEdit executable, dedicated and universal
+    pub fn xedit_executables(&self) -> impl Iterator<Item = &str> {
+        self.games
+            .values()
+            .flat_map(|game| &game.xedit_executables)
+            .chain(&self.universal_xedit)
+            .map(String::as_str)
+    }
+
+    /// Executables of a game and every game it is layered on; with no game, of every game
+    pub fn game_executables(&self, id: Option<&str>) -> Vec<&str> {
+        let games = match id {
+            Some(id) => self.lineage(id),
+            None => self.games().collect(),
+        };
+        games
+            .into_iter()
+            .flat_map(|game| &game.game_executables)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Check whether an executable is a universal xEdit that needs a game mode flag
+    pub fn is_universal_xedit(&self, xedit_path: &str) -> bool {
+        let file_name = xedit_file_name(xedit_path);
+        self.universal_xedit
+            .iter()
+            .any(|exe| exe.eq_ignore_ascii_case(file_name))
+    }
+}
+
+impl Default for GameRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// File name component of a path, accepting both `/` and `\` separators
+fn xedit_file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// Strip a trailing `.exe` from a lowercased executable name
+fn exe_stem(file_name: &str) -> &str {
+    file_name.strip_suffix(".exe").unwrap_or(file_name)
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+/// Built-in game definitions, in load order detection priority
+fn builtin_games() -> Vec<GameDefinition> {
+    vec![
+        GameDefinition {
+            id: "Oblivion".to_string(),
+            name: "Oblivion".to_string(),
+            xedit_executables: strings(&["TES4Edit.exe", "TES4Edit64.exe"]),
+            game_executables: strings(&["Oblivion.exe", "TESConstructionSet.exe"]),
+            mode_flag: "-tes4".to_string(),
+            base_master: "Oblivion.esm".to_string(),
+            ignore_key: "PACT_Ignore_Oblivion".to_string(),
+            appdata_folder: "Oblivion".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "FO3".to_string(),
+            name: "Fallout 3".to_string(),
+            xedit_executables: strings(&["FO3Edit.exe", "FO3Edit64.exe"]),
+            game_executables: strings(&["Fallout3.exe", "GECK.exe"]),
+            mode_flag: "-fo3".to_string(),
+            base_master: "Fallout3.esm".to_string(),
+            ignore_key: "PACT_Ignore_FO3".to_string(),
+            appdata_folder: "Fallout3".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "FNV".to_string(),
+            name: "Fallout: New Vegas".to_string(),
+            xedit_executables: strings(&["FNVEdit.exe", "FNVEdit64.exe"]),
+            game_executables: strings(&["FalloutNV.exe", "GECK.exe"]),
+            mode_flag: "-fnv".to_string(),
+            base_master: "FalloutNV.esm".to_string(),
+            ignore_key: "PACT_Ignore_FNV".to_string(),
+            appdata_folder: "FalloutNV".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "TTW".to_string(),
+            name: "Tale of Two Wastelands".to_string(),
+            base_game: Some("FNV".to_string()),
+            xedit_executables: strings(&["TTWEdit.exe", "TTWEdit64.exe"]),
+            mode_flag: "-fnv".to_string(),
+            base_master: "FalloutNV.esm".to_string(),
+            implicit_masters: strings(&["TaleOfTwoWastelands.esm", "YUPTTW.esm"]),
+            markers: strings(&["TaleOfTwoWastelands.esm"]),
+            ignore_key: "PACT_Ignore_TTW".to_string(),
+            appdata_folder: "FalloutNV".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "FO4".to_string(),
+            name: "Fallout 4".to_string(),
+            xedit_executables: strings(&["FO4Edit.exe", "FO4Edit64.exe"]),
+            game_executables: strings(&["Fallout4.exe", "CreationKit.exe"]),
+            mode_flag: "-fo4".to_string(),
+            base_master: "Fallout4.esm".to_string(),
+            implicit_masters: strings(&[
+                "DLCRobot.esm",
+                "DLCworkshop01.esm",
+                "DLCCoast.esm",
+                "DLCworkshop02.esm",
+                "DLCworkshop03.esm",
+                "DLCNukaWorld.esm",
+            ]),
+            light_flag: 0x200,
+            ignore_key: "PACT_Ignore_FO4".to_string(),
+            appdata_folder: "Fallout4".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "London".to_string(),
+            name: "Fallout: London".to_string(),
+            base_game: Some("FO4".to_string()),
+            mode_flag: "-fo4".to_string(),
+            base_master: "Fallout4.esm".to_string(),
+            implicit_masters: strings(&["LondonWorldSpace.esm"]),
+            markers: strings(&["LondonWorldSpace.esm"]),
+            light_flag: 0x200,
+            ignore_key: "PACT_Ignore_London".to_string(),
+            appdata_folder: "Fallout4".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "FO4VR".to_string(),
+            name: "Fallout 4 VR".to_string(),
+            xedit_executables: strings(&["FO4VREdit.exe", "FO4VREdit64.exe"]),
+            game_executables: strings(&["Fallout4VR.exe"]),
+            mode_flag: "-fo4vr".to_string(),
+            base_master: "Fallout4.esm".to_string(),
+            implicit_masters: strings(&["Fallout4_VR.esm"]),
+            markers: strings(&["Fallout4_VR.esm"]),
+            ignore_key: "PACT_Ignore_FO4VR".to_string(),
+            appdata_folder: "Fallout4VR".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "SSE".to_string(),
+            name: "Skyrim Special Edition".to_string(),
+            xedit_executables: strings(&["SSEEdit.exe", "SSEEdit64.exe"]),
+            game_executables: strings(&["SkyrimSE.exe", "CreationKit.exe"]),
+            mode_flag: "-sse".to_string(),
+            base_master: "Skyrim.esm".to_string(),
+            implicit_masters: strings(&[
+                "Update.esm",
+                "Dawnguard.esm",
+                "HearthFires.esm",
+                "Dragonborn.esm",
+            ]),
+            form_version: Some(44),
+            light_flag: 0x200,
+            ignore_key: "PACT_Ignore_SSE".to_string(),
+            appdata_folder: "Skyrim Special Edition".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "Enderal".to_string(),
+            name: "Enderal: Forgotten Stories (Special Edition)".to_string(),
+            base_game: Some("SSE".to_string()),
+            xedit_executables: strings(&["EnderalSEEdit.exe", "EnderalSEEdit64.exe"]),
+            mode_flag: "-enderalse".to_string(),
+            base_master: "Skyrim.esm".to_string(),
+            implicit_masters: strings(&["Enderal - Forgotten Stories.esm"]),
+            markers: strings(&["Enderal - Forgotten Stories.esm"]),
+            form_version: Some(44),
+            light_flag: 0x200,
+            ignore_key: "PACT_Ignore_Enderal".to_string(),
+            appdata_folder: "Enderal Special Edition".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "SkyrimLE".to_string(),
+            name: "Skyrim Legendary Edition".to_string(),
+            xedit_executables: strings(&["TES5Edit.exe", "TES5Edit64.exe"]),
+            game_executables: strings(&["TESV.exe", "CreationKit.exe"]),
+            mode_flag: "-tes5".to_string(),
+            base_master: "Skyrim.esm".to_string(),
+            implicit_masters: strings(&["Update.esm"]),
+            form_version: Some(43),
+            ignore_key: "PACT_Ignore_SkyrimLE".to_string(),
+            appdata_folder: "Skyrim".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "SkyrimVR".to_string(),
+            name: "Skyrim VR".to_string(),
+            xedit_executables: strings(&["TES5VREdit.exe", "SkyrimVREdit.exe"]),
+            game_executables: strings(&["SkyrimVR.exe"]),
+            mode_flag: "-tes5vr".to_string(),
+            base_master: "Skyrim.esm".to_string(),
+            implicit_masters: strings(&[
+                "Update.esm",
+                "Dawnguard.esm",
+                "HearthFires.esm",
+                "Dragonborn.esm",
+                "SkyrimVR.esm",
+            ]),
+            form_version: Some(44),
+            markers: strings(&["SkyrimVR.esm"]),
+            ignore_key: "PACT_Ignore_SkyrimVR".to_string(),
+            appdata_folder: "Skyrim VR".to_string(),
+            ..Default::default()
+        },
+        GameDefinition {
+            id: "Starfield".to_string(),
+            name: "Starfield".to_string(),
+            xedit_executables: strings(&["SF1Edit.exe", "SF1Edit64.exe"]),
+            game_executables: strings(&["Starfield.exe", "CreationKit.exe"]),
+            mode_flag: "-sf1".to_string(),
+            min_xedit_version: Some("4.1.5".to_string()),
+            base_master: "Starfield.esm".to_string(),
+            implicit_masters: strings(&[
+                "Constellation.esm",
+                "OldMars.esm",
+                "BlueprintShips-Starfield.esm",
+                "SFBGS003.esm",
+                "SFBGS004.esm",
+                "SFBGS006.esm",
+                "SFBGS007.esm",
+                "SFBGS008.esm",
+            ]),
+            light_flag: 0x100,
+            medium_flag: 0x400,
+            ignore_key: "PACT_Ignore_Starfield".to_string(),
+            appdata_folder: "Starfield".to_string(),
+            ..Default::default()
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_lookup_by_id() {
+        let registry = GameRegistry::builtin();
+
+        let fo4 = registry.get("FO4").unwrap();
+        assert_eq!(fo4.base_master, "Fallout4.esm");
+        assert_eq!(fo4.mode_flag, "-fo4");
+        assert_eq!(registry.get("sse").unwrap().id, "SSE");
+        assert!(registry.get("Morrowind").is_none());
+    }
+
+    #[test]
+    fn test_find_by_xedit() {
+        let registry = GameRegistry::builtin();
+
+        assert_eq!(registry.find_by_xedit("FO4Edit64.exe").unwrap().id, "FO4");
+        assert_eq!(
+            registry.find_by_xedit("C:\\Tools\\SSEEdit.exe").unwrap().id,
+            "SSE"
+        );
+        assert_eq!(
+            registry.find_by_xedit("FO4Edit - QAC.exe").unwrap().id,
+            "FO4"
+        );
+        assert_eq!(
+            registry.find_by_xedit("FO4VREdit64.exe").unwrap().id,
+            "FO4VR"
+        );
+        assert_eq!(
+            registry.find_by_xedit("TES5VREdit.exe").unwrap().id,
+            "SkyrimVR"
+        );
+        assert_eq!(
+            registry.find_by_xedit("SF1Edit64.exe").unwrap().id,
+            "Starfield"
+        );
+        assert_eq!(
+            registry.find_by_xedit("TES5Edit.exe").unwrap().id,
+            "SkyrimLE"
+        );
+        assert_eq!(
+            registry.find_by_xedit("TES4Edit.exe").unwrap().id,
+            "Oblivion"
+        );
+        assert!(registry.find_by_xedit("xEdit.exe").is_none());
+        assert!(registry.is_universal_xedit("C:/Tools/xEdit64.exe"));
+    }
+
+    #[test]
+    fn test_implicit_masters() {
+        let registry = GameRegistry::builtin();
+        let sse = registry.get("SSE").unwrap();
+
+        assert!(sse.is_implicit_master("Skyrim.esm"));
+        assert!(sse.is_implicit_master("dawnguard.esm"));
+        assert!(!sse.is_implicit_master("MyMod.esp"));
+    }
+
+    #[test]
+    fn test_vr_games_share_base_master() {
+        let registry = GameRegistry::builtin();
+
+        // Flat games win on the base master; VR is identified by its marker
+        assert_eq!(registry.find_by_master("Skyrim.esm").unwrap().id, "SSE");
+        assert_eq!(
+            registry.find_by_marker("SkyrimVR.esm").unwrap().id,
+            "SkyrimVR"
+        );
+        assert_eq!(
+            registry.find_by_marker("Fallout4_VR.esm").unwrap().id,
+            "FO4VR"
+        );
+        assert_eq!(registry.get("FO4VR").unwrap().mode_flag, "-fo4vr");
+
+        // Load orders written by other tools may change the case of plugin names
+        assert_eq!(registry.find_by_master("skyrim.esm").unwrap().id, "SSE");
+        assert_eq!(
+            registry.find_by_marker("FALLOUT4_VR.ESM").unwrap().id,
+            "FO4VR"
+        );
+    }
+
+    #[test]
+    fn test_total_conversions_layer_on_base_game() {
+        let registry = GameRegistry::builtin();
+        let config = MainConfig {
+            pact_data: crate::models::PactData {
+                skip_lists: IndexMap::from([(
+                    "FNV".to_string(),
+                    strings(&["FalloutNV.esm", "DeadMoney.esm"]),
+                )]),
+                ..Default::default()
+            },
+        };
+
+        let lineage: Vec<&str> = registry
+            .lineage("TTW")
+            .iter()
+            .map(|g| g.id.as_str())
+            .collect();
+        assert_eq!(lineage, vec!["TTW", "FNV"]);
+        assert!(registry.is_based_on("Enderal", "SSE"));
+        assert!(registry.is_based_on("London", "FO4"));
+        assert!(!registry.is_based_on("FO4", "London"));
+
+        // TTW masters and the FNV skip list are both protected
+        assert!(registry.is_protected(&config, "TTW", "TaleOfTwoWastelands.esm"));
+        assert!(registry.is_protected(&config, "TTW", "DeadMoney.esm"));
+        assert!(!registry.is_protected(&config, "FNV", "TaleOfTwoWastelands.esm"));
+        assert_eq!(
+            registry.ignore_keys("TTW"),
+            vec!["PACT_Ignore_TTW", "PACT_Ignore_FNV"]
+        );
+    }
+
+    #[test]
+    fn test_game_executables_follow_lineage() {
+        let registry = GameRegistry::builtin();
+
+        assert_eq!(
+            registry.game_executables(Some("TTW")),
+            vec!["FalloutNV.exe", "GECK.exe"]
+        );
+        assert_eq!(
+            registry.game_executables(Some("FO4")),
+            vec!["Fallout4.exe", "CreationKit.exe"]
+        );
+        assert!(registry.game_executables(None).contains(&"Starfield.exe"));
+        assert!(registry.xedit_executables().any(|exe| exe == "xEdit64.exe"));
+        assert!(registry.xedit_executables().any(|exe| exe == "SSEEdit.exe"));
+    }
+
+    #[test]
+    fn test_game_definition_yaml() {
+        let yaml = r#"
+id: FO4
+name: Fallout 4
+xedit_executables: [FO4Edit.exe]
+mode_flag: -fo4
+base_master: Fallout4.esm
+ignore_key: PACT_Ignore_FO4
+"#;
+        let game: GameDefinition = serde_yaml_ng::from_str(yaml).unwrap();
+
+        assert_eq!(game.id, "FO4");
+        assert!(game.implicit_masters.is_empty());
+        assert!(game.owns_xedit("fo4edit.exe"));
+    }
+}
@@ -0,0 +1,80 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How many durations are kept per plugin
+pub const HISTORY_LENGTH: usize = 5;
+
+/// Recent xEdit run times and cleaned checksums per plugin, from AutoQAC History.yaml
+///
+/// Used to size each plugin's cleaning timeout from how long it took before, and to skip
+/// plugins that have not changed since they were last cleaned.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleaningHistory {
+    /// Seconds per run, oldest first, keyed by lower-case plugin name
+    #[serde(rename = "Durations", default)]
+    pub durations: IndexMap<String, Vec<u64>>,
+
+    /// CRC32 of each plugin right after it was last cleaned, keyed by lower-case plugin name
+    #[serde(rename = "Cleaned", default)]
+    pub cleaned: IndexMap<String, u32>,
+}
+
+impl CleaningHistory {
+    /// Recorded run times of a plugin (case-insensitive), oldest first
+    pub fn durations(&self, plugin: &str) -> Vec<Duration> {
+        self.durations
+            .get(&plugin.to_lowercase())
+            .map(|secs| secs.iter().copied().map(Duration::from_secs).collect())
+            .unwrap_or_default()
+    }
+
+    /// Add a run time, keeping the last [`HISTORY_LENGTH`] per plugin
+    pub fn record(&mut self, plugin: &str, duration: Duration) {
+        let entries = self.durations.entry(plugin.to_lowercase()).or_default();
+        entries.push(duration.as_secs().max(1));
+        if entries.len() > HISTORY_LENGTH {
+            entries.drain(..entries.len() - HISTORY_LENGTH);
+        }
+    }
+
+    /// CRC32 of a plugin (case-insensitive) after it was last cleaned
+    pub fn cleaned_crc(&self, plugin: &str) -> Option<u32> {
+        self.cleaned.get(&plugin.to_lowercase()).copied()
+    }
+
+    /// Remember the CRC32 of a plugin that was just cleaned
+    pub fn record_cleaned(&mut self, plugin: &str, crc: u32) {
+        self.cleaned.insert(plugin.to_lowercase(), crc);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_keeps_recent_runs() {
+        let mut history = CleaningHistory::default();
+        for secs in 1..=7 {
+            history.record("MyPlugin.esp", Duration::from_secs(secs * 10));
+        }
+
+        let durations = history.durations("myplugin.ESP");
+        assert_eq!(durations.len(), HISTORY_LENGTH);
+        assert_eq!(durations[0], Duration::from_secs(30));
+        assert_eq!(durations[4], Duration::from_secs(70));
+        assert!(history.durations("Other.esp").is_empty());
+    }
+
+    #[test]
+    fn test_history_remembers_cleaned_crc() {
+        let mut history = CleaningHistory::default();
+        assert_eq!(history.cleaned_crc("MyPlugin.esp"), None);
+
+        history.record_cleaned("MyPlugin.esp", 0x1234_ABCD);
+        history.record_cleaned("myplugin.esp", 0xDEAD_BEEF);
+        assert_eq!(history.cleaned_crc("MYPLUGIN.ESP"), Some(0xDEAD_BEEF));
+        assert_eq!(history.cleaned.len(), 1);
+    }
+}
@@ -3,8 +3,16 @@
 //! This module contains all the core data structures used throughout the application:
 //! - [`AppState`]: The central state container holding runtime data, settings, and cleaning results
 //! - [`MainConfig`]: Game configurations, xEdit executables, and skip lists loaded from `AutoQAC Main.yaml`
-//! - [`UserConfig`]: User preferences and paths loaded from `AutoQAC Config.yaml` or `PACT Settings.yaml`
+//! - [`UserConfig`]: User preferences and paths loaded from `AutoQAC Config.yaml` or `PACT Settings.yaml`,
+//!   including the [`RunnerKind`] used to launch xEdit (native, Wine or Proton) and the
+//!   [`RetryReason`]s that are retried and the [`AdaptiveTimeout`] mode
 //! - [`IgnoreConfig`]: Additional plugin ignore list from `PACT Ignore.yaml`
+//! - [`CleaningHistory`]: Recent xEdit run times and cleaned checksums per plugin from
+//!   `AutoQAC History.yaml`, used for adaptive timeouts and to skip unchanged plugins
+//! - [`SessionCheckpoint`]: Progress of the current run in `AutoQAC Session.yaml`, saved after
+//!   every plugin so an interrupted run can be resumed
+//! - [`GameRegistry`]: Supported games ([`GameDefinition`]) with their xEdit executables, mode
+//!   flags, masters and ignore-list keys
 //! - [`MAX_CONCURRENT_XEDIT_PROCESSES`]: Critical concurrency limit constant (always 1 due to xEdit file locking)
 //!
 //! # Architecture Note
@@ -15,7 +23,16 @@
 //! - **Immutable**: State updates go through StateManager's `update()` method to ensure consistency
 
 pub mod app_state;
+pub mod checkpoint;
 pub mod config;
+pub mod game;
+pub mod history;
 
-pub use app_state::{AppState, MAX_CONCURRENT_XEDIT_PROCESSES};
-pub use config::{IgnoreConfig, MainConfig, PactData, UserConfig};
+pub use app_state::{AppState, MAX_CONCURRENT_XEDIT_PROCESSES, PluginFilter, PluginListItem};
+pub use checkpoint::{CheckpointSettings, PluginOutcome, SessionCheckpoint};
+pub use config::{
+    AdaptiveTimeout, IgnoreConfig, MainConfig, PactData, PactSettings, RetryReason, RunnerKind,
+    UserConfig,
+};
+pub use game::{GameDefinition, GameRegistry};
+pub use history::CleaningHistory;
@@ -1,11 +1,20 @@
+use super::Runner;
+use super::command::{CommandError, CommandOptions, CommandValues};
+use super::exception_log::XEditException;
+use super::hang::{HangDetector, ProcessTree, SAMPLE_INTERVAL};
+use super::messages::{Message, MessageCatalog, MessageId};
+use super::navmesh::DeletedNavmesh;
+use crate::i18n;
+use crate::models::GameDefinition;
 use anyhow::{Context, Result};
-use camino::{Utf8Path, Utf8PathBuf};
+use camino::Utf8Path;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::time::{Duration, Instant};
 use thiserror::Error;
-use tokio::process::Command;
-use tokio::time::timeout;
+use tokio::process::Child;
+use tokio::time::MissedTickBehavior;
 
 /// Result of a plugin cleaning operation
 #[derive(Debug, Clone)]
@@ -26,7 +35,8 @@ pub enum CleanStatus {
 }
 
 /// Statistics from a cleaning operation
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
 pub struct CleaningStats {
     pub undeleted: usize,     // Undisabled References (UDR)
     pub removed: usize,       // Identical To Master (ITM)
@@ -40,25 +50,63 @@ impl CleaningStats {
         self.undeleted > 0 || self.removed > 0 || self.skipped > 0 || self.partial_forms > 0
     }
 
+    /// Check if xEdit changed the plugin; deleted navmeshes are only reported, not fixed
+    pub fn modified_plugin(&self) -> bool {
+        self.undeleted > 0 || self.removed > 0 || self.partial_forms > 0
+    }
+
+    /// Statistics of several QAC passes over the same plugin.
+    ///
+    /// Fixed records add up. Deleted navmeshes are left in place, so every pass reports
+    /// them again and the last pass's count is kept.
+    pub fn combined(passes: &[CleaningStats]) -> CleaningStats {
+        let mut total = CleaningStats {
+            skipped: passes.last().map_or(0, |pass| pass.skipped),
+            ..Default::default()
+        };
+        for pass in passes {
+            total.undeleted += pass.undeleted;
+            total.removed += pass.removed;
+            total.partial_forms += pass.partial_forms;
+        }
+        total
+    }
+
     /// Get a summary string of what was cleaned
     pub fn summary(&self) -> String {
         let mut parts = Vec::new();
 
         if self.undeleted > 0 {
-            parts.push(format!("{} UDRs", self.undeleted));
+            parts.push(i18n::tr_format(
+                "GuiController",
+                "{} UDRs",
+                &[&self.undeleted],
+            ));
         }
         if self.removed > 0 {
-            parts.push(format!("{} ITMs", self.removed));
+            parts.push(i18n::tr_format(
+                "GuiController",
+                "{} ITMs",
+                &[&self.removed],
+            ));
         }
         if self.skipped > 0 {
-            parts.push(format!("{} deleted navmeshes", self.skipped));
+            parts.push(i18n::tr_format(
+                "GuiController",
+                "{} deleted navmeshes",
+                &[&self.skipped],
+            ));
         }
         if self.partial_forms > 0 {
-            parts.push(format!("{} partial forms", self.partial_forms));
+            parts.push(i18n::tr_format(
+                "GuiController",
+                "{} partial forms",
+                &[&self.partial_forms],
+            ));
         }
 
         if parts.is_empty() {
-            "Nothing to clean".to_string()
+            i18n::tr("GuiController", "Nothing to clean")
         } else {
             parts.join(", ")
         }
@@ -71,26 +119,102 @@ pub enum CleaningError {
     #[error("Plugin {0} not found")]
     PluginNotFound(String),
 
+    #[error("{0} is not in the game Data folder; install it before cleaning it")]
+    PluginNotInstalled(String),
+
     #[error("xEdit executable not configured")]
     XEditNotConfigured,
 
+    #[error("Load order file not configured")]
+    LoadOrderNotConfigured,
+
+    #[error("Game Data folder not configured")]
+    DataFolderNotConfigured,
+
     #[error("Game type not configured")]
     GameTypeNotConfigured,
 
     #[error("Timeout after {0:?}")]
     Timeout(Duration),
 
+    #[error("xEdit hung (below {threshold}% CPU for {window:?})")]
+    Hung { threshold: u32, window: Duration },
+
     #[error("Process error: {0}")]
     ProcessError(#[from] std::io::Error),
 
-    #[error("Plugin has missing requirements or is empty: {0}")]
-    MissingRequirements(String),
+    #[error("xEdit exception for {plugin}: {exception}")]
+    Exception {
+        plugin: String,
+        exception: XEditException,
+    },
 
     #[error("Log file not found: {0}")]
     LogFileNotFound(String),
 
     #[error("Failed to parse log file: {0}")]
     LogParseError(String),
+
+    #[error("Invalid xEdit command: {0}")]
+    InvalidCommand(#[from] CommandError),
+
+    #[error("Another AutoQAC (process {0}) is cleaning")]
+    AlreadyRunning(u32),
+
+    #[error("Programs using the plugins are running: {}", .0.join(", "))]
+    ProgramsRunning(Vec<String>),
+}
+
+impl CleaningError {
+    /// Catalog message shown to the user for this error
+    pub fn message_id(&self) -> MessageId {
+        match self {
+            CleaningError::PluginNotFound(_) => MessageId::PluginNotFound,
+            CleaningError::PluginNotInstalled(_) => MessageId::PluginNotInstalled,
+            CleaningError::XEditNotConfigured => MessageId::XEditNotConfigured,
+            CleaningError::LoadOrderNotConfigured => MessageId::LoadOrderNotConfigured,
+            CleaningError::DataFolderNotConfigured => MessageId::DataFolderNotConfigured,
+            CleaningError::GameTypeNotConfigured => MessageId::GameTypeNotConfigured,
+            CleaningError::Timeout(_) => MessageId::CleaningTimeout,
+            CleaningError::Hung { .. } => MessageId::XEditHung,
+            CleaningError::ProcessError(_) => MessageId::ProcessFailed,
+            CleaningError::Exception { exception, .. } => exception.message_id(),
+            CleaningError::LogFileNotFound(_) => MessageId::LogFileNotFound,
+            CleaningError::LogParseError(_) => MessageId::LogParseFailed,
+            CleaningError::InvalidCommand(_) => MessageId::InvalidCommand,
+            CleaningError::AlreadyRunning(_) => MessageId::AlreadyRunning,
+            CleaningError::ProgramsRunning(_) => MessageId::ProgramsRunning,
+        }
+    }
+
+    /// User-facing message from the catalog, with the error's details filled in.
+    ///
+    /// The `Display` text stays in English for the logs.
+    pub fn to_message(&self, catalog: &MessageCatalog) -> Message {
+        let id = self.message_id();
+        match self {
+            CleaningError::PluginNotFound(plugin) | CleaningError::PluginNotInstalled(plugin) => {
+                catalog.format(id, &[("plugin", plugin)])
+            }
+            CleaningError::Exception { plugin, exception } => exception.to_message(catalog, plugin),
+            CleaningError::Timeout(duration) => {
+                catalog.format(id, &[("timeout", &duration.as_secs())])
+            }
+            CleaningError::Hung { threshold, window } => catalog.format(
+                id,
+                &[("threshold", threshold), ("window", &window.as_secs())],
+            ),
+            CleaningError::ProcessError(error) => catalog.format(id, &[("error", error)]),
+            CleaningError::LogFileNotFound(path) => catalog.format(id, &[("path", path)]),
+            CleaningError::LogParseError(error) => catalog.format(id, &[("error", error)]),
+            CleaningError::InvalidCommand(error) => catalog.format(id, &[("error", error)]),
+            CleaningError::AlreadyRunning(pid) => catalog.format(id, &[("pid", pid)]),
+            CleaningError::ProgramsRunning(programs) => {
+                catalog.format(id, &[("programs", &programs.join("\n"))])
+            }
+            _ => catalog.get(id),
+        }
+    }
 }
 
 /// Service for cleaning plugins with xEdit
@@ -150,86 +274,24 @@ impl CleaningService {
         }
     }
 
-    /// Get the log file paths for xEdit
-    ///
-    /// xEdit creates log files in the same directory as the executable:
-    /// - Main log: `<GameMode>Edit_log.txt` or `<XEDIT>_log.txt`
-    /// - Exception log: `<GameMode>EditException.log` or `<XEDIT>Exception.log`
-    ///
-    /// # Arguments
-    /// * `xedit_exe_path` - Path to the xEdit executable
-    /// * `game_type` - Optional game type for universal xEdit (e.g., "FO4", "SSE")
-    ///
-    /// # Errors
-    /// Returns `CleaningError::XEditNotConfigured` if the xEdit path is invalid
-    pub fn get_log_paths(
-        &self,
-        xedit_exe_path: &Utf8Path,
-        game_type: Option<&str>,
-    ) -> Result<(Utf8PathBuf, Utf8PathBuf), CleaningError> {
-        let xedit_dir = xedit_exe_path
-            .parent()
-            .ok_or(CleaningError::XEditNotConfigured)?;
-        let xedit_stem = xedit_exe_path
-            .file_stem()
-            .ok_or(CleaningError::XEditNotConfigured)?;
-
-        // Determine the base name for log files
-        let log_base = if let Some(game) = game_type {
-            // Universal xEdit with game mode: FO4Edit, SSEEdit, etc.
-            format!("{}Edit", game.to_uppercase())
-        } else {
-            // Specific xEdit: Use the exe stem (e.g., "SSEEdit" from "SSEEdit.exe")
-            xedit_stem.to_uppercase()
-        };
-
-        let main_log = xedit_dir.join(format!("{}_log.txt", log_base));
-        let exception_log = xedit_dir.join(format!("{}Exception.log", log_base));
-
-        Ok((main_log, exception_log))
-    }
-
-    /// Clear xEdit log files before cleaning
-    ///
-    /// This ensures we're reading fresh logs for each plugin.
-    /// Python equivalent: `clear_xedit_logs()`
-    pub fn clear_logs(&self, main_log: &Utf8Path, exception_log: &Utf8Path) -> Result<()> {
-        if main_log.exists() {
-            fs::remove_file(main_log)
-                .with_context(|| format!("Failed to remove main log: {}", main_log))?;
-            tracing::debug!("Cleared main log: {}", main_log);
-        }
-
-        if exception_log.exists() {
-            fs::remove_file(exception_log)
-                .with_context(|| format!("Failed to remove exception log: {}", exception_log))?;
-            tracing::debug!("Cleared exception log: {}", exception_log);
-        }
-
-        Ok(())
-    }
-
     /// Check the exception log for errors
     ///
-    /// Returns true if the plugin has missing requirements or is empty.
+    /// Returns the classified error, or None if the log is missing or empty.
     /// Python equivalent: `check_process_exceptions()`
-    pub fn check_exception_log(&self, exception_log: &Utf8Path) -> Result<bool> {
+    pub fn check_exception_log(&self, exception_log: &Utf8Path) -> Result<Option<XEditException>> {
         if !exception_log.exists() {
-            return Ok(false);
+            return Ok(None);
         }
 
         let content = fs::read_to_string(exception_log)
             .with_context(|| format!("Failed to read exception log: {}", exception_log))?;
 
-        // Check for known error patterns from Python version
-        let has_error = content.contains("which can not be found")
-            || content.contains("which it does not have");
-
-        if has_error {
-            tracing::warn!("Exception log indicates missing requirements or empty plugin");
+        let exception = XEditException::parse(&content);
+        if let Some(ref exception) = exception {
+            tracing::warn!("Exception log: {}", exception);
         }
 
-        Ok(has_error)
+        Ok(exception)
     }
 
     /// Parse the main log file to get cleaning statistics
@@ -272,14 +334,30 @@ impl CleaningService {
         Ok(stats)
     }
 
-    /// Build the xEdit cleaning command
+    /// Read the deleted navmeshes (`Skipping:` lines) from xEdit's main log
+    ///
+    /// # Errors
+    /// Returns an error if the log file cannot be read
+    pub fn deleted_navmeshes(&self, main_log: &Utf8Path) -> Result<Vec<DeletedNavmesh>> {
+        let content = fs::read_to_string(main_log)
+            .with_context(|| format!("Failed to read main log: {}", main_log))?;
+
+        Ok(content
+            .lines()
+            .filter_map(|line| self.nvm_pattern.captures(line))
+            .map(|captures| DeletedNavmesh::parse(&captures[1]))
+            .collect())
+    }
+
+    /// Build the xEdit cleaning command with the built-in arguments
     ///
     /// Python equivalent: `create_bat_command()` and `_build_cleaning_command()`
     ///
     /// # Arguments
     /// * `xedit_exe_path` - Path to xEdit executable
     /// * `plugin_name` - Name of the plugin to clean
-    /// * `game_type` - Optional game type for universal xEdit
+    /// * `game` - Optional game definition; its mode flag is added when `xedit_exe_path`
+    ///   is not one of the game's dedicated executables (universal xEdit)
     /// * `mo2_exe_path` - Optional MO2 executable path for MO2 mode
     /// * `partial_forms_enabled` - Enable partial forms cleaning
     ///
@@ -289,101 +367,153 @@ impl CleaningService {
         &self,
         xedit_exe_path: &Utf8Path,
         plugin_name: &str,
-        game_type: Option<&str>,
+        game: Option<&GameDefinition>,
         mo2_exe_path: Option<&Utf8Path>,
         partial_forms_enabled: bool,
     ) -> String {
-        let cleaning_flag = "-QAC";
-
-        // Add partial forms flags if enabled (experimental)
-        let partial_forms_options = if partial_forms_enabled {
-            " -iknowwhatimdoing -allowmakepartial"
-        } else {
-            ""
+        let values = CommandValues {
+            partial_forms: partial_forms_enabled,
+            ..Default::default()
         };
 
+        self.build_command(
+            xedit_exe_path,
+            plugin_name,
+            game,
+            mo2_exe_path,
+            &CommandOptions::default(),
+            &values,
+        )
+        .expect("the default command template is valid")
+    }
+
+    /// Build the xEdit cleaning command from the user's command options
+    ///
+    /// The options' template supplies xEdit's arguments; the executable, the universal xEdit
+    /// mode flag and the MO2 wrapper are added around them.
+    ///
+    /// # Arguments
+    /// * `xedit_exe_path` - Path to xEdit executable
+    /// * `plugin_name` - Name of the plugin to clean
+    /// * `game` - Optional game definition (mode flag and per-game arguments)
+    /// * `mo2_exe_path` - Optional MO2 executable path for MO2 mode
+    /// * `options` - Command template and extra arguments
+    /// * `values` - Partial forms flag and the paths filled into the template
+    ///
+    /// # Errors
+    /// Returns a [`CommandError`] if the template or extra arguments are invalid
+    pub fn build_command(
+        &self,
+        xedit_exe_path: &Utf8Path,
+        plugin_name: &str,
+        game: Option<&GameDefinition>,
+        mo2_exe_path: Option<&Utf8Path>,
+        options: &CommandOptions,
+        values: &CommandValues,
+    ) -> Result<String, CommandError> {
+        let args = options.render(game.map_or("", |g| g.id.as_str()), plugin_name, values)?;
+
+        // Universal xEdit needs the game mode flag; dedicated executables already know their game
+        let mode_flag = game
+            .filter(|g| !g.owns_xedit(xedit_exe_path.as_str()))
+            .map(|g| g.mode_flag.as_str());
+
         // Build the command based on MO2 mode and game type
-        if let Some(mo2_path) = mo2_exe_path {
-            // MO2 mode
-            if let Some(game) = game_type {
-                // Universal xEdit with game mode
-                let args = format!(
-                    "{} -autoexit -autoload{} \"{}\"",
-                    cleaning_flag, partial_forms_options, plugin_name
-                );
+        let command = match (mo2_exe_path, mode_flag) {
+            // MO2 mode with universal xEdit: the mode flag goes with the executable
+            (Some(mo2_path), Some(flag)) => {
                 format!(
-                    "\"{}\" run \"{} -{}\" {}",
-                    mo2_path, xedit_exe_path, game, args
+                    "\"{}\" run \"{} {}\" {}",
+                    mo2_path, xedit_exe_path, flag, args
                 )
-            } else {
-                // Specific xEdit
-                let args = format!(
-                    "{} -autoexit -autoload{} \"{}\"",
-                    cleaning_flag, partial_forms_options, plugin_name
-                );
-                format!("\"{}\" run \"{}\" {}", mo2_path, xedit_exe_path, args)
             }
-        } else {
-            // Direct mode (no MO2)
-            if let Some(game) = game_type {
-                // Universal xEdit with game mode
-                format!(
-                    "\"{}\" -{} {} -autoexit -autoload{} \"{}\"",
-                    xedit_exe_path, game, cleaning_flag, partial_forms_options, plugin_name
-                )
-            } else {
-                // Specific xEdit
-                format!(
-                    "\"{}\" {} -autoexit -autoload{} \"{}\"",
-                    xedit_exe_path, cleaning_flag, partial_forms_options, plugin_name
-                )
+            // MO2 mode with a specific xEdit
+            (Some(mo2_path), None) => {
+                format!("\"{}\" run \"{}\" {}", mo2_path, xedit_exe_path, args)
             }
-        }
+            // Direct mode with universal xEdit
+            (None, Some(flag)) => format!("\"{}\" {} {}", xedit_exe_path, flag, args),
+            // Direct mode with a specific xEdit
+            (None, None) => format!("\"{}\" {}", xedit_exe_path, args),
+        };
+
+        Ok(command)
     }
 
     /// Execute xEdit QAC (Quick Auto Clean) for a plugin
     ///
     /// Python equivalent: `run_process()` and subprocess execution
     ///
+    /// While xEdit runs, its CPU usage is sampled; when the detector reports a hang, or the
+    /// timeout passes, xEdit and everything it started are killed.
+    ///
     /// # Arguments
     /// * `command` - The full command to execute
+    /// * `runner` - How to launch it (natively, through Wine or through Proton)
     /// * `timeout_duration` - Maximum time to wait for the process
+    /// * `hang_detector` - When low CPU usage counts as a hang
     ///
     /// # Returns
     /// The process exit code (0 = success)
     pub async fn execute_cleaning_command(
         &self,
         command: &str,
+        runner: &Runner,
         timeout_duration: Duration,
+        mut hang_detector: HangDetector,
     ) -> Result<i32> {
-        tracing::info!("Executing: {}", command);
+        tracing::info!("Executing ({:?}): {}", runner.kind, command);
 
         let start = Instant::now();
 
-        let mut cmd = if cfg!(target_os = "windows") {
-            let mut c = Command::new("cmd");
-            c.args(["/C", command]);
-            c
-        } else {
-            let mut c = Command::new("sh");
-            c.args(["-c", command]);
-            c
-        };
+        let mut cmd = runner.command(command);
 
         // Spawn the process
-        let child = cmd.spawn().context("Failed to spawn xEdit process")?;
-
-        // Execute with timeout
-        let output = timeout(timeout_duration, child.wait_with_output())
-            .await
-            .map_err(|_| {
-                tracing::warn!("xEdit process timed out after {:?}", timeout_duration);
-                CleaningError::Timeout(timeout_duration)
-            })?
-            .context("Failed to wait for xEdit process")?;
+        let mut child = cmd.spawn().context("Failed to spawn xEdit process")?;
+        let mut tree = child.id().map(ProcessTree::new);
+
+        let deadline = tokio::time::sleep(timeout_duration);
+        tokio::pin!(deadline);
+        let mut samples = tokio::time::interval(SAMPLE_INTERVAL);
+        samples.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        // Wait for exit, the timeout or a hang
+        let status = loop {
+            tokio::select! {
+                status = child.wait() => {
+                    break status.context("Failed to wait for xEdit process")?;
+                }
+                _ = &mut deadline => {
+                    tracing::warn!("xEdit process timed out after {:?}", timeout_duration);
+                    Self::stop(&mut child, tree.as_mut()).await;
+                    return Err(CleaningError::Timeout(timeout_duration).into());
+                }
+                _ = samples.tick(), if hang_detector.is_enabled() => {
+                    let Some(tree) = tree.as_mut() else {
+                        continue;
+                    };
+
+                    let usage = tree.cpu_usage();
+                    tracing::trace!("xEdit CPU usage: {:.1}%", usage);
+                    if hang_detector.observe(usage, Instant::now()) {
+                        tracing::warn!(
+                            "xEdit process hung: below {}% CPU for {:?}",
+                            hang_detector.threshold,
+                            hang_detector.window
+                        );
+                        Self::stop(&mut child, Some(tree)).await;
+                        return Err(CleaningError::Hung {
+                            threshold: hang_detector.threshold,
+                            window: hang_detector.window,
+                        }
+                        .into());
+                    }
+                }
+            }
+        };
 
         let duration = start.elapsed();
-        let exit_code = output.status.code().unwrap_or(-1);
+        let exit_code = status.code().unwrap_or(-1);
 
         tracing::info!(
             "xEdit process completed in {:.2}s with exit code {}",
@@ -393,6 +523,16 @@ impl CleaningService {
 
         Ok(exit_code)
     }
+
+    /// Kill xEdit with everything it started, then reap the launched process
+    async fn stop(child: &mut Child, tree: Option<&mut ProcessTree>) {
+        if let Some(tree) = tree {
+            tree.kill();
+        }
+        if let Err(e) = child.kill().await {
+            tracing::warn!("Failed to kill xEdit process: {}", e);
+        }
+    }
 }
 
 impl Default for CleaningService {
@@ -404,27 +544,8 @@ impl Default for CleaningService {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn test_log_paths_specific_xedit() {
-        let service = CleaningService::new();
-        let xedit_path = Utf8PathBuf::from("C:/Games/SSEEdit.exe");
-        let (main_log, exc_log) = service.get_log_paths(&xedit_path, None).unwrap();
-
-        assert_eq!(main_log, Utf8PathBuf::from("C:/Games/SSEEDIT_log.txt"));
-        assert_eq!(exc_log, Utf8PathBuf::from("C:/Games/SSEEDITException.log"));
-    }
-
-    #[test]
-    fn test_log_paths_universal_xedit() {
-        let service = CleaningService::new();
-        let xedit_path = Utf8PathBuf::from("C:/Games/xEdit.exe");
-        let (main_log, exc_log) = service.get_log_paths(&xedit_path, Some("FO4")).unwrap();
-
-        // Note: path separator might be \ or / depending on OS
-        assert!(main_log.as_str().ends_with("FO4Edit_log.txt"));
-        assert!(exc_log.as_str().ends_with("FO4EditException.log"));
-    }
+    use crate::models::GameRegistry;
+    use camino::Utf8PathBuf;
 
     #[test]
     fn test_parse_log_file() {
@@ -449,6 +570,10 @@ mod tests {
         assert_eq!(stats.skipped, 1);
         assert_eq!(stats.partial_forms, 1);
         assert!(stats.has_changes());
+
+        let navmeshes = service.deleted_navmeshes(&path).unwrap();
+        assert_eq!(navmeshes.len(), 1);
+        assert_eq!(navmeshes[0].form_id.as_deref(), Some("00000004"));
     }
 
     #[test]
@@ -466,6 +591,61 @@ mod tests {
         assert!(summary.contains("1 deleted navmeshes"));
     }
 
+    #[test]
+    fn test_combined_pass_stats() {
+        let passes = [
+            CleaningStats {
+                undeleted: 3,
+                removed: 5,
+                skipped: 1,
+                partial_forms: 0,
+            },
+            CleaningStats {
+                undeleted: 0,
+                removed: 2,
+                skipped: 1,
+                partial_forms: 0,
+            },
+            CleaningStats {
+                skipped: 1,
+                ..Default::default()
+            },
+        ];
+
+        assert!(passes[1].modified_plugin());
+        assert!(!passes[2].modified_plugin());
+        assert!(passes[2].has_changes());
+
+        let total = CleaningStats::combined(&passes);
+        assert_eq!(total.undeleted, 3);
+        assert_eq!(total.removed, 7);
+        assert_eq!(total.skipped, 1);
+        assert_eq!(total.partial_forms, 0);
+        assert!(!CleaningStats::combined(&[]).has_changes());
+    }
+
+    #[test]
+    fn test_cleaning_error_messages() {
+        let catalog = MessageCatalog::builtin();
+
+        let message = CleaningError::Timeout(Duration::from_secs(300)).to_message(&catalog);
+        assert_eq!(message.id, MessageId::CleaningTimeout);
+        assert_eq!(message.title(), "xEdit Timed Out");
+        assert_eq!(
+            message.summary(),
+            "xEdit did not finish within 300 seconds."
+        );
+
+        let message = CleaningError::XEditNotConfigured.to_message(&catalog);
+        assert_eq!(message.title(), "xEdit Not Configured");
+
+        let message =
+            CleaningError::ProgramsRunning(vec!["CreationKit.exe".into(), "SSEEdit.exe".into()])
+                .to_message(&catalog);
+        assert_eq!(message.title(), "Programs Using Your Plugins");
+        assert!(message.body().contains("CreationKit.exe\nSSEEdit.exe"));
+    }
+
     #[test]
     fn test_build_cleaning_command_direct() {
         let service = CleaningService::new();
@@ -499,6 +679,21 @@ mod tests {
         assert!(cmd.contains("run"));
     }
 
+    #[test]
+    fn test_build_cleaning_command_mode_flag_only_for_universal_xedit() {
+        let service = CleaningService::new();
+        let registry = GameRegistry::builtin();
+        let sse = registry.get("SSE");
+
+        let universal = Utf8PathBuf::from("C:/Games/xEdit64.exe");
+        let cmd = service.build_cleaning_command(&universal, "Test.esp", sse, None, false);
+        assert!(cmd.contains(" -sse -QAC"));
+
+        let dedicated = Utf8PathBuf::from("C:/Games/SSEEdit.exe");
+        let cmd = service.build_cleaning_command(&dedicated, "Test.esp", sse, None, false);
+        assert!(!cmd.contains("-sse"));
+    }
+
     #[test]
     fn test_regex_patterns() {
         let service = CleaningService::new();
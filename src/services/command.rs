@@ -0,0 +1,527 @@
+//! xEdit command-line options: extra arguments and the argument template.
+//!
+//! By default xEdit is started with `-QAC -autoexit -autoload`, plus the partial forms flags
+//! when they are enabled. The user config can change that without patching the code:
+//! - `XEdit Arguments`: options added for every game (e.g. `-cache -nobuildrefs`)
+//! - `XEdit Game Arguments`: options added for one game, keyed by game id
+//! - `Command Template`: the whole xEdit argument list, replacing [`DEFAULT_TEMPLATE`]
+//!
+//! The executable, the universal xEdit mode flag and the MO2 wrapper are not part of the
+//! template; [`CleaningService`](super::CleaningService) adds them around it.
+//!
+//! Templates and extra arguments may use these placeholders:
+//! - `{plugin}`: the plugin being cleaned
+//! - `{partial}`: `-iknowwhatimdoing -allowmakepartial` when partial forms are enabled
+//! - `{args}`: the global and per-game extra arguments (template only)
+//! - `{data}`: the game Data folder
+//! - `{load_order}`: the load order file
+//! - `{log}`: the plugin's main log in the session folder (required, passed with `-R:`)
+//!
+//! An argument whose placeholder has no value is left out, so `-D:"{data}"` disappears when no
+//! Data folder is configured. Paths and plugin names may contain spaces, so their placeholders
+//! must be quoted.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::command::{CommandOptions, CommandValues};
+//!
+//! let options = CommandOptions {
+//!     args: "-cache".to_string(),
+//!     ..Default::default()
+//! };
+//! options.validate()?;
+//!
+//! let args = options.render("SSE", "MyPlugin.esp", &CommandValues::default())?;
+//! assert_eq!(args, r#"-QAC -autoexit -autoload -cache "MyPlugin.esp""#);
+//! ```
+
+use crate::models::PactSettings;
+use indexmap::IndexMap;
+use thiserror::Error;
+
+/// xEdit arguments used when no `Command Template` is set
+pub const DEFAULT_TEMPLATE: &str =
+    r#"-QAC -autoexit -autoload -R:"{log}" {partial} {args} "{plugin}""#;
+
+/// Flags that let xEdit create partial forms (experimental)
+const PARTIAL_FORMS_FLAGS: &str = "-iknowwhatimdoing -allowmakepartial";
+
+/// Options every template needs so xEdit cleans and exits without user input
+const REQUIRED_OPTIONS: [&str; 3] = ["-QAC", "-autoexit", "-autoload"];
+
+/// Placeholders whose values may contain spaces
+const QUOTED_PLACEHOLDERS: [&str; 4] = ["plugin", "data", "load_order", "log"];
+
+/// Every placeholder a template knows
+const PLACEHOLDERS: [&str; 6] = ["plugin", "partial", "args", "data", "load_order", "log"];
+
+/// Characters the Windows or Unix shell would interpret on a native run
+const SHELL_CHARACTERS: [char; 9] = ['&', '|', '<', '>', ';', '^', '%', '$', '`'];
+
+/// Setting names used in error messages
+const TEMPLATE_SETTING: &str = "Command Template";
+const ARGS_SETTING: &str = "XEdit Arguments";
+
+/// A problem with the command template or the extra arguments
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    #[error("{setting}: unknown placeholder {{{name}}}")]
+    UnknownPlaceholder { setting: String, name: String },
+
+    #[error("{setting}: {{{name}}} cannot be used here")]
+    PlaceholderNotAllowed { setting: String, name: String },
+
+    #[error("{setting}: a placeholder is missing its closing }}")]
+    UnclosedPlaceholder { setting: String },
+
+    #[error("{setting}: {{{name}}} must be inside quotes, e.g. \"{{{name}}}\"")]
+    UnquotedPlaceholder { setting: String, name: String },
+
+    #[error("{setting}: a quote is not closed")]
+    UnbalancedQuotes { setting: String },
+
+    #[error("{setting}: '{character}' is not allowed")]
+    ShellCharacter { setting: String, character: char },
+
+    #[error("{setting}: {argument} is not an xEdit option (options start with -)")]
+    NotAnOption { setting: String, argument: String },
+
+    #[error("Command Template must contain {0}")]
+    MissingRequired(String),
+}
+
+/// Values filled into the placeholders, as xEdit sees them (Windows paths)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandValues {
+    pub partial_forms: bool,
+    pub data: Option<String>,
+    pub load_order: Option<String>,
+    pub log: Option<String>,
+}
+
+/// User-configurable xEdit command-line options
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandOptions {
+    /// Argument template (None uses [`DEFAULT_TEMPLATE`])
+    pub template: Option<String>,
+
+    /// Extra arguments for every game
+    pub args: String,
+
+    /// Extra arguments for one game, keyed by game id
+    pub game_args: IndexMap<String, String>,
+}
+
+impl CommandOptions {
+    /// Build the options from the user settings.
+    pub fn from_settings(settings: &PactSettings) -> Self {
+        let template = settings.command_template.trim();
+
+        Self {
+            template: (!template.is_empty()).then(|| template.to_string()),
+            args: settings.xedit_arguments.trim().to_string(),
+            game_args: settings
+                .xedit_game_arguments
+                .iter()
+                .map(|(game, args)| (game.clone(), args.trim().to_string()))
+                .filter(|(_, args)| !args.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Template in use
+    pub fn template(&self) -> &str {
+        self.template.as_deref().unwrap_or(DEFAULT_TEMPLATE)
+    }
+
+    /// Extra arguments for a game (case-insensitive game id)
+    pub fn game_args(&self, game_id: &str) -> Option<&str> {
+        self.game_args
+            .iter()
+            .find(|(game, _)| game.eq_ignore_ascii_case(game_id))
+            .map(|(_, args)| args.as_str())
+    }
+
+    /// Check the template and every extra argument list.
+    ///
+    /// # Errors
+    /// Returns the first problem found
+    pub fn validate(&self) -> Result<(), CommandError> {
+        let template = parse(TEMPLATE_SETTING, self.template())?;
+
+        if !template
+            .iter()
+            .any(|arg| arg.placeholders.contains(&"plugin"))
+        {
+            return Err(CommandError::MissingRequired("\"{plugin}\"".to_string()));
+        }
+        if !template.iter().any(|arg| arg.placeholders.contains(&"log")) {
+            return Err(CommandError::MissingRequired("-R:\"{log}\"".to_string()));
+        }
+        for option in REQUIRED_OPTIONS {
+            if !template
+                .iter()
+                .any(|arg| arg.raw.eq_ignore_ascii_case(option))
+            {
+                return Err(CommandError::MissingRequired(option.to_string()));
+            }
+        }
+
+        parse_args(ARGS_SETTING, &self.args)?;
+        for (game, args) in &self.game_args {
+            parse_args(&format!("XEdit Game Arguments ({})", game), args)?;
+        }
+
+        Ok(())
+    }
+
+    /// Render the xEdit arguments for one plugin.
+    ///
+    /// # Errors
+    /// Returns an error if the options do not pass [`validate`](Self::validate)
+    pub fn render(
+        &self,
+        game_id: &str,
+        plugin: &str,
+        values: &CommandValues,
+    ) -> Result<String, CommandError> {
+        self.validate()?;
+
+        let value = |name: &str| -> Option<String> {
+            match name {
+                "plugin" => Some(plugin.to_string()),
+                "partial" => values
+                    .partial_forms
+                    .then(|| PARTIAL_FORMS_FLAGS.to_string()),
+                "data" => values.data.clone(),
+                "load_order" => values.load_order.clone(),
+                "log" => values.log.clone(),
+                _ => None,
+            }
+        };
+
+        let extra_args = [Some(self.args.as_str()), self.game_args(game_id)]
+            .into_iter()
+            .flatten()
+            .flat_map(split_args)
+            .filter_map(|arg| substitute(&arg, &value))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let rendered = split_args(self.template())
+            .into_iter()
+            .filter_map(|arg| {
+                substitute(&arg, &|name| match name {
+                    "args" => Some(extra_args.clone()),
+                    name => value(name),
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Ok(rendered)
+    }
+}
+
+/// One argument of a template or argument list
+struct Arg {
+    raw: String,
+    placeholders: Vec<&'static str>,
+}
+
+/// Split and check a template or argument list
+fn parse(setting: &str, text: &str) -> Result<Vec<Arg>, CommandError> {
+    if let Some(character) = text.chars().find(|c| SHELL_CHARACTERS.contains(c)) {
+        return Err(CommandError::ShellCharacter {
+            setting: setting.to_string(),
+            character,
+        });
+    }
+    if text.chars().filter(|&c| c == '"').count() % 2 != 0 {
+        return Err(CommandError::UnbalancedQuotes {
+            setting: setting.to_string(),
+        });
+    }
+
+    split_args(text)
+        .into_iter()
+        .map(|raw| {
+            let mut placeholders = Vec::new();
+            let mut in_quotes = false;
+            let mut chars = raw.chars();
+
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => in_quotes = !in_quotes,
+                    '{' => {
+                        let mut name = String::new();
+                        let mut closed = false;
+                        for c in chars.by_ref() {
+                            if c == '}' {
+                                closed = true;
+                                break;
+                            }
+                            name.push(c);
+                        }
+                        if !closed {
+                            return Err(CommandError::UnclosedPlaceholder {
+                                setting: setting.to_string(),
+                            });
+                        }
+                        let Some(&known) = PLACEHOLDERS.iter().find(|&&p| p == name) else {
+                            return Err(CommandError::UnknownPlaceholder {
+                                setting: setting.to_string(),
+                                name,
+                            });
+                        };
+                        if QUOTED_PLACEHOLDERS.contains(&known) && !in_quotes {
+                            return Err(CommandError::UnquotedPlaceholder {
+                                setting: setting.to_string(),
+                                name,
+                            });
+                        }
+                        placeholders.push(known);
+                    }
+                    _ => {}
+                }
+            }
+
+            Ok(Arg { raw, placeholders })
+        })
+        .collect()
+}
+
+/// Check an extra argument list: options only, and no placeholders that need the template
+fn parse_args(setting: &str, text: &str) -> Result<(), CommandError> {
+    for arg in parse(setting, text)? {
+        if let Some(name) = arg
+            .placeholders
+            .iter()
+            .find(|&&name| name == "args" || name == "partial")
+        {
+            return Err(CommandError::PlaceholderNotAllowed {
+                setting: setting.to_string(),
+                name: name.to_string(),
+            });
+        }
+        if !arg.raw.trim_start_matches('"').starts_with('-') {
+            return Err(CommandError::NotAnOption {
+                setting: setting.to_string(),
+                argument: arg.raw,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Split on whitespace outside double quotes, keeping the quotes
+fn split_args(text: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in text.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+
+    if !current.is_empty() {
+        args.push(current);
+    }
+
+    args
+}
+
+/// Fill in an argument's placeholders, or drop it when one of them has no value
+fn substitute(arg: &str, value: &dyn Fn(&str) -> Option<String>) -> Option<String> {
+    let mut out = String::with_capacity(arg.len());
+    let mut rest = arg;
+
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        let filled = value(&rest[start + 1..end]).filter(|v| !v.is_empty())?;
+        out.push_str(&rest[..start]);
+        out.push_str(&filled);
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(template: Option<&str>, args: &str) -> CommandOptions {
+        CommandOptions {
+            template: template.map(str::to_string),
+            args: args.to_string(),
+            game_args: IndexMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_default_template_matches_builtin_arguments() {
+        let options = CommandOptions::default();
+
+        assert_eq!(
+            options
+                .render("SSE", "Test.esp", &CommandValues::default())
+                .unwrap(),
+            r#"-QAC -autoexit -autoload "Test.esp""#
+        );
+
+        let values = CommandValues {
+            partial_forms: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            options.render("SSE", "Test.esp", &values).unwrap(),
+            r#"-QAC -autoexit -autoload -iknowwhatimdoing -allowmakepartial "Test.esp""#
+        );
+
+        // The main log goes to the session folder when one is given
+        let values = CommandValues {
+            log: Some(r"Z:\logs\sessions\1\Test.esp_log.txt".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.render("SSE", "Test.esp", &values).unwrap(),
+            r#"-QAC -autoexit -autoload -R:"Z:\logs\sessions\1\Test.esp_log.txt" "Test.esp""#
+        );
+    }
+
+    #[test]
+    fn test_global_and_game_arguments() {
+        let mut options = options(None, r#"-cache -D:"{data}""#);
+        options
+            .game_args
+            .insert("sse".to_string(), "-nobuildrefs".to_string());
+
+        let values = CommandValues {
+            data: Some(r"C:\Games\Skyrim\Data".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.render("SSE", "My Mod.esp", &values).unwrap(),
+            r#"-QAC -autoexit -autoload -cache -D:"C:\Games\Skyrim\Data" -nobuildrefs "My Mod.esp""#
+        );
+
+        // Other games only get the global arguments; -D: is left out without a Data folder
+        assert_eq!(
+            options
+                .render("FO4", "My Mod.esp", &CommandValues::default())
+                .unwrap(),
+            r#"-QAC -autoexit -autoload -cache "My Mod.esp""#
+        );
+    }
+
+    #[test]
+    fn test_custom_template() {
+        let options = options(
+            Some(r#"-autoload -QAC -autoexit -veryquickshowconflicts -R:"{log}" "{plugin}""#),
+            "",
+        );
+        let values = CommandValues {
+            log: Some(r"Z:\xEdit\SSEEdit_log.txt".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            options.render("SSE", "Test.esp", &values).unwrap(),
+            r#"-autoload -QAC -autoexit -veryquickshowconflicts -R:"Z:\xEdit\SSEEdit_log.txt" "Test.esp""#
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        let error = |template: &str| options(Some(template), "").validate().unwrap_err();
+
+        assert_eq!(
+            error(r#"-autoexit -autoload -R:"{log}" "{plugin}""#),
+            CommandError::MissingRequired("-QAC".to_string())
+        );
+        assert_eq!(
+            error(r#"-QAC -autoexit -autoload "{plugin}""#),
+            CommandError::MissingRequired("-R:\"{log}\"".to_string())
+        );
+        assert_eq!(
+            error("-QAC -autoexit -autoload"),
+            CommandError::MissingRequired("\"{plugin}\"".to_string())
+        );
+        assert!(matches!(
+            error(r#"-QAC -autoexit -autoload {plugin}"#),
+            CommandError::UnquotedPlaceholder { .. }
+        ));
+        assert!(matches!(
+            error(r#"-QAC -autoexit -autoload {plugins} "{plugin}""#),
+            CommandError::UnknownPlaceholder { .. }
+        ));
+        assert!(matches!(
+            error(r#"-QAC -autoexit -autoload "{plugin""#),
+            CommandError::UnclosedPlaceholder { .. }
+        ));
+        assert!(matches!(
+            error(r#"-QAC -autoexit -autoload "{plugin}"#),
+            CommandError::UnbalancedQuotes { .. }
+        ));
+        assert!(matches!(
+            error(r#"-QAC -autoexit -autoload "{plugin}" & del *.esp"#),
+            CommandError::ShellCharacter { character: '&', .. }
+        ));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(matches!(
+            options(None, "-cache nobuildrefs").validate(),
+            Err(CommandError::NotAnOption { argument, .. }) if argument == "nobuildrefs"
+        ));
+        assert!(matches!(
+            options(None, "{args}").validate(),
+            Err(CommandError::PlaceholderNotAllowed { .. })
+        ));
+
+        let mut options = options(None, "");
+        options
+            .game_args
+            .insert("FO4".to_string(), "-cache; rm".to_string());
+        assert_eq!(
+            options.validate().unwrap_err().to_string(),
+            "XEdit Game Arguments (FO4): ';' is not allowed"
+        );
+    }
+
+    #[test]
+    fn test_from_settings() {
+        let mut settings = PactSettings {
+            xedit_arguments: " -cache ".to_string(),
+            ..Default::default()
+        };
+        settings
+            .xedit_game_arguments
+            .insert("SSE".to_string(), "-nobuildrefs".to_string());
+        settings
+            .xedit_game_arguments
+            .insert("FO4".to_string(), String::new());
+
+        let options = CommandOptions::from_settings(&settings);
+        assert_eq!(options.template(), DEFAULT_TEMPLATE);
+        assert_eq!(options.args, "-cache");
+        assert_eq!(options.game_args("sse"), Some("-nobuildrefs"));
+        assert_eq!(options.game_args("FO4"), None);
+    }
+}
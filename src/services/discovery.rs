@@ -0,0 +1,496 @@
+//! Discovery of game installs, load order files and xEdit for first-run setup.
+//!
+//! Instead of browsing for every path, the GUI can ask this module for setup candidates.
+//! It looks in:
+//! - Steam libraries listed in `steamapps/libraryfolders.vdf`
+//! - GOG install folders and Epic Games Launcher manifests (`*.item`)
+//! - `%LOCALAPPDATA%/<Game>/plugins.txt`, or the same folder inside a Wine/Proton prefix
+//! - Common xEdit locations (`C:\Modding`, `C:\Tools`, ...) and the game folders themselves
+//!
+//! Installs are identified by the masters in their `Data` folder, so renamed folders and
+//! total conversions (TTW inside Fallout New Vegas) are recognized like any other game.
+//! Candidates are ranked by how much was found for each game.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::models::GameRegistry;
+//! use autoqac::services::discovery::{DiscoverySources, discover};
+//! use autoqac::services::Runner;
+//!
+//! let runner = Runner::default();
+//! let registry = GameRegistry::builtin();
+//! let candidates = discover(&registry, &runner, &DiscoverySources::for_runner(&runner));
+//! if let Some(best) = candidates.first() {
+//!     println!("{}: {:?} / {:?}", best.game_id, best.load_order, best.xedit);
+//! }
+//! ```
+
+use crate::models::GameRegistry;
+use crate::services::Runner;
+use crate::services::game_detection::{find_load_order, refine_game_by_form_version};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::cmp::Reverse;
+use std::env;
+use std::fmt;
+use std::fs;
+
+/// Steam's list of library folders, relative to a Steam root
+const LIBRARY_FOLDERS_VDF: &str = "steamapps/libraryfolders.vdf";
+
+/// Default Windows install locations, translated through the runner for Wine/Proton
+const WINDOWS_STEAM_ROOTS: &[&str] =
+    &["C:\\Program Files (x86)\\Steam", "C:\\Program Files\\Steam"];
+const WINDOWS_GOG_DIRS: &[&str] = &[
+    "C:\\GOG Games",
+    "C:\\Program Files (x86)\\GOG Galaxy\\Games",
+];
+const WINDOWS_EPIC_MANIFESTS: &[&str] =
+    &["C:\\ProgramData\\Epic\\EpicGamesLauncher\\Data\\Manifests"];
+const WINDOWS_XEDIT_DIRS: &[&str] = &["C:\\Modding", "C:\\Tools", "C:\\xEdit", "C:\\Games"];
+
+/// Native Linux Steam roots, relative to `$HOME`
+const LINUX_STEAM_ROOTS: &[&str] = &[
+    ".steam/steam",
+    ".local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+];
+
+/// Where a game install was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallSource {
+    Steam,
+    Gog,
+    Epic,
+}
+
+impl fmt::Display for InstallSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstallSource::Steam => write!(f, "Steam"),
+            InstallSource::Gog => write!(f, "GOG"),
+            InstallSource::Epic => write!(f, "Epic Games"),
+        }
+    }
+}
+
+/// A game install identified from its Data folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameInstall {
+    pub game_id: String,
+    pub source: InstallSource,
+
+    /// Game folder (containing `Data`)
+    pub path: Utf8PathBuf,
+}
+
+impl GameInstall {
+    /// The install's Data folder
+    pub fn data_path(&self) -> Utf8PathBuf {
+        self.path.join("Data")
+    }
+}
+
+/// Everything found for one game, ready to fill in the setup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupCandidate {
+    pub game_id: String,
+    pub install: Option<GameInstall>,
+    pub load_order: Option<Utf8PathBuf>,
+    pub xedit: Option<Utf8PathBuf>,
+
+    /// Ranking score; higher means more of the setup was found
+    pub score: u32,
+}
+
+impl SetupCandidate {
+    /// Data folder of the install, if one was found
+    pub fn data_path(&self) -> Option<Utf8PathBuf> {
+        self.install.as_ref().map(GameInstall::data_path)
+    }
+}
+
+/// Folders searched during discovery
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoverySources {
+    /// Steam roots containing `steamapps/libraryfolders.vdf`
+    pub steam_roots: Vec<Utf8PathBuf>,
+
+    /// Folders whose subfolders are GOG game installs
+    pub gog_dirs: Vec<Utf8PathBuf>,
+
+    /// Epic Games Launcher manifest folders (`*.item` files)
+    pub epic_manifest_dirs: Vec<Utf8PathBuf>,
+
+    /// Folders searched (one level deep) for xEdit executables
+    pub xedit_dirs: Vec<Utf8PathBuf>,
+}
+
+impl DiscoverySources {
+    /// Default locations for the platform.
+    ///
+    /// Windows locations are translated into the prefix for Wine and Proton runners; on
+    /// Linux the native Steam roots are searched as well.
+    pub fn for_runner(runner: &Runner) -> Self {
+        let windows = |paths: &[&str]| -> Vec<Utf8PathBuf> {
+            if runner.is_native() && !cfg!(target_os = "windows") {
+                return Vec::new();
+            }
+            paths.iter().map(|path| runner.host_path(path)).collect()
+        };
+
+        let mut sources = Self {
+            steam_roots: windows(WINDOWS_STEAM_ROOTS),
+            gog_dirs: windows(WINDOWS_GOG_DIRS),
+            epic_manifest_dirs: windows(WINDOWS_EPIC_MANIFESTS),
+            xedit_dirs: windows(WINDOWS_XEDIT_DIRS),
+        };
+
+        if !cfg!(target_os = "windows")
+            && let Ok(home) = env::var("HOME")
+        {
+            let home = Utf8PathBuf::from(home);
+            sources
+                .steam_roots
+                .extend(LINUX_STEAM_ROOTS.iter().map(|root| home.join(root)));
+            sources
+                .xedit_dirs
+                .extend(["Modding", "Games"].iter().map(|dir| home.join(dir)));
+        }
+
+        sources
+    }
+}
+
+/// Discover setup candidates, best first.
+///
+/// Every game with an identified install, a `plugins.txt` or a dedicated xEdit becomes a
+/// candidate. Total conversions are only offered when their install was identified, since
+/// they share the base game's `plugins.txt`.
+pub fn discover(
+    registry: &GameRegistry,
+    runner: &Runner,
+    sources: &DiscoverySources,
+) -> Vec<SetupCandidate> {
+    let installs = find_installs(registry, runner, sources);
+
+    let mut xedit_dirs = sources.xedit_dirs.clone();
+    xedit_dirs.extend(installs.iter().map(|install| install.path.clone()));
+    let xedits = find_xedits(registry, &xedit_dirs);
+    let universal = xedits
+        .iter()
+        .find(|path| registry.is_universal_xedit(path.as_str()));
+
+    let mut candidates: Vec<SetupCandidate> = registry
+        .games()
+        .filter_map(|game| {
+            let install = installs.iter().find(|i| i.game_id == game.id).cloned();
+            if game.base_game.is_some() && install.is_none() {
+                return None;
+            }
+
+            let load_order = find_load_order(registry, runner, Some(&game.id));
+            // Total conversions also run on their base game's xEdit (FNVEdit for TTW)
+            let dedicated = registry
+                .lineage(&game.id)
+                .into_iter()
+                .find_map(|owner| xedits.iter().find(|path| owner.owns_xedit(path.as_str())));
+
+            let mut score = 0;
+            if install.is_some() {
+                score += 3;
+            }
+            if load_order.is_some() {
+                score += 4;
+            }
+            if dedicated.is_some() {
+                score += 3;
+            }
+            if score == 0 {
+                return None;
+            }
+
+            // A universal xEdit works for any game, but only counts once something else matched
+            let xedit = dedicated.or(universal).cloned();
+            if dedicated.is_none() && xedit.is_some() {
+                score += 1;
+            }
+
+            Some(SetupCandidate {
+                game_id: game.id.clone(),
+                install,
+                load_order,
+                xedit,
+                score,
+            })
+        })
+        .collect();
+
+    // Stable sort keeps registry order for equal scores
+    candidates.sort_by_key(|candidate| Reverse(candidate.score));
+
+    tracing::info!("Discovered {} setup candidates", candidates.len());
+    candidates
+}
+
+/// Identify the game installed in a folder from the masters in its Data folder.
+///
+/// Marker plugins (SkyrimVR.esm, TaleOfTwoWastelands.esm) win over base masters; games
+/// sharing a base master are told apart by its form version.
+pub fn identify_install(registry: &GameRegistry, install_dir: &Utf8Path) -> Option<String> {
+    let data = install_dir.join("Data");
+    if !data.is_dir() {
+        return None;
+    }
+
+    if let Some(game) = registry.games().find(|game| {
+        game.markers
+            .iter()
+            .any(|marker| data.join(marker).is_file())
+    }) {
+        return Some(game.id.clone());
+    }
+
+    registry
+        .games()
+        .find(|game| {
+            game.markers.is_empty()
+                && game.base_game.is_none()
+                && !game.base_master.is_empty()
+                && data.join(&game.base_master).is_file()
+        })
+        .map(|game| refine_game_by_form_version(registry, &game.id, &data))
+}
+
+/// Library folders listed in a Steam root's `libraryfolders.vdf`, plus the root itself
+pub fn steam_libraries(runner: &Runner, steam_root: &Utf8Path) -> Vec<Utf8PathBuf> {
+    let mut libraries = vec![steam_root.to_path_buf()];
+
+    let vdf = steam_root.join(LIBRARY_FOLDERS_VDF);
+    if let Ok(content) = fs::read_to_string(&vdf) {
+        for path in parse_library_folders(&content) {
+            let library = runner.host_path(&path);
+            if !libraries.contains(&library) {
+                libraries.push(library);
+            }
+        }
+    }
+
+    libraries
+}
+
+/// Extract the `"path"` values from a `libraryfolders.vdf` file
+fn parse_library_folders(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('"').skip(1).step_by(2);
+            (fields.next()? == "path").then(|| fields.next().map(|p| p.replace("\\\\", "\\")))?
+        })
+        .collect()
+}
+
+/// Find and identify game installs from every source
+fn find_installs(
+    registry: &GameRegistry,
+    runner: &Runner,
+    sources: &DiscoverySources,
+) -> Vec<GameInstall> {
+    let mut folders: Vec<(InstallSource, Utf8PathBuf)> = Vec::new();
+
+    for root in &sources.steam_roots {
+        for library in steam_libraries(runner, root) {
+            folders.extend(
+                subdirectories(&library.join("steamapps/common"))
+                    .into_iter()
+                    .map(|dir| (InstallSource::Steam, dir)),
+            );
+        }
+    }
+
+    for dir in &sources.gog_dirs {
+        folders.extend(
+            subdirectories(dir)
+                .into_iter()
+                .map(|dir| (InstallSource::Gog, dir)),
+        );
+    }
+
+    for dir in &sources.epic_manifest_dirs {
+        folders.extend(
+            epic_install_locations(dir)
+                .into_iter()
+                .map(|location| (InstallSource::Epic, runner.host_path(&location))),
+        );
+    }
+
+    let mut installs: Vec<GameInstall> = Vec::new();
+    for (source, path) in folders {
+        if installs.iter().any(|install| install.path == path) {
+            continue;
+        }
+        if let Some(game_id) = identify_install(registry, &path) {
+            tracing::debug!("Found {} install ({}): {}", game_id, source, path);
+            installs.push(GameInstall {
+                game_id,
+                source,
+                path,
+            });
+        }
+    }
+
+    installs
+}
+
+/// Install locations from Epic Games Launcher `*.item` manifests
+fn epic_install_locations(manifest_dir: &Utf8Path) -> Vec<String> {
+    let Ok(entries) = manifest_dir.read_dir_utf8() else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| entry.path().extension() == Some("item"))
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path()).ok()?;
+            let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+            manifest["InstallLocation"].as_str().map(str::to_string)
+        })
+        .collect()
+}
+
+/// Find known xEdit executables in the given folders and their direct subfolders
+fn find_xedits(registry: &GameRegistry, dirs: &[Utf8PathBuf]) -> Vec<Utf8PathBuf> {
+    let is_xedit = |path: &Utf8Path| {
+        path.file_name().is_some_and(|name| {
+            registry.is_universal_xedit(name) || registry.games().any(|g| g.owns_xedit(name))
+        })
+    };
+
+    let mut found = Vec::new();
+    for dir in dirs {
+        for folder in std::iter::once(dir.clone()).chain(subdirectories(dir)) {
+            let Ok(entries) = folder.read_dir_utf8() else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.into_path();
+                if path.is_file() && is_xedit(&path) && !found.contains(&path) {
+                    found.push(path);
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// Direct subfolders of a folder, sorted by name (empty if it cannot be read)
+fn subdirectories(dir: &Utf8Path) -> Vec<Utf8PathBuf> {
+    let Ok(entries) = dir.read_dir_utf8() else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<Utf8PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.into_path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn touch(path: &Utf8Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, b"").unwrap();
+    }
+
+    #[test]
+    fn test_parse_library_folders() {
+        let vdf = r#"
+"libraryfolders"
+{
+    "0"
+    {
+        "path"		"C:\\Program Files (x86)\\Steam"
+        "label"		""
+    }
+    "1"
+    {
+        "path"		"D:\\SteamLibrary"
+    }
+}
+"#;
+
+        assert_eq!(
+            parse_library_folders(vdf),
+            vec!["C:\\Program Files (x86)\\Steam", "D:\\SteamLibrary"]
+        );
+    }
+
+    #[test]
+    fn test_identify_install_prefers_markers() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        let registry = GameRegistry::builtin();
+
+        touch(&root.join("Fallout 4/Data/Fallout4.esm"));
+        touch(&root.join("Fallout New Vegas/Data/FalloutNV.esm"));
+        touch(&root.join("Fallout New Vegas/Data/TaleOfTwoWastelands.esm"));
+
+        assert_eq!(
+            identify_install(&registry, &root.join("Fallout 4")),
+            Some("FO4".to_string())
+        );
+        assert_eq!(
+            identify_install(&registry, &root.join("Fallout New Vegas")),
+            Some("TTW".to_string())
+        );
+        assert_eq!(identify_install(&registry, root), None);
+    }
+
+    #[test]
+    fn test_discover_ranks_steam_install_with_xedit() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        let registry = GameRegistry::builtin();
+
+        let steam = root.join("Steam");
+        let library = root.join("Library");
+        touch(&steam.join("steamapps/common/Fallout 3 goty/Data/Fallout3.esm"));
+        touch(&library.join("steamapps/common/Fallout 4/Data/Fallout4.esm"));
+        fs::write(
+            steam.join(LIBRARY_FOLDERS_VDF),
+            format!(
+                "\"libraryfolders\"\n{{\n\"1\"\n{{\n\"path\" \"{}\"\n}}\n}}\n",
+                library
+            ),
+        )
+        .unwrap();
+        touch(&root.join("Modding/FO4Edit/FO4Edit.exe"));
+
+        let sources = DiscoverySources {
+            steam_roots: vec![steam],
+            xedit_dirs: vec![root.join("Modding")],
+            ..Default::default()
+        };
+        let candidates = discover(&registry, &Runner::default(), &sources);
+
+        let ids: Vec<&str> = candidates.iter().map(|c| c.game_id.as_str()).collect();
+        assert_eq!(ids, vec!["FO4", "FO3"]);
+        assert_eq!(
+            candidates[0].xedit,
+            Some(root.join("Modding/FO4Edit/FO4Edit.exe"))
+        );
+        assert_eq!(
+            candidates[0].data_path(),
+            Some(library.join("steamapps/common/Fallout 4/Data"))
+        );
+        assert_eq!(candidates[1].xedit, None);
+    }
+}
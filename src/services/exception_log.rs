@@ -0,0 +1,303 @@
+//! Classification of xEdit's exception log.
+//!
+//! After each plugin, the output xEdit appended to its `*Exception.log` is archived in the
+//! session folder (see [`ExceptionLogWatch`](super::session::ExceptionLogWatch)). This module
+//! sorts that output into an [`XEditException`], so a plugin that cannot be loaded (a missing
+//! or misplaced master) is skipped with advice for the load order, while a damaged plugin or
+//! an xEdit crash is reported as a failure.
+//!
+//! Each category has its own [`MessageId`] in the `Errors` / `Warnings` of
+//! `AutoQAC Main.yaml`. The first line of the message body says what went wrong and the rest
+//! is advice.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::exception_log::XEditException;
+//! use autoqac::services::messages::MessageCatalog;
+//!
+//! let log = r#"Exception: "MyPlugin.esp" requires master "Dawnguard.esm" which can not be found"#;
+//! let exception = XEditException::parse(log).unwrap();
+//! assert_eq!(exception.status(), "skipped");
+//! println!("{}", exception.to_message(&MessageCatalog::builtin(), "MyPlugin.esp"));
+//! ```
+
+use super::messages::{Message, MessageCatalog, MessageId};
+use regex::Regex;
+use std::fmt;
+use std::sync::LazyLock;
+
+/// Quoted master name, e.g. `master "Dawnguard.esm"`
+static MASTER_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)master\s+"([^"]+)""#).expect("Invalid master regex"));
+
+/// Record reference, e.g. `[REFR:0001A2B3]` or `[0001A2B3]`
+static RECORD_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[((?:[A-Z0-9_]{4}:)?[0-9A-Fa-f]{8})\]").expect("Invalid record regex")
+});
+
+/// Lower-case fragments of xEdit's exception texts, per category
+const MISSING_MASTER: &[&str] = &["which can not be found", "which cannot be found"];
+const MASTERS_OUT_OF_ORDER: &[&str] = &["is loaded after", "must be loaded before", "out of order"];
+const LOAD_ORDER_ERROR: &[&str] = &["which it does not have", "can not be mapped", "load order"];
+const CORRUPTED_RECORD: &[&str] = &[
+    "error reading",
+    "unexpected end of",
+    "corrupt",
+    "invalid record",
+    "invalid size",
+];
+const ACCESS_VIOLATION: &[&str] = &["access violation"];
+
+/// What xEdit's exception log says went wrong with a plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XEditException {
+    /// A master of the plugin is not installed or not enabled
+    MissingMaster { master: Option<String> },
+
+    /// A master is loaded after the plugin that needs it
+    MastersOutOfOrder,
+
+    /// xEdit could not map the plugin's masters to the load order
+    LoadOrderError,
+
+    /// A record in the plugin cannot be read
+    CorruptedRecord { record: Option<String> },
+
+    /// xEdit itself crashed
+    AccessViolation,
+
+    /// Output that matches none of the known errors
+    Unknown(String),
+}
+
+impl XEditException {
+    /// Classify the exception log output of one run.
+    ///
+    /// Problems with the plugin and its load order take precedence over an access violation,
+    /// since xEdit often crashes because of them. Returns `None` for empty output.
+    pub fn parse(content: &str) -> Option<Self> {
+        let content = content.trim();
+        if content.is_empty() {
+            return None;
+        }
+
+        let lines: Vec<&str> = content.lines().collect();
+        let find = |fragments: &[&str]| {
+            lines.iter().copied().find(|line| {
+                let line = line.to_lowercase();
+                fragments.iter().any(|fragment| line.contains(fragment))
+            })
+        };
+
+        let exception = if let Some(line) = find(MISSING_MASTER) {
+            XEditException::MissingMaster {
+                master: capture(&MASTER_PATTERN, line),
+            }
+        } else if find(MASTERS_OUT_OF_ORDER).is_some() {
+            XEditException::MastersOutOfOrder
+        } else if find(LOAD_ORDER_ERROR).is_some() {
+            XEditException::LoadOrderError
+        } else if let Some(line) = find(CORRUPTED_RECORD) {
+            XEditException::CorruptedRecord {
+                record: capture(&RECORD_PATTERN, line),
+            }
+        } else if find(ACCESS_VIOLATION).is_some() {
+            XEditException::AccessViolation
+        } else {
+            XEditException::Unknown(content.to_string())
+        };
+
+        Some(exception)
+    }
+
+    /// Catalog message for this category
+    pub fn message_id(&self) -> MessageId {
+        match self {
+            XEditException::MissingMaster { .. } => MessageId::MissingRequirements,
+            XEditException::MastersOutOfOrder => MessageId::MastersOutOfOrder,
+            XEditException::LoadOrderError => MessageId::LoadOrderError,
+            XEditException::CorruptedRecord { .. } => MessageId::CorruptedRecord,
+            XEditException::AccessViolation => MessageId::XEditCrashed,
+            XEditException::Unknown(_) => MessageId::XEditException,
+        }
+    }
+
+    /// Plugin result status: load order problems skip the plugin, the rest fail it
+    pub fn status(&self) -> &'static str {
+        match self {
+            XEditException::MissingMaster { .. }
+            | XEditException::MastersOutOfOrder
+            | XEditException::LoadOrderError => "skipped",
+            XEditException::CorruptedRecord { .. }
+            | XEditException::AccessViolation
+            | XEditException::Unknown(_) => "failed",
+        }
+    }
+
+    /// Whether xEdit failed rather than the plugin or its load order
+    pub fn is_xedit_failure(&self) -> bool {
+        matches!(
+            self,
+            XEditException::AccessViolation | XEditException::Unknown(_)
+        )
+    }
+
+    /// User-facing message for a plugin, from the catalog
+    pub fn to_message(&self, catalog: &MessageCatalog, plugin: &str) -> Message {
+        let id = self.message_id();
+        match self {
+            XEditException::MissingMaster { master } => catalog.format(
+                id,
+                &[
+                    ("plugin", &plugin),
+                    ("master", &master.as_deref().unwrap_or("a master")),
+                ],
+            ),
+            XEditException::CorruptedRecord { record } => catalog.format(
+                id,
+                &[
+                    ("plugin", &plugin),
+                    ("record", &record.as_deref().unwrap_or("unknown record")),
+                ],
+            ),
+            XEditException::Unknown(text) => catalog.format(
+                id,
+                &[
+                    ("plugin", &plugin),
+                    ("details", &text.lines().next().unwrap_or_default()),
+                ],
+            ),
+            _ => catalog.format(id, &[("plugin", &plugin)]),
+        }
+    }
+}
+
+impl fmt::Display for XEditException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XEditException::MissingMaster {
+                master: Some(master),
+            } => {
+                write!(f, "Missing master: {}", master)
+            }
+            XEditException::MissingMaster { master: None } => write!(f, "Missing master"),
+            XEditException::MastersOutOfOrder => write!(f, "Masters out of order"),
+            XEditException::LoadOrderError => write!(f, "Load order error"),
+            XEditException::CorruptedRecord {
+                record: Some(record),
+            } => write!(f, "Corrupted record: {}", record),
+            XEditException::CorruptedRecord { record: None } => write!(f, "Corrupted record"),
+            XEditException::AccessViolation => write!(f, "xEdit access violation"),
+            XEditException::Unknown(text) => write!(f, "Unknown xEdit exception: {}", text),
+        }
+    }
+}
+
+/// First capture group of a pattern in a line
+fn capture(pattern: &Regex, line: &str) -> Option<String> {
+    pattern
+        .captures(line)
+        .and_then(|captures| captures.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_parse_categories() {
+        assert_eq!(
+            XEditException::parse(
+                r#"Exception: "MyPlugin.esp" requires master "Dawnguard.esm" which can not be found"#
+            ),
+            Some(XEditException::MissingMaster {
+                master: Some("Dawnguard.esm".to_string())
+            })
+        );
+        assert_eq!(
+            XEditException::parse(r#"Master "Update.esm" of "MyPlugin.esp" is loaded after it"#),
+            Some(XEditException::MastersOutOfOrder)
+        );
+        assert_eq!(
+            XEditException::parse(
+                r#"Load order FileID [03] can not be mapped to file FileID for file "MyPlugin.esp""#
+            ),
+            Some(XEditException::LoadOrderError)
+        );
+        assert_eq!(
+            XEditException::parse("Error reading [REFR:0001A2B3]: Unexpected end of stream"),
+            Some(XEditException::CorruptedRecord {
+                record: Some("REFR:0001A2B3".to_string())
+            })
+        );
+        assert_eq!(
+            XEditException::parse(
+                "Access violation at address 00A1B2C3 in module 'SSEEdit.exe'. Read of address 00000000"
+            ),
+            Some(XEditException::AccessViolation)
+        );
+        assert_eq!(
+            XEditException::parse("  Something new went wrong\nsecond line\n"),
+            Some(XEditException::Unknown(
+                "Something new went wrong\nsecond line".to_string()
+            ))
+        );
+        assert_eq!(XEditException::parse(" \n"), None);
+    }
+
+    #[test]
+    fn test_plugin_problems_take_precedence_over_crashes() {
+        let log = "Access violation at address 00000000\n\
+                   \"MyPlugin.esp\" requires master \"Missing.esm\" which can not be found";
+        assert!(matches!(
+            XEditException::parse(log),
+            Some(XEditException::MissingMaster { .. })
+        ));
+    }
+
+    #[test]
+    fn test_outcomes_and_messages() {
+        let catalog = MessageCatalog::builtin();
+
+        let missing = XEditException::MissingMaster {
+            master: Some("Dawnguard.esm".to_string()),
+        };
+        assert_eq!(missing.status(), "skipped");
+        assert!(!missing.is_xedit_failure());
+        assert!(
+            missing
+                .to_message(&catalog, "MyPlugin.esp")
+                .summary()
+                .contains("Dawnguard.esm")
+        );
+
+        let crash = XEditException::AccessViolation;
+        assert_eq!(crash.status(), "failed");
+        assert!(crash.is_xedit_failure());
+
+        let corrupted = XEditException::CorruptedRecord { record: None };
+        assert_eq!(corrupted.status(), "failed");
+        assert!(!corrupted.is_xedit_failure());
+
+        // Every category has its own message
+        let all = [
+            missing,
+            XEditException::MastersOutOfOrder,
+            XEditException::LoadOrderError,
+            corrupted,
+            crash,
+            XEditException::Unknown("Oops".to_string()),
+        ];
+        let ids: HashSet<MessageId> = all.iter().map(XEditException::message_id).collect();
+        assert_eq!(ids.len(), all.len());
+        assert!(
+            all[5]
+                .to_message(&catalog, "MyPlugin.esp")
+                .summary()
+                .contains("Oops")
+        );
+    }
+}
@@ -4,6 +4,14 @@
 //! based on:
 //! - xEdit executable filename (FO4Edit.exe → FO4, SSEEdit.exe → SSE, etc.)
 //! - Load order file contents (looking for master ESM files like Skyrim.esm, Fallout4.esm, etc.)
+//! - The base master's header form version, when the game Data folder is known
+//!   (Skyrim.esm is form version 43 in Skyrim LE and 44 in Skyrim SE)
+//!
+//! [`find_load_order`] locates a game's `plugins.txt` under `%LOCALAPPDATA%`, including
+//! inside a Wine or Proton prefix.
+//!
+//! All game knowledge comes from a [`GameRegistry`]. The `*_with` variants take the registry
+//! built from `AutoQAC Main.yaml`; the plain functions use the built-in definitions.
 //!
 //! # Examples
 //!
@@ -20,8 +28,11 @@
 //! let game = detect_game_from_load_order(load_order)?;
 //! ```
 
+use crate::models::GameRegistry;
+use crate::services::Runner;
+use crate::services::plugin_header::PluginHeader;
 use anyhow::{Context, Result};
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
@@ -39,7 +50,8 @@ use std::io::{BufRead, BufReader};
 ///
 /// # Returns
 ///
-/// Game type abbreviation (FO3, FNV, FO4, SSE, TTW) if detected, None otherwise
+/// Game id (Oblivion, FO3, FNV, FO4, FO4VR, SSE, SkyrimLE, SkyrimVR, Starfield, TTW) if detected,
+/// None otherwise
 ///
 /// # Examples
 ///
@@ -48,42 +60,50 @@ use std::io::{BufRead, BufReader};
 /// assert_eq!(game, Some("SSE".to_string()));
 /// ```
 pub fn detect_xedit_game(xedit_path: &str, load_order_path: Option<&Utf8Path>) -> Option<String> {
-    let filename = Utf8Path::new(xedit_path).file_stem()?.to_lowercase();
-
-    // Map of executable name patterns to game types
-    let game_map: Vec<(&str, &str)> = vec![
-        ("fo3edit", "FO3"),
-        ("fnvedit", "FNV"),
-        ("ttwedit", "TTW"),
-        ("fo4edit", "FO4"),
-        ("fo4vredit", "FO4"),
-        ("sseedit", "SSE"),
-        ("tes5edit", "SSE"),
-        ("skyrimvredit", "SSE"),
-    ];
+    detect_xedit_game_with(GameRegistry::builtin_ref(), xedit_path, load_order_path)
+}
 
+/// Detects game type from the xEdit executable filename using the given registry.
+///
+/// See [`detect_xedit_game`] for details.
+pub fn detect_xedit_game_with(
+    registry: &GameRegistry,
+    xedit_path: &str,
+    load_order_path: Option<&Utf8Path>,
+) -> Option<String> {
     // Try to detect from xEdit executable name
-    for (pattern, game) in game_map {
-        if filename.contains(pattern) {
-            tracing::info!("Detected game type from xEdit: {}", game);
-            return Some(game.to_string());
+    if let Some(game) = registry.find_by_xedit(xedit_path) {
+        tracing::info!("Detected game type from xEdit: {}", game.id);
+
+        // A total conversion runs on its base game's xEdit; its marker in the load order
+        // narrows the detection down (FNVEdit + TaleOfTwoWastelands.esm is TTW)
+        if let Some(lo_path) = load_order_path
+            && lo_path.exists()
+            && let Ok(Some(conversion)) = detect_game_from_load_order_with(registry, lo_path)
+            && conversion != game.id
+            && registry.is_based_on(&conversion, &game.id)
+        {
+            tracing::info!("Detected total conversion from load order: {}", conversion);
+            return Some(conversion);
         }
+
+        return Some(game.id.clone());
     }
 
     // If xEdit detection failed and load order path is provided, try load order detection
-    if let Some(lo_path) = load_order_path {
-        if lo_path.exists() {
-            match detect_game_from_load_order(lo_path) {
-                Ok(Some(game)) => {
-                    tracing::info!("Detected game type from load order: {}", game);
-                    return Some(game);
-                }
-                Ok(None) => {
-                    tracing::debug!("Could not detect game type from load order");
-                }
-                Err(e) => {
-                    tracing::warn!("Error detecting game type from load order: {}", e);
-                }
+    if let Some(lo_path) = load_order_path
+        && lo_path.exists()
+    {
+        match detect_game_from_load_order_with(registry, lo_path) {
+            Ok(Some(game)) => {
+                tracing::info!("Detected game type from load order: {}", game);
+                return Some(game);
+            }
+            Ok(None) => {
+                tracing::debug!("Could not detect game type from load order");
+            }
+            Err(e) => {
+                tracing::warn!("Error detecting game type from load order: {}", e);
             }
         }
     }
@@ -92,10 +112,124 @@ pub fn detect_xedit_game(xedit_path: &str, load_order_path: Option<&Utf8Path>) -
     None
 }
 
+/// Detects game type from the xEdit executable and load order, then checks the base master's
+/// header in the game Data folder.
+///
+/// Games that share a base master (Skyrim LE and Skyrim SE both use Skyrim.esm) are told
+/// apart by the master's form version, so the Data folder wins over the executable name.
+///
+/// # Arguments
+///
+/// * `registry` - Game definitions to detect against
+/// * `xedit_path` - Path to the xEdit executable
+/// * `load_order_path` - Optional path to load order file for fallback detection
+/// * `data_folder` - Optional game Data folder containing the base master
+pub fn detect_game(
+    registry: &GameRegistry,
+    xedit_path: &str,
+    load_order_path: Option<&Utf8Path>,
+    data_folder: Option<&Utf8Path>,
+) -> Option<String> {
+    let game = detect_xedit_game_with(registry, xedit_path, load_order_path)?;
+
+    match data_folder {
+        Some(data_folder) => Some(refine_game_by_form_version(registry, &game, data_folder)),
+        None => Some(game),
+    }
+}
+
+/// Picks the game matching the form version of the base master in the Data folder.
+///
+/// Returns `game_id` unchanged when the game has no expected form version, the master
+/// cannot be read, or its form version already matches.
+pub fn refine_game_by_form_version(
+    registry: &GameRegistry,
+    game_id: &str,
+    data_folder: &Utf8Path,
+) -> String {
+    let Some(game) = registry.get(game_id) else {
+        return game_id.to_string();
+    };
+    if game.form_version.is_none() {
+        return game.id.clone();
+    }
+
+    let master_path = data_folder.join(&game.base_master);
+    let form_version = match PluginHeader::read(&master_path) {
+        Ok(header) => header.form_version,
+        Err(e) => {
+            tracing::debug!(
+                "Could not read {} to check form version: {}",
+                master_path,
+                e
+            );
+            return game.id.clone();
+        }
+    };
+
+    if form_version.is_none() || form_version == game.form_version {
+        return game.id.clone();
+    }
+
+    // Marker-identified games (VR) are never picked by form version alone
+    match registry.games().find(|other| {
+        other.base_master == game.base_master
+            && other.form_version == form_version
+            && other.markers.is_empty()
+    }) {
+        Some(other) => {
+            tracing::info!(
+                "{} has form version {}, detected {} instead of {}",
+                game.base_master,
+                form_version.unwrap_or_default(),
+                other.id,
+                game.id
+            );
+            other.id.clone()
+        }
+        None => game.id.clone(),
+    }
+}
+
+/// Locates a game's `plugins.txt` in the runner's `%LOCALAPPDATA%` folder.
+///
+/// With a game id only that game's folder is checked; without one the first game (in
+/// registry order) that has a `plugins.txt` wins.
+///
+/// # Arguments
+///
+/// * `registry` - Game definitions providing the AppData folder names
+/// * `runner` - Runner whose prefix (or native `%LOCALAPPDATA%`) is searched
+/// * `game_id` - Optional game to look for
+pub fn find_load_order(
+    registry: &GameRegistry,
+    runner: &Runner,
+    game_id: Option<&str>,
+) -> Option<Utf8PathBuf> {
+    let local_appdata = runner.local_appdata()?;
+
+    let candidates: Vec<_> = match game_id {
+        Some(id) => registry.get(id).into_iter().collect(),
+        None => registry.games().collect(),
+    };
+
+    let path = candidates
+        .into_iter()
+        .filter(|game| !game.appdata_folder.is_empty())
+        .map(|game| local_appdata.join(&game.appdata_folder).join("plugins.txt"))
+        .find(|path| path.is_file());
+
+    if let Some(ref path) = path {
+        tracing::info!("Found load order file: {}", path);
+    }
+    path
+}
+
 /// Detects game type by reading the load order file and looking for specific master ESM files.
 ///
 /// This function reads the load order file line by line, looking for game-specific
-/// master files (Skyrim.esm, Fallout4.esm, etc.) in the first few entries.
+/// master files (Skyrim.esm, Fallout4.esm, etc.). VR marker plugins (SkyrimVR.esm,
+/// Fallout4_VR.esm) anywhere in the file take precedence over the base master.
 ///
 /// # Arguments
 ///
@@ -103,7 +237,7 @@ pub fn detect_xedit_game(xedit_path: &str, load_order_path: Option<&Utf8Path>) -
 ///
 /// # Returns
 ///
-/// Game type abbreviation (FO3, FNV, FO4, SSE) if detected, None otherwise
+/// Game id (FO3, FNV, FO4, FO4VR, SSE, SkyrimVR) if detected, None otherwise
 ///
 /// # Errors
 ///
@@ -117,10 +251,21 @@ pub fn detect_xedit_game(xedit_path: &str, load_order_path: Option<&Utf8Path>) -
 /// assert_eq!(game, Some("SSE".to_string()));
 /// ```
 pub fn detect_game_from_load_order(load_order_path: &Utf8Path) -> Result<Option<String>> {
+    detect_game_from_load_order_with(GameRegistry::builtin_ref(), load_order_path)
+}
+
+/// Detects game type from a load order file using the given registry.
+///
+/// See [`detect_game_from_load_order`] for details.
+pub fn detect_game_from_load_order_with(
+    registry: &GameRegistry,
+    load_order_path: &Utf8Path,
+) -> Result<Option<String>> {
     let file = File::open(load_order_path)
         .with_context(|| format!("Failed to open load order file: {}", load_order_path))?;
 
     let reader = BufReader::new(file);
+    let mut base_game = None;
 
     for line_result in reader.lines() {
         let line = line_result.context("Failed to read line from load order file")?;
@@ -132,37 +277,28 @@ pub fn detect_game_from_load_order(load_order_path: &Utf8Path) -> Result<Option<
         }
 
         // Remove prefix characters (*, +, -)
-        let plugin_name = if let Some(first_char) = line.chars().next() {
-            if first_char == '*' || first_char == '+' || first_char == '-' {
-                line[1..].trim()
-            } else {
-                line
-            }
-        } else {
-            line
-        };
+        let plugin_name = line.trim_start_matches(['*', '+', '-']).trim();
 
-        // Check for game-specific master files
-        if plugin_name.contains("Skyrim.esm") {
-            return Ok(Some("SSE".to_string()));
-        }
-        if plugin_name.contains("Fallout3.esm") {
-            return Ok(Some("FO3".to_string()));
-        }
-        if plugin_name.contains("FalloutNV.esm") {
-            return Ok(Some("FNV".to_string()));
+        // Marker plugins (SkyrimVR.esm, TaleOfTwoWastelands.esm) identify the game outright
+        if let Some(game) = registry.find_by_marker(plugin_name) {
+            return Ok(Some(game.id.clone()));
         }
-        if plugin_name.contains("Fallout4.esm") {
-            return Ok(Some("FO4".to_string()));
+
+        // Otherwise the first game-specific master decides, unless a marker follows
+        if base_game.is_none() {
+            base_game = registry
+                .find_by_master(plugin_name)
+                .map(|game| game.id.clone());
         }
     }
 
-    Ok(None)
+    Ok(base_game)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::plugin_header::{MASTER_FLAG, TestPlugin};
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -262,6 +398,62 @@ mod tests {
         assert_eq!(result, Some("FO3".to_string()));
     }
 
+    #[test]
+    fn test_detect_vr_from_load_order_marker() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "*Skyrim.esm").unwrap();
+        writeln!(temp_file, "*Update.esm").unwrap();
+        writeln!(temp_file, "*SkyrimVR.esm").unwrap();
+
+        let temp_path = Utf8Path::from_path(temp_file.path()).unwrap();
+        let result = detect_game_from_load_order(temp_path).unwrap();
+        assert_eq!(result, Some("SkyrimVR".to_string()));
+    }
+
+    #[test]
+    fn test_detect_total_conversion_with_base_xedit() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "*FalloutNV.esm").unwrap();
+        writeln!(temp_file, "*TaleOfTwoWastelands.esm").unwrap();
+
+        let temp_path = Utf8Path::from_path(temp_file.path()).unwrap();
+        assert_eq!(
+            detect_xedit_game("FNVEdit.exe", Some(temp_path)),
+            Some("TTW".to_string())
+        );
+        // An unrelated xEdit still takes precedence over the load order
+        assert_eq!(
+            detect_xedit_game("FO4Edit.exe", Some(temp_path)),
+            Some("FO4".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_load_order_in_proton_prefix() {
+        let compat_data = tempfile::TempDir::new().unwrap();
+        let compat_path = Utf8Path::from_path(compat_data.path()).unwrap();
+        let runner = Runner {
+            kind: crate::models::RunnerKind::Proton,
+            compat_data: Some(compat_path.to_path_buf()),
+            ..Default::default()
+        };
+        let registry = GameRegistry::builtin();
+
+        let folder = compat_path.join("pfx/drive_c/users/steamuser/AppData/Local/Fallout4");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("plugins.txt"), "*Fallout4.esm\n").unwrap();
+
+        assert_eq!(
+            find_load_order(&registry, &runner, Some("FO4")),
+            Some(folder.join("plugins.txt"))
+        );
+        assert_eq!(
+            find_load_order(&registry, &runner, None),
+            Some(folder.join("plugins.txt"))
+        );
+        assert_eq!(find_load_order(&registry, &runner, Some("SSE")), None);
+    }
+
     #[test]
     fn test_load_order_with_comments() {
         let mut temp_file = NamedTempFile::new().unwrap();
@@ -282,4 +474,68 @@ mod tests {
         let result = detect_xedit_game("xEdit.exe", Some(temp_path));
         assert_eq!(result, Some("FO4".to_string()));
     }
+
+    #[test]
+    fn test_detect_with_custom_registry() {
+        use crate::models::GameDefinition;
+
+        let mut registry = GameRegistry::builtin();
+        registry.insert(GameDefinition {
+            id: "EXAMPLE".to_string(),
+            name: "Example Game".to_string(),
+            xedit_executables: vec!["ExampleEdit.exe".to_string()],
+            mode_flag: "-example".to_string(),
+            base_master: "Example.esm".to_string(),
+            ignore_key: "PACT_Ignore_EXAMPLE".to_string(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            detect_xedit_game_with(&registry, "ExampleEdit.exe", None),
+            Some("EXAMPLE".to_string())
+        );
+        assert_eq!(detect_xedit_game("ExampleEdit.exe", None), None);
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "*Example.esm").unwrap();
+
+        let temp_path = Utf8Path::from_path(temp_file.path()).unwrap();
+        let result = detect_game_from_load_order_with(&registry, temp_path).unwrap();
+        assert_eq!(result, Some("EXAMPLE".to_string()));
+    }
+
+    #[test]
+    fn test_refine_skyrim_by_form_version() {
+        use tempfile::TempDir;
+
+        let registry = GameRegistry::builtin();
+        let temp_dir = TempDir::new().unwrap();
+        let data_folder = Utf8Path::from_path(temp_dir.path()).unwrap();
+
+        // No Skyrim.esm to read: keep the detected game
+        assert_eq!(
+            refine_game_by_form_version(&registry, "SkyrimLE", data_folder),
+            "SkyrimLE"
+        );
+
+        // Skyrim.esm with the SE form version (44)
+        let master = TestPlugin::new()
+            .flags(MASTER_FLAG)
+            .form_version(44)
+            .build();
+        std::fs::write(data_folder.join("Skyrim.esm"), master).unwrap();
+
+        assert_eq!(
+            refine_game_by_form_version(&registry, "SkyrimLE", data_folder),
+            "SSE"
+        );
+        assert_eq!(
+            detect_game(&registry, "TES5Edit.exe", None, Some(data_folder)),
+            Some("SSE".to_string())
+        );
+        assert_eq!(
+            refine_game_by_form_version(&registry, "SkyrimVR", data_folder),
+            "SkyrimVR"
+        );
+    }
 }
@@ -0,0 +1,178 @@
+//! Hang detection for xEdit runs.
+//!
+//! A hung xEdit sits at the cleaning timeout doing nothing, and with adaptive timeouts for
+//! large masters that can be a long wait. While xEdit runs, its CPU usage is sampled every
+//! [`SAMPLE_INTERVAL`]; when it stays below `CPU Threshold` percent for `Hang Window`
+//! seconds, the run is stopped and the plugin is reported as hung.
+//!
+//! The usage is summed over the launched process and all of its descendants ([`ProcessTree`]),
+//! since xEdit is never the direct child: native runs go through the shell, Wine and Proton
+//! start it through their own launchers, and MO2 starts it itself.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::hang::{HangDetector, ProcessTree};
+//! use std::time::{Duration, Instant};
+//!
+//! let mut detector = HangDetector::new(5, Duration::from_secs(120));
+//! let mut tree = ProcessTree::new(child.id().unwrap());
+//! if detector.observe(tree.cpu_usage(), Instant::now()) {
+//!     tree.kill();
+//! }
+//! ```
+
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// How often xEdit's CPU usage is sampled
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Tracks how long CPU usage has stayed below the threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HangDetector {
+    /// CPU usage in percent of one core below which xEdit counts as idle; 0 turns detection off
+    pub threshold: u32,
+
+    /// How long xEdit may stay idle before it counts as hung
+    pub window: Duration,
+
+    /// Start of the current idle stretch
+    idle_since: Option<Instant>,
+}
+
+impl HangDetector {
+    pub fn new(threshold: u32, window: Duration) -> Self {
+        Self {
+            threshold,
+            window,
+            idle_since: None,
+        }
+    }
+
+    /// Whether hangs are detected at all
+    pub fn is_enabled(&self) -> bool {
+        self.threshold > 0 && !self.window.is_zero()
+    }
+
+    /// Record a sample; returns true once usage has stayed below the threshold for the window
+    pub fn observe(&mut self, cpu_usage: f32, now: Instant) -> bool {
+        if !self.is_enabled() || cpu_usage >= self.threshold as f32 {
+            self.idle_since = None;
+            return false;
+        }
+
+        let idle_since = *self.idle_since.get_or_insert(now);
+        now.duration_since(idle_since) >= self.window
+    }
+}
+
+/// A launched process and everything it started
+pub struct ProcessTree {
+    system: System,
+    root: Pid,
+}
+
+impl ProcessTree {
+    /// Start watching the process with this id; the first CPU sample is taken right away
+    pub fn new(pid: u32) -> Self {
+        let mut tree = Self {
+            system: System::new(),
+            root: Pid::from_u32(pid),
+        };
+        tree.refresh();
+        tree
+    }
+
+    /// CPU usage of the whole tree since the previous sample, in percent of one core
+    pub fn cpu_usage(&mut self) -> f32 {
+        self.refresh();
+        self.members()
+            .iter()
+            .filter_map(|pid| self.system.process(*pid))
+            .map(|process| process.cpu_usage())
+            .sum()
+    }
+
+    /// Kill every process in the tree, children first
+    pub fn kill(&mut self) {
+        self.refresh();
+        for pid in self.members().iter().rev() {
+            if let Some(process) = self.system.process(*pid)
+                && !process.kill()
+            {
+                tracing::warn!("Failed to kill process {}", pid);
+            }
+        }
+    }
+
+    fn refresh(&mut self) {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu(),
+        );
+    }
+
+    /// The root and its descendants, parents before children; threads are left out
+    fn members(&self) -> Vec<Pid> {
+        let processes = self.system.processes();
+        let mut members = vec![self.root];
+        let mut index = 0;
+
+        while let Some(parent) = members.get(index).copied() {
+            members.extend(processes.iter().filter_map(|(pid, process)| {
+                (process.parent() == Some(parent) && process.thread_kind().is_none())
+                    .then_some(*pid)
+            }));
+            index += 1;
+        }
+
+        members
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hang_after_idle_window() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut detector = HangDetector::new(5, Duration::from_secs(60));
+
+        assert!(!detector.observe(1.0, at(0)));
+        assert!(!detector.observe(0.5, at(30)));
+        assert!(detector.observe(2.0, at(60)));
+
+        // Any busy sample starts the window over
+        let mut detector = HangDetector::new(5, Duration::from_secs(60));
+        assert!(!detector.observe(0.0, at(0)));
+        assert!(!detector.observe(40.0, at(50)));
+        assert!(!detector.observe(0.0, at(70)));
+        assert!(!detector.observe(0.0, at(120)));
+        assert!(detector.observe(0.0, at(130)));
+    }
+
+    #[test]
+    fn test_zero_threshold_disables_detection() {
+        let start = Instant::now();
+        let mut detector = HangDetector::new(0, Duration::from_secs(10));
+
+        assert!(!detector.is_enabled());
+        assert!(!detector.observe(0.0, start));
+        assert!(!detector.observe(0.0, start + Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn test_process_tree_includes_root() {
+        let mut tree = ProcessTree::new(std::process::id());
+
+        assert_eq!(
+            tree.members().first(),
+            Some(&Pid::from_u32(std::process::id()))
+        );
+        assert!(tree.cpu_usage() >= 0.0);
+    }
+}
@@ -0,0 +1,250 @@
+//! Guards against cleaning plugins that something else has open.
+//!
+//! [`MAX_CONCURRENT_XEDIT_PROCESSES`](crate::models::MAX_CONCURRENT_XEDIT_PROCESSES) only
+//! holds within one AutoQAC. Two more things can have the plugins open while they are cleaned:
+//!
+//! - Another AutoQAC cleaning from the same `AutoQAC Data` folder. A run holds an
+//!   [`InstanceLock`], a lock file with its process id, and a second run refuses to start
+//!   while that process is alive. A lock left behind by a crash names a process that is gone
+//!   and is taken over.
+//! - The game, its editor (Creation Kit, GECK, Construction Set) or an xEdit the user opened.
+//!   [`running_programs`] finds them by executable name.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::instance::{InstanceLock, blocking_executables, running_programs};
+//!
+//! let _lock = InstanceLock::acquire(config_manager.config_dir())?;
+//! let running = running_programs(&blocking_executables(&registry, Some("FO4")));
+//! if !running.is_empty() {
+//!     return Err(CleaningError::ProgramsRunning(running).into());
+//! }
+//! ```
+
+use crate::models::GameRegistry;
+use crate::services::cleaning::CleaningError;
+use anyhow::{Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// Name of the lock file in the configuration directory
+pub const LOCK_FILE: &str = "AutoQAC.lock";
+
+/// How often a paused run checks whether the programs using the plugins were closed
+pub const PROGRAM_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Lock held by a cleaning run, removed when dropped
+#[derive(Debug)]
+pub struct InstanceLock {
+    path: Utf8PathBuf,
+}
+
+impl InstanceLock {
+    /// Take the lock in a configuration directory.
+    ///
+    /// # Errors
+    ///
+    /// [`CleaningError::AlreadyRunning`] if another AutoQAC holds the lock, or an I/O error
+    /// if the lock file cannot be written
+    pub fn acquire(config_dir: &Utf8Path) -> Result<Self> {
+        let path = config_dir.join(LOCK_FILE);
+
+        // Two tries: the second after removing a stale lock
+        for _ in 0..2 {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    write!(file, "{}", std::process::id())
+                        .with_context(|| format!("Failed to write lock file: {}", path))?;
+                    tracing::debug!("Acquired lock {}", path);
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if let Some(pid) = Self::holder(config_dir) {
+                        return Err(CleaningError::AlreadyRunning(pid).into());
+                    }
+                    tracing::info!("Removing stale lock file {}", path);
+                    fs::remove_file(&path)
+                        .with_context(|| format!("Failed to remove stale lock file: {}", path))?;
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to create lock file: {}", path));
+                }
+            }
+        }
+
+        anyhow::bail!("Lock file {} keeps coming back", path)
+    }
+
+    /// Process id of the AutoQAC holding the lock, if the lock is held by one that is running
+    pub fn holder(config_dir: &Utf8Path) -> Option<u32> {
+        let pid = fs::read_to_string(config_dir.join(LOCK_FILE))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        is_autoqac(pid).then_some(pid)
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            tracing::warn!("Failed to remove lock file {}: {}", self.path, e);
+        }
+    }
+}
+
+/// Check whether a process is running and is an AutoQAC, so reused process ids don't count
+fn is_autoqac(pid: u32) -> bool {
+    let own = Pid::from_u32(std::process::id());
+    let other = Pid::from_u32(pid);
+
+    let mut system = System::new();
+    // sysinfo drops a process listed twice
+    let pids = if own == other {
+        vec![own]
+    } else {
+        vec![own, other]
+    };
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&pids),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+
+    match (system.process(own), system.process(other)) {
+        (Some(own), Some(other)) => own.name() == other.name(),
+        (None, Some(_)) => true,
+        (_, None) => false,
+    }
+}
+
+/// Executables that must not run while a game's plugins are cleaned: the game and its editor
+/// (every game's when the game is not known) and every xEdit
+pub fn blocking_executables<'a>(registry: &'a GameRegistry, game_id: Option<&str>) -> Vec<&'a str> {
+    let mut executables = registry.game_executables(game_id);
+    for exe in registry.xedit_executables() {
+        if !executables.iter().any(|e| e.eq_ignore_ascii_case(exe)) {
+            executables.push(exe);
+        }
+    }
+    executables
+}
+
+/// Names of the running processes among the given executables, sorted and without duplicates
+///
+/// Names are compared without case and without `.exe`, which Wine and Proton processes
+/// may leave out.
+pub fn running_programs<S: AsRef<str>>(executables: &[S]) -> Vec<String> {
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+
+    let mut running: Vec<String> = system
+        .processes()
+        .values()
+        .filter(|process| process.thread_kind().is_none())
+        .filter_map(|process| {
+            let name = process.name().to_string_lossy();
+            executables
+                .iter()
+                .map(AsRef::as_ref)
+                .find(|exe| exe_stem(exe).eq_ignore_ascii_case(exe_stem(&name)))
+                .map(str::to_string)
+        })
+        .collect();
+    running.sort_unstable_by_key(|name| name.to_lowercase());
+    running.dedup();
+    running
+}
+
+fn exe_stem(name: &str) -> &str {
+    name.len()
+        .checked_sub(4)
+        .filter(|&at| name.is_char_boundary(at) && name[at..].eq_ignore_ascii_case(".exe"))
+        .map_or(name, |at| &name[..at])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn temp_dir() -> (TempDir, Utf8PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_lock_blocks_second_run() {
+        let (_dir, path) = temp_dir();
+
+        let lock = InstanceLock::acquire(&path).unwrap();
+        assert_eq!(InstanceLock::holder(&path), Some(std::process::id()));
+
+        let error = InstanceLock::acquire(&path).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<CleaningError>(),
+            Some(CleaningError::AlreadyRunning(pid)) if *pid == std::process::id()
+        ));
+
+        drop(lock);
+        assert!(!path.join(LOCK_FILE).exists());
+        assert!(InstanceLock::acquire(&path).is_ok());
+    }
+
+    #[test]
+    fn test_stale_lock_is_taken_over() {
+        let (_dir, path) = temp_dir();
+        fs::write(path.join(LOCK_FILE), "not a process id").unwrap();
+
+        assert_eq!(InstanceLock::holder(&path), None);
+        let _lock = InstanceLock::acquire(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(path.join(LOCK_FILE)).unwrap(),
+            std::process::id().to_string()
+        );
+    }
+
+    #[test]
+    fn test_blocking_executables() {
+        let registry = GameRegistry::builtin();
+        let executables = blocking_executables(&registry, Some("SSE"));
+
+        assert!(executables.contains(&"SkyrimSE.exe"));
+        assert!(executables.contains(&"CreationKit.exe"));
+        assert!(executables.contains(&"FO4Edit.exe"));
+        assert!(executables.contains(&"xEdit.exe"));
+        assert!(!executables.contains(&"Fallout4.exe"));
+    }
+
+    #[test]
+    fn test_running_programs_finds_own_process() {
+        let pid = Pid::from_u32(std::process::id());
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+        let own = system.process(pid).unwrap().name().to_string_lossy();
+
+        assert_eq!(running_programs(&[&own]), vec![own.to_string()]);
+        assert!(running_programs(&["NoSuchGame.exe"]).is_empty());
+    }
+
+    #[test]
+    fn test_exe_stem() {
+        assert_eq!(exe_stem("Fallout4.EXE"), "Fallout4");
+        assert_eq!(exe_stem("xedit"), "xedit");
+        assert_eq!(exe_stem(".exe"), "");
+    }
+}
@@ -0,0 +1,202 @@
+//! LOOT masterlist lookups for plugins that are already known to be clean.
+//!
+//! LOOT's masterlist records, for many popular plugins, the CRC32 of versions that were
+//! checked with xEdit and found clean:
+//!
+//! ```yaml
+//! plugins:
+//!   - name: 'Unofficial Skyrim Special Edition Patch.esp'
+//!     clean:
+//!       - crc: 0x1A2B3C4D
+//!         util: 'SSEEdit v4.0.4'
+//! ```
+//!
+//! A plugin whose CRC32 matches one of these entries does not need to be cleaned. LOOT keeps
+//! its masterlists in `%LOCALAPPDATA%/LOOT/games/<Game>/masterlist.yaml`, where the game
+//! folder matches the game's AppData folder; [`LootMasterlist::find`] looks there (inside the
+//! Wine/Proton prefix when xEdit runs through one).
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::loot::{LootMasterlist, plugin_crc};
+//!
+//! let path = LootMasterlist::find(&registry, &runner, "SSE").unwrap();
+//! let masterlist = LootMasterlist::load(&path)?;
+//! let crc = plugin_crc(&data_path.join("MyPlugin.esp"))?;
+//! if let Some(clean) = masterlist.clean_info("MyPlugin.esp", crc) {
+//!     println!("Already clean ({})", clean.util);
+//! }
+//! ```
+
+use crate::models::GameRegistry;
+use crate::services::Runner;
+use anyhow::{Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+
+/// LOOT's folder under `%LOCALAPPDATA%`
+const LOOT_GAMES_DIR: &str = "LOOT/games";
+
+/// Masterlist file in each game folder
+const MASTERLIST_FILE: &str = "masterlist.yaml";
+
+/// A version of a plugin that LOOT lists as clean
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CleanInfo {
+    /// CRC32 of the clean plugin file
+    pub crc: u32,
+
+    /// Tool that checked it (e.g. `SSEEdit v4.0.4`)
+    #[serde(default)]
+    pub util: String,
+}
+
+/// Clean plugin versions from a LOOT masterlist
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LootMasterlist {
+    /// Clean versions keyed by lower-case plugin name
+    clean: HashMap<String, Vec<CleanInfo>>,
+}
+
+#[derive(Deserialize)]
+struct Masterlist {
+    #[serde(default)]
+    plugins: Vec<PluginEntry>,
+}
+
+#[derive(Deserialize)]
+struct PluginEntry {
+    name: String,
+    #[serde(default)]
+    clean: Vec<CleanInfo>,
+}
+
+impl LootMasterlist {
+    /// Load a masterlist file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or is not a valid masterlist
+    pub fn load(path: &Utf8Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read LOOT masterlist: {}", path))?;
+        Self::parse(&content).with_context(|| format!("Invalid LOOT masterlist: {}", path))
+    }
+
+    /// Parse the YAML of a masterlist.
+    pub fn parse(yaml: &str) -> Result<Self> {
+        let masterlist: Masterlist = serde_yaml_ng::from_str(yaml)?;
+
+        let mut clean: HashMap<String, Vec<CleanInfo>> = HashMap::new();
+        for plugin in masterlist.plugins {
+            if !plugin.clean.is_empty() {
+                clean
+                    .entry(plugin.name.to_lowercase())
+                    .or_default()
+                    .extend(plugin.clean);
+            }
+        }
+
+        Ok(Self { clean })
+    }
+
+    /// Find the masterlist LOOT keeps for a game or the games it is layered on
+    pub fn find(registry: &GameRegistry, runner: &Runner, game_id: &str) -> Option<Utf8PathBuf> {
+        let games_dir = runner.local_appdata()?.join(LOOT_GAMES_DIR);
+
+        registry
+            .lineage(game_id)
+            .into_iter()
+            .filter(|game| !game.appdata_folder.is_empty())
+            .map(|game| games_dir.join(&game.appdata_folder).join(MASTERLIST_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// Whether the masterlist has any clean versions of a plugin (case-insensitive)
+    pub fn lists(&self, plugin: &str) -> bool {
+        self.clean.contains_key(&plugin.to_lowercase())
+    }
+
+    /// The clean version of a plugin (case-insensitive) with this CRC32, if LOOT lists one
+    pub fn clean_info(&self, plugin: &str, crc: u32) -> Option<&CleanInfo> {
+        self.clean
+            .get(&plugin.to_lowercase())?
+            .iter()
+            .find(|info| info.crc == crc)
+    }
+}
+
+/// CRC32 of a plugin file, as LOOT and xEdit report it
+///
+/// # Errors
+/// Returns an error if the file cannot be read
+pub fn plugin_crc(path: &Utf8Path) -> Result<u32> {
+    let mut file = File::open(path).with_context(|| format!("Failed to open plugin: {}", path))?;
+    let mut hasher = crc32fast::Hasher::new();
+    let mut buf = vec![0; 64 * 1024];
+
+    loop {
+        let read = file
+            .read(&mut buf)
+            .with_context(|| format!("Failed to read plugin: {}", path))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+
+    Ok(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MASTERLIST: &str = r#"
+prelude:
+  - &reqManualFix
+    type: warn
+    content: 'Needs a manual fix.'
+plugins:
+  - name: 'Unofficial Skyrim Special Edition Patch.esp'
+    clean:
+      - crc: 0x1A2B3C4D
+        util: 'SSEEdit v4.0.4'
+      - crc: 0x00C0FFEE
+        util: 'SSEEdit v4.1.5'
+  - name: 'Dirty.esp'
+    dirty:
+      - crc: 0x12345678
+        util: 'SSEEdit v4.0.4'
+        itm: 3
+    msg: [ *reqManualFix ]
+"#;
+
+    #[test]
+    fn test_parse_clean_entries() {
+        let masterlist = LootMasterlist::parse(MASTERLIST).unwrap();
+
+        let plugin = "unofficial skyrim special edition patch.ESP";
+        assert!(masterlist.lists(plugin));
+        assert_eq!(
+            masterlist.clean_info(plugin, 0x00C0_FFEE).unwrap().util,
+            "SSEEdit v4.1.5"
+        );
+        assert!(masterlist.clean_info(plugin, 0x1234_5678).is_none());
+        assert!(!masterlist.lists("Dirty.esp"));
+    }
+
+    #[test]
+    fn test_plugin_crc() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = Utf8PathBuf::try_from(temp.path().join("MyPlugin.esp")).unwrap();
+        fs::write(&path, b"123456789").unwrap();
+
+        // CRC-32 check value
+        assert_eq!(plugin_crc(&path).unwrap(), 0xCBF4_3926);
+        assert!(plugin_crc(&path.with_file_name("Missing.esp")).is_err());
+    }
+}
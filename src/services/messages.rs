@@ -0,0 +1,508 @@
+//! User-facing message catalog backed by the `Errors` and `Warnings` sections of
+//! `AutoQAC Main.yaml`.
+//!
+//! Every message shown to the user has a [`MessageId`] whose key is its YAML id. The catalog
+//! looks the text up in the main config and falls back to the built-in English text, so
+//! modders can reword or translate messages without rebuilding AutoQAC.
+//!
+//! Messages follow the PACT layout: the first line is the title (`❌ ERROR : TITLE`), the rest
+//! explains it. `{name}` placeholders are filled in by [`MessageCatalog::format`].
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::messages::{MessageCatalog, MessageId};
+//!
+//! let catalog = MessageCatalog::new(&main_config);
+//! let message = catalog.format(MessageId::PluginNotFound, &[("plugin", &"MyPlugin.esp")]);
+//! println!("{}: {}", message.title(), message.body());
+//! ```
+
+use crate::models::{MainConfig, PactData};
+use std::fmt;
+
+/// Which section of the main config a message lives in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// `Errors`: the operation cannot go ahead
+    Error,
+    /// `Warnings`: the operation can go ahead, but probably not as intended
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Typed ids for every message in the catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageId {
+    // Errors
+    InvalidXEditFile,
+    InvalidLoadOrderFile,
+    ConfigIncomplete,
+    XEditNotConfigured,
+    LoadOrderNotConfigured,
+    DataFolderNotConfigured,
+    GameTypeNotConfigured,
+    XEditUpdateRequired,
+    InvalidCommand,
+    PluginNotFound,
+    PluginNotInstalled,
+    CleaningTimeout,
+    XEditHung,
+    ProcessFailed,
+    LogFileNotFound,
+    LogParseFailed,
+    CleaningFailed,
+    CorruptedRecord,
+    XEditCrashed,
+    XEditException,
+    AlreadyRunning,
+    ProgramsRunning,
+
+    // Warnings
+    InvalidIniSetup,
+    OutdatedPact,
+    PactUpdateFailed,
+    MissingRequirements,
+    MastersOutOfOrder,
+    LoadOrderError,
+    OldXEditVersion,
+    VortexHardLinks,
+    NothingDetected,
+}
+
+impl MessageId {
+    /// Every message id, errors first
+    pub const ALL: [MessageId; 31] = [
+        MessageId::InvalidXEditFile,
+        MessageId::InvalidLoadOrderFile,
+        MessageId::ConfigIncomplete,
+        MessageId::XEditNotConfigured,
+        MessageId::LoadOrderNotConfigured,
+        MessageId::DataFolderNotConfigured,
+        MessageId::GameTypeNotConfigured,
+        MessageId::XEditUpdateRequired,
+        MessageId::InvalidCommand,
+        MessageId::PluginNotFound,
+        MessageId::PluginNotInstalled,
+        MessageId::CleaningTimeout,
+        MessageId::XEditHung,
+        MessageId::ProcessFailed,
+        MessageId::LogFileNotFound,
+        MessageId::LogParseFailed,
+        MessageId::CleaningFailed,
+        MessageId::CorruptedRecord,
+        MessageId::XEditCrashed,
+        MessageId::XEditException,
+        MessageId::AlreadyRunning,
+        MessageId::ProgramsRunning,
+        MessageId::InvalidIniSetup,
+        MessageId::OutdatedPact,
+        MessageId::PactUpdateFailed,
+        MessageId::MissingRequirements,
+        MessageId::MastersOutOfOrder,
+        MessageId::LoadOrderError,
+        MessageId::OldXEditVersion,
+        MessageId::VortexHardLinks,
+        MessageId::NothingDetected,
+    ];
+
+    /// Key of the message in `Errors` / `Warnings`.
+    ///
+    /// `Ivalid_XEDIT_File` keeps PACT's spelling so existing config files still match.
+    pub const fn key(self) -> &'static str {
+        match self {
+            MessageId::InvalidXEditFile => "Ivalid_XEDIT_File",
+            MessageId::InvalidLoadOrderFile => "Invalid_LO_File",
+            MessageId::ConfigIncomplete => "Config_Incomplete",
+            MessageId::XEditNotConfigured => "XEdit_Not_Configured",
+            MessageId::LoadOrderNotConfigured => "LO_Not_Configured",
+            MessageId::DataFolderNotConfigured => "Data_Folder_Not_Configured",
+            MessageId::GameTypeNotConfigured => "Game_Not_Detected",
+            MessageId::XEditUpdateRequired => "XEdit_Update_Required",
+            MessageId::InvalidCommand => "Invalid_XEdit_Command",
+            MessageId::PluginNotFound => "Plugin_Not_Found",
+            MessageId::PluginNotInstalled => "Plugin_Not_Installed",
+            MessageId::CleaningTimeout => "XEdit_Timeout",
+            MessageId::XEditHung => "XEdit_Hung",
+            MessageId::ProcessFailed => "XEdit_Process_Failed",
+            MessageId::LogFileNotFound => "XEdit_Log_Missing",
+            MessageId::LogParseFailed => "XEdit_Log_Invalid",
+            MessageId::CleaningFailed => "Cleaning_Failed",
+            MessageId::CorruptedRecord => "Corrupted_Record",
+            MessageId::XEditCrashed => "XEdit_Access_Violation",
+            MessageId::XEditException => "XEdit_Exception",
+            MessageId::AlreadyRunning => "AutoQAC_Running",
+            MessageId::ProgramsRunning => "Programs_Running",
+            MessageId::InvalidIniSetup => "Invalid_INI_Setup",
+            MessageId::OutdatedPact => "Outdated_PACT",
+            MessageId::PactUpdateFailed => "PACT_Update_Failed",
+            MessageId::MissingRequirements => "Missing_Requirements",
+            MessageId::MastersOutOfOrder => "Masters_Out_Of_Order",
+            MessageId::LoadOrderError => "Load_Order_Error",
+            MessageId::OldXEditVersion => "Old_XEdit_Version",
+            MessageId::VortexHardLinks => "Vortex_Hard_Links",
+            MessageId::NothingDetected => "Nothing_Detected",
+        }
+    }
+
+    /// Section of the main config the message lives in
+    pub const fn severity(self) -> Severity {
+        match self {
+            MessageId::InvalidIniSetup
+            | MessageId::OutdatedPact
+            | MessageId::PactUpdateFailed
+            | MessageId::MissingRequirements
+            | MessageId::MastersOutOfOrder
+            | MessageId::LoadOrderError
+            | MessageId::OldXEditVersion
+            | MessageId::VortexHardLinks
+            | MessageId::NothingDetected => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    /// Built-in English text, used when the main config has no entry
+    pub const fn default_text(self) -> &'static str {
+        match self {
+            MessageId::InvalidXEditFile => {
+                "❌ ERROR : CANNOT DETERMINE THE SET XEDIT EXECUTABLE FROM PACT SETTINGS!\n\
+                 Make sure that you have set XEDIT EXE path to a valid .exe file!\n\
+                 OR try changing XEDIT EXE path to a different XEdit version."
+            }
+            MessageId::InvalidLoadOrderFile => {
+                "❌ ERROR : CANNOT PROCESS LOAD ORDER FILE FOR XEDIT IN THIS SITUATION!\n\
+                 You have to set your load order file path to loadorder.txt and NOT plugins.txt\n\
+                 This is so PACT can detect the right game. Change the load order file path and try again."
+            }
+            MessageId::ConfigIncomplete => {
+                "❌ ERROR : Configuration Incomplete\n\
+                 Please configure the following before starting:\n\n{missing}"
+            }
+            MessageId::XEditNotConfigured => {
+                "❌ ERROR : xEdit Not Configured\n\
+                 Select your xEdit executable and try again."
+            }
+            MessageId::LoadOrderNotConfigured => {
+                "❌ ERROR : Load Order Not Configured\n\
+                 Select your plugins.txt or loadorder.txt and try again."
+            }
+            MessageId::DataFolderNotConfigured => {
+                "❌ ERROR : Configuration Incomplete\n\
+                 Vortex mode needs the game Data folder so AutoQAC can read vortex.deployment.json.\n\n\
+                 Please select the Data folder before starting."
+            }
+            MessageId::GameTypeNotConfigured => {
+                "❌ ERROR : Game Not Detected\n\
+                 AutoQAC could not tell which game to clean. Check the xEdit and load order paths."
+            }
+            MessageId::XEditUpdateRequired => {
+                "❌ ERROR : xEdit Update Required\n\
+                 The selected xEdit is too old for this game or for the enabled options.\n\n\
+                 Please update xEdit or disable the options listed below."
+            }
+            MessageId::InvalidCommand => {
+                "❌ ERROR : Invalid xEdit Command\n\
+                 The Command Template or XEdit Arguments setting cannot be used:\n\n{error}"
+            }
+            MessageId::PluginNotFound => {
+                "❌ ERROR : Plugin Not Found\n\
+                 {plugin} is not in the game Data folder."
+            }
+            MessageId::PluginNotInstalled => {
+                "❌ ERROR : Plugin Not Installed\n\
+                 {plugin} is not in the game Data folder, so xEdit cannot load it.\n\
+                 Install it with your mod manager first, then clean it again."
+            }
+            MessageId::CleaningTimeout => {
+                "❌ ERROR : xEdit Timed Out\n\
+                 xEdit did not finish within {timeout} seconds."
+            }
+            MessageId::XEditHung => {
+                "❌ ERROR : xEdit Stopped Responding\n\
+                 xEdit used less than {threshold}% CPU for {window} seconds and was stopped."
+            }
+            MessageId::ProcessFailed => {
+                "❌ ERROR : xEdit Could Not Run\n\
+                 Process error: {error}"
+            }
+            MessageId::LogFileNotFound => {
+                "❌ ERROR : xEdit Log Not Found\n\
+                 Log file not found: {path}"
+            }
+            MessageId::LogParseFailed => {
+                "❌ ERROR : xEdit Log Unreadable\n\
+                 Failed to parse log file: {error}"
+            }
+            MessageId::CleaningFailed => {
+                "❌ ERROR : Cleaning Failed\n\
+                 An error occurred during the cleaning process."
+            }
+            MessageId::CorruptedRecord => {
+                "❌ ERROR : Corrupted Plugin\n\
+                 {plugin} has a record xEdit cannot read: {record}\n\
+                 Reinstall the mod or ask its author for a fixed version before cleaning it."
+            }
+            MessageId::XEditCrashed => {
+                "❌ ERROR : xEdit Crashed\n\
+                 xEdit hit an access violation while cleaning {plugin}.\n\
+                 This is usually a problem with xEdit rather than the plugin. \
+                 Update xEdit and clean the plugin again."
+            }
+            MessageId::XEditException => {
+                "❌ ERROR : xEdit Exception\n\
+                 xEdit reported an unrecognized error for {plugin}: {details}\n\
+                 The full text is in the plugin's exception log in the session folder."
+            }
+            MessageId::AlreadyRunning => {
+                "❌ ERROR : AutoQAC Is Already Running\n\
+                 Another AutoQAC (process {pid}) is cleaning plugins from the same AutoQAC Data folder.\n\
+                 Two runs at once corrupt plugins. Wait for the other run to finish, \
+                 then start cleaning again."
+            }
+            MessageId::ProgramsRunning => {
+                "❌ ERROR : Programs Using Your Plugins\n\
+                 These programs have the game's plugins open, and cleaning them now would corrupt them:\n\n\
+                 {programs}\n\n\
+                 Save your work, close them, then start cleaning again."
+            }
+            MessageId::InvalidIniSetup => {
+                "❌  WARNING : YOUR PACT INI SETUP IS INCORRECT!\n\
+                 You likely set the wrong XEdit version for your game.\n\
+                 Check your EXE or PACT Settings.toml settings and try again."
+            }
+            MessageId::OutdatedPact => {
+                "❌  WARNING : YOUR PACT VERSION IS OUTDATED!\n\
+                 You can download the latest version from the PACT Nexus Page.\n\
+                 https://www.nexusmods.com/fallout4/mods/48065"
+            }
+            MessageId::PactUpdateFailed => {
+                "❌  WARNING : PACT FAILED TO CHECK FOR UPDATES!\n\
+                 You can download the latest version from the PACT Nexus Page.\n\
+                 https://www.nexusmods.com/fallout4/mods/48065"
+            }
+            MessageId::MissingRequirements => {
+                "❌  WARNING : Missing Master\n\
+                 {plugin} requires {master}, which is not installed or not enabled.\n\
+                 Install and enable the master, or disable the plugin."
+            }
+            MessageId::MastersOutOfOrder => {
+                "❌  WARNING : Masters Out Of Order\n\
+                 {plugin} is loaded before one of its masters.\n\
+                 Sort the load order (for example with LOOT) so masters load first, \
+                 then clean the plugin again."
+            }
+            MessageId::LoadOrderError => {
+                "❌  WARNING : Load Order Error\n\
+                 xEdit could not match the masters of {plugin} to the load order.\n\
+                 Check that its masters are enabled in plugins.txt, then clean the plugin again."
+            }
+            MessageId::OldXEditVersion => {
+                "❌  WARNING : Old xEdit Version\n\
+                 Cleaning will continue, but results may differ from current xEdit:\n\n{issues}"
+            }
+            MessageId::VortexHardLinks => {
+                "❌  WARNING : Vortex Hard-Link Deployment\n\
+                 Vortex deployed your plugins as hard links.\n\n\
+                 Cleaning a hard-linked plugin also changes the copy in the Vortex staging folder, \
+                 so {count} plugin(s) from {staging} will be modified in place.\n\n\
+                 Reinstalling a mod in Vortex restores its uncleaned plugin."
+            }
+            MessageId::NothingDetected => {
+                "❌  WARNING : Nothing Detected\n\
+                 No supported game installs, load order files or xEdit executables were found. \
+                 Please browse for them manually."
+            }
+        }
+    }
+}
+
+impl fmt::Display for MessageId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+/// A looked-up message with its placeholders filled in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub id: MessageId,
+    pub text: String,
+}
+
+impl Message {
+    /// First line without the decoration (e.g. "YOUR PACT INI SETUP IS INCORRECT!")
+    pub fn title(&self) -> &str {
+        let first = self.text.lines().next().unwrap_or_default();
+        first
+            .split_once(" : ")
+            .map_or(first, |(_, title)| title)
+            .trim()
+    }
+
+    /// Everything after the title line
+    pub fn body(&self) -> &str {
+        self.text
+            .split_once('\n')
+            .map_or("", |(_, body)| body)
+            .trim()
+    }
+
+    /// First line of the body, or the title for one-line messages
+    pub fn summary(&self) -> &str {
+        self.body()
+            .lines()
+            .next()
+            .filter(|line| !line.is_empty())
+            .unwrap_or_else(|| self.title())
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// Message lookups over the main config, falling back to the built-in text
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessageCatalog<'a> {
+    data: Option<&'a PactData>,
+}
+
+impl<'a> MessageCatalog<'a> {
+    /// Catalog over the `Errors` and `Warnings` of a main config
+    pub fn new(config: &'a MainConfig) -> Self {
+        Self {
+            data: Some(&config.pact_data),
+        }
+    }
+
+    /// Catalog with only the built-in text
+    pub fn builtin() -> Self {
+        Self::default()
+    }
+
+    /// Raw message text for an id, without placeholders filled in
+    pub fn text(&self, id: MessageId) -> &str {
+        self.data
+            .and_then(|data| match id.severity() {
+                Severity::Error => data.errors.get(id.key()),
+                Severity::Warning => data.warnings.get(id.key()),
+            })
+            .map(|text| text.trim_end())
+            .unwrap_or_else(|| id.default_text())
+    }
+
+    /// Look up a message that has no placeholders
+    pub fn get(&self, id: MessageId) -> Message {
+        self.format(id, &[])
+    }
+
+    /// Look up a message and replace its `{name}` placeholders.
+    ///
+    /// Placeholders without a value are left as they are, so a customized message with a
+    /// typo still shows something readable.
+    pub fn format(&self, id: MessageId, args: &[(&str, &dyn fmt::Display)]) -> Message {
+        Message {
+            id,
+            text: substitute(self.text(id), args),
+        }
+    }
+}
+
+/// Replace `{name}` placeholders in one pass, so values are never substituted again
+fn substitute(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            args.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (end, value.to_string()))
+        });
+
+        match value {
+            Some((end, value)) => {
+                out.push_str(&value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+
+    fn config_with_error(key: &str, text: &str) -> MainConfig {
+        MainConfig {
+            pact_data: PactData {
+                errors: IndexMap::from([(key.to_string(), text.to_string())]),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_config_text_overrides_builtin() {
+        let config = config_with_error(
+            "Plugin_Not_Found",
+            "❌ FEHLER : Plugin fehlt\n{plugin} ist nicht im Data-Ordner.\n",
+        );
+        let catalog = MessageCatalog::new(&config);
+
+        let message = catalog.format(MessageId::PluginNotFound, &[("plugin", &"Test.esp")]);
+        assert_eq!(message.title(), "Plugin fehlt");
+        assert_eq!(message.body(), "Test.esp ist nicht im Data-Ordner.");
+
+        // Ids missing from the config use the built-in text
+        let message = catalog.get(MessageId::CleaningFailed);
+        assert_eq!(message.title(), "Cleaning Failed");
+        assert_eq!(
+            message.summary(),
+            "An error occurred during the cleaning process."
+        );
+    }
+
+    #[test]
+    fn test_placeholder_substitution() {
+        let args: [(&str, &dyn fmt::Display); 2] = [("count", &3), ("staging", &"{count}")];
+        assert_eq!(
+            substitute("{count} from {staging}, {unknown} {", &args),
+            "3 from {count}, {unknown} {"
+        );
+    }
+
+    #[test]
+    fn test_keys_are_unique() {
+        let mut keys: Vec<&str> = MessageId::ALL.iter().map(|id| id.key()).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        assert_eq!(keys.len(), MessageId::ALL.len());
+
+        for id in MessageId::ALL {
+            assert!(!MessageCatalog::builtin().get(id).title().is_empty());
+        }
+    }
+}
@@ -12,6 +12,52 @@
 //!   - Parsing xEdit log files to extract cleaning statistics
 //!   - Error detection from exception logs
 //!
+//! - [`CommandOptions`]: User-configurable xEdit arguments (global and per-game extra
+//!   arguments and a validated argument template with placeholders)
+//!
+//! - [`MessageCatalog`]: Looks up user-facing text by [`MessageId`] in the `Errors` and
+//!   `Warnings` of `AutoQAC Main.yaml`, falling back to built-in English, and fills in
+//!   `{placeholders}`
+//!
+//! - [`PluginHeader`]: Reads a plugin's `TES4` header to tell masters, light and medium
+//!   plugins apart and to get its form version and record count; [`read_masters`] reads the
+//!   masters it depends on
+//!
+//! - [`CleaningPlan`]: Decides for every plugin of the load order whether it will be cleaned,
+//!   and gives the [`Exclusion`] for the ones left out (skip and ignore lists, inactive,
+//!   missing master, unchanged since the last cleaning, clean according to [`LootMasterlist`]);
+//!   [`PluginQueue`] holds the user's selection and order of the plugins it cleans
+//!
+//! - [`discover`]: Finds game installs (Steam, GOG, Epic), `plugins.txt` files and xEdit
+//!   executables and ranks them as [`SetupCandidate`]s for first-run setup
+//!
+//! - [`CleaningSession`]: Per-run folder where each plugin's main and exception logs are
+//!   archived
+//!
+//! - [`XEditException`]: Sorts exception log output into missing masters, load order
+//!   problems, corrupted records, xEdit crashes and unknown errors
+//!
+//! - [`InstanceLock`]: Lock file that keeps two AutoQAC runs from cleaning at once;
+//!   [`running_programs`] finds the game, its editor or an xEdit that has the plugins open
+//!
+//! - [`RetryPolicy`]: How often and after which [`AttemptFailure`]s (timeout, exit code,
+//!   missing log) a plugin is cleaned again, with doubling delays
+//!
+//! - [`plugin_timeout`]: Sizes each plugin's timeout from its [`PluginMetrics`] (file size,
+//!   record count) and earlier run times, bounded by the configured timeout
+//!
+//! - [`Runner`]: Launches xEdit natively, through Wine or through Proton, and translates
+//!   paths between the host and the Wine prefix
+//!
+//! - [`validate_configuration`]: Checks that xEdit, the load order and the Data folder belong
+//!   to the same game and reports conflicts as [`Diagnostic`]s
+//!
+//! - [`VortexDeployment`]: Reads Vortex's `vortex.deployment.json` manifest to map deployed
+//!   plugins back to their staging mods and detect hard-link deployments
+//!
+//! - [`XEditVersion`]: Reads the xEdit version resource and checks it against the
+//!   [`XEditFeature`] capability table (QAC, partial forms, game modes)
+//!
 //! - [`CleanResult`]: Complete result of a single plugin cleaning operation, including:
 //!   - [`CleanStatus`]: Success, skipped, or failure state
 //!   - [`CleaningStats`]: ITMs, UDRs, navmeshes, partial forms removed
@@ -43,24 +89,67 @@
 //! // Execute cleaning
 //! let result = service.execute_cleaning_command(
 //!     &command,
-//!     &xedit_exe_path,
-//!     &data_folder_path,
-//!     300, // timeout in seconds
+//!     &runner,
+//!     Duration::from_secs(300),
+//!     HangDetector::new(5, Duration::from_secs(120)), // stop after 2 minutes below 5% CPU
 //! ).await?;
 //! ```
 //!
 //! # xEdit Integration
 //!
 //! The service integrates with xEdit by:
-//! 1. Clearing old log files before execution
-//! 2. Running xEdit with `-QAC -autoexit -autoload` flags
-//! 3. Monitoring exception logs for errors (missing masters, empty plugins)
-//! 4. Parsing main log files using regex to extract statistics
+//! 1. Running xEdit with `-QAC -autoexit -autoload` flags and `-R:` pointing its main log
+//!    into the run's [`CleaningSession`] folder
+//! 2. Archiving what xEdit appends to its exception log, without touching the user's logs
+//! 3. Classifying exception log output (missing masters, load order problems, corrupted
+//!    records, access violations)
+//! 4. Stopping xEdit when its CPU usage shows it has hung ([`hang`])
+//! 5. Parsing main log files using regex to extract statistics
 //!
 //! See the [xEdit documentation](https://tes5edit.github.io/) for details on QAC mode.
 
 pub mod cleaning;
+pub mod command;
+pub mod discovery;
+pub mod exception_log;
 pub mod game_detection;
+pub mod hang;
+pub mod instance;
+pub mod loot;
+pub mod messages;
+pub mod navmesh;
+pub mod plan;
+pub mod plugin_header;
+pub mod retry;
+pub mod runner;
+pub mod session;
+pub mod timeout;
+pub mod validation;
+pub mod vortex;
+pub mod xedit_version;
 
 pub use cleaning::{CleanResult, CleanStatus, CleaningError, CleaningService, CleaningStats};
-pub use game_detection::{detect_game_from_load_order, detect_xedit_game};
+pub use command::{CommandError, CommandOptions, CommandValues};
+pub use discovery::{DiscoverySources, SetupCandidate, discover};
+pub use exception_log::XEditException;
+pub use game_detection::{
+    detect_game, detect_game_from_load_order, detect_game_from_load_order_with, detect_xedit_game,
+    detect_xedit_game_with, find_load_order, refine_game_by_form_version,
+};
+pub use hang::HangDetector;
+pub use instance::{InstanceLock, blocking_executables, running_programs};
+pub use loot::{LootMasterlist, plugin_crc};
+pub use messages::{Message, MessageCatalog, MessageId, Severity};
+pub use navmesh::{DeletedNavmesh, NavmeshReport};
+pub use plan::{
+    CleaningPlan, Exclusion, LoadOrderEntry, PlanEntry, PlanSources, PluginQueue, QueuedPlugin,
+    plugin_name, read_load_order,
+};
+pub use plugin_header::{PluginHeader, PluginKind, read_masters};
+pub use retry::{AttemptFailure, RetryPolicy};
+pub use runner::Runner;
+pub use session::{CleaningSession, ExceptionLogWatch, PluginLogs};
+pub use timeout::{PluginMetrics, PluginTimeout, TimeoutBasis, plugin_timeout};
+pub use validation::{ConfigPaths, Diagnostic, validate_command, validate_configuration};
+pub use vortex::VortexDeployment;
+pub use xedit_version::{CapabilityIssue, XEditFeature, XEditVersion};
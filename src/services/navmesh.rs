@@ -0,0 +1,164 @@
+//! Reports of deleted navmeshes that need a manual fix.
+//!
+//! QAC cannot repair deleted navmeshes; xEdit only logs them as `Skipping:` lines and leaves
+//! them in the plugin. Each line names the navmesh's FormID and the cell it belongs to, e.g.
+//!
+//! ```text
+//! Skipping: [NAVM:0009A5B2] (in GRUP Cell Temporary Children of [CELL:00009C8E] (in Tamriel "Skyrim" [WRLD:0000003C] at 5,-3))
+//! ```
+//!
+//! [`NavmeshReport`] collects these per plugin and writes them to `<plugin>_Navmeshes.txt`
+//! in the session folder, so the navmeshes can be undeleted and moved by hand in xEdit or
+//! the Creation Kit.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::navmesh::NavmeshReport;
+//!
+//! let navmeshes = service.deleted_navmeshes(&logs.main_log)?;
+//! let report = NavmeshReport::new("MyPlugin.esp", navmeshes);
+//! report.write(&logs.navmesh_report)?;
+//! ```
+
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use regex::Regex;
+use std::fmt::Write as _;
+use std::fs;
+use std::sync::LazyLock;
+
+/// First record reference on the line, e.g. `[NAVM:0009A5B2]`
+static FORM_ID_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[(?:[A-Z0-9_]{4}:)?([0-9A-Fa-f]{8})\]").expect("Invalid FormID regex")
+});
+
+/// Cell the navmesh belongs to, with its worldspace and grid position when xEdit gives them
+static CELL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"Children of ((?:"[^"]*"\s*)?\[CELL:[0-9A-Fa-f]{8}\](?:\s*\(in [^()]*\))?)"#)
+        .expect("Invalid cell regex")
+});
+
+/// A deleted navmesh from the xEdit log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedNavmesh {
+    /// FormID of the navmesh (e.g. `0009A5B2`)
+    pub form_id: Option<String>,
+
+    /// Cell the navmesh is in (e.g. `[CELL:00009C8E] (in Tamriel "Skyrim" [WRLD:0000003C] at 5,-3)`)
+    pub cell: Option<String>,
+
+    /// Everything xEdit logged after `Skipping:`
+    pub entry: String,
+}
+
+impl DeletedNavmesh {
+    /// Parse the text after `Skipping:`
+    pub fn parse(entry: &str) -> Self {
+        let entry = entry.trim();
+        Self {
+            form_id: FORM_ID_PATTERN
+                .captures(entry)
+                .map(|captures| captures[1].to_uppercase()),
+            cell: CELL_PATTERN
+                .captures(entry)
+                .map(|captures| captures[1].to_string()),
+            entry: entry.to_string(),
+        }
+    }
+}
+
+/// Deleted navmeshes of one plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavmeshReport {
+    pub plugin: String,
+    pub navmeshes: Vec<DeletedNavmesh>,
+}
+
+impl NavmeshReport {
+    pub fn new(plugin: &str, navmeshes: Vec<DeletedNavmesh>) -> Self {
+        Self {
+            plugin: plugin.to_string(),
+            navmeshes,
+        }
+    }
+
+    /// Text of the report file
+    pub fn render(&self) -> String {
+        let mut text = format!(
+            "{}: {} deleted navmesh(es) that QAC cannot fix\n\
+             Undelete each navmesh and move it out of reach, or replace it, in xEdit or the Creation Kit.\n\n",
+            self.plugin,
+            self.navmeshes.len()
+        );
+
+        for navmesh in &self.navmeshes {
+            let _ = writeln!(
+                text,
+                "FormID: {}\nCell:   {}\nLog:    {}\n",
+                navmesh.form_id.as_deref().unwrap_or("unknown"),
+                navmesh.cell.as_deref().unwrap_or("unknown"),
+                navmesh.entry
+            );
+        }
+
+        text
+    }
+
+    /// Write the report file
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be written
+    pub fn write(&self, path: &Utf8Path) -> Result<()> {
+        fs::write(path, self.render())
+            .with_context(|| format!("Failed to write navmesh report: {}", path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_skipping_entry() {
+        let navmesh = DeletedNavmesh::parse(
+            r#" [NAVM:0009a5b2] (in GRUP Cell Temporary Children of [CELL:00009C8E] (in Tamriel "Skyrim" [WRLD:0000003C] at 5,-3))"#,
+        );
+        assert_eq!(navmesh.form_id.as_deref(), Some("0009A5B2"));
+        assert_eq!(
+            navmesh.cell.as_deref(),
+            Some(r#"[CELL:00009C8E] (in Tamriel "Skyrim" [WRLD:0000003C] at 5,-3)"#)
+        );
+        assert!(navmesh.entry.starts_with("[NAVM:0009a5b2]"));
+
+        let interior = DeletedNavmesh::parse(
+            r#"[NAVM:00012345] (in GRUP Cell Temporary Children of "MyDungeon01" [CELL:01000D62])"#,
+        );
+        assert_eq!(
+            interior.cell.as_deref(),
+            Some(r#""MyDungeon01" [CELL:01000D62]"#)
+        );
+
+        let unknown = DeletedNavmesh::parse("something xEdit did not say before");
+        assert_eq!(unknown.form_id, None);
+        assert_eq!(unknown.cell, None);
+    }
+
+    #[test]
+    fn test_report_lists_each_navmesh() {
+        let report = NavmeshReport::new(
+            "MyPlugin.esp",
+            vec![
+                DeletedNavmesh::parse(
+                    "[NAVM:00012345] (in GRUP Cell Temporary Children of [CELL:01000D62])",
+                ),
+                DeletedNavmesh::parse("[NAVM:00054321]"),
+            ],
+        );
+
+        let text = report.render();
+        assert!(text.starts_with("MyPlugin.esp: 2 deleted navmesh(es)"));
+        assert!(text.contains("FormID: 00012345\nCell:   [CELL:01000D62]\n"));
+        assert!(text.contains("FormID: 00054321\nCell:   unknown\n"));
+    }
+}
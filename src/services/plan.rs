@@ -0,0 +1,697 @@
+//! Cleaning plans: which plugins of the load order a run will clean, and why the others are left out.
+//!
+//! [`CleaningPlan::build`] walks the load order in order and gives every plugin a decision.
+//! A plugin is left out, with the first [`Exclusion`] that applies, when it is
+//! - in the game's skip list (including the game's own masters)
+//! - in `PACT Ignore.yaml`
+//! - inactive in `plugins.txt` (a line without `*` when other lines have one)
+//! - missing a master: the master is neither active, an implicit master of the game, nor
+//!   present in the Data folder without being disabled in the load order
+//! - unchanged since AutoQAC last cleaned it (same CRC32 as recorded in the history)
+//! - listed as clean by LOOT (same CRC32 as a `clean` entry of the masterlist)
+//!
+//! The last three need the Data folder to read the plugins; without it they are not checked.
+//! The same plan is shown in the GUI plugin list, printed by `--dry-run` and used by the
+//! cleaning run, so the list always matches what gets cleaned.
+//!
+//! In the GUI the plugins the plan cleans form a [`PluginQueue`]: the user can deselect
+//! plugins and drag them into another order, and the run cleans the selected ones in that
+//! order.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::plan::{CleaningPlan, PlanSources, read_load_order};
+//!
+//! let load_order = read_load_order(Utf8Path::new("plugins.txt"))?;
+//! let sources = PlanSources {
+//!     registry: &registry,
+//!     main_config: &main_config,
+//!     ignore_config: &ignore_config,
+//!     game: registry.get("SSE"),
+//!     data_path: Some(Utf8Path::new("C:/Games/Skyrim Special Edition/Data")),
+//!     history: Some(&history),
+//!     loot: None,
+//! };
+//! let plan = CleaningPlan::build(&load_order, &sources);
+//! print!("{}", plan);
+//! ```
+
+use crate::models::{CleaningHistory, GameDefinition, GameRegistry, IgnoreConfig, MainConfig};
+use crate::services::loot::{LootMasterlist, plugin_crc};
+use crate::services::plugin_header::read_masters;
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+
+/// Plugin file extensions read from load order files
+const PLUGIN_EXTENSIONS: [&str; 3] = ["esp", "esm", "esl"];
+
+/// A plugin listed in a load order file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadOrderEntry {
+    pub plugin: String,
+
+    /// Whether the plugin is enabled
+    pub active: bool,
+}
+
+/// Read the plugins of a load order file (plugins.txt or loadorder.txt), in order.
+///
+/// When any line has the `*` active marker of newer games' `plugins.txt`, lines without it
+/// are inactive. Files without markers (older games, `loadorder.txt`) list active plugins.
+///
+/// # Errors
+/// Returns an error if the file cannot be read
+pub fn read_load_order(path: &Utf8Path) -> Result<Vec<LoadOrderEntry>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read load order file: {}", path))?;
+    Ok(parse_load_order(&content))
+}
+
+/// Parse the content of a load order file; see [`read_load_order`].
+pub fn parse_load_order(content: &str) -> Vec<LoadOrderEntry> {
+    let lines: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    let has_markers = lines.iter().any(|line| line.starts_with('*'));
+
+    lines
+        .into_iter()
+        .filter_map(|line| {
+            let (plugin, marked) = match line.strip_prefix('*') {
+                Some(stripped) => (stripped.trim(), true),
+                None => (line, false),
+            };
+
+            is_plugin_file(plugin).then(|| LoadOrderEntry {
+                plugin: plugin.to_string(),
+                active: marked || !has_markers,
+            })
+        })
+        .collect()
+}
+
+/// Whether a file name has a plugin extension (`.esp`, `.esm` or `.esl`, any case)
+fn is_plugin_file(name: &str) -> bool {
+    name.rsplit_once('.').is_some_and(|(_, extension)| {
+        PLUGIN_EXTENSIONS
+            .iter()
+            .any(|ext| extension.eq_ignore_ascii_case(ext))
+    })
+}
+
+/// Plugin name of a path or name given to clean a single plugin
+/// (e.g. `C:/Downloads/MyPlugin.esp` or `MyPlugin.esp`); None if it is not a plugin file
+pub fn plugin_name(target: &str) -> Option<String> {
+    // Windows separators too, for paths passed through Wine
+    let name = target.trim().rsplit(['/', '\\']).next()?;
+    is_plugin_file(name).then(|| name.to_string())
+}
+
+/// Why a plugin is left out of a cleaning run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exclusion {
+    /// In the game's skip list or one of its masters
+    SkipList,
+    /// In `PACT Ignore.yaml`
+    IgnoreList,
+    /// Disabled in the load order
+    Inactive,
+    /// Depends on a master that is not loaded
+    MissingMaster(String),
+    /// Same CRC32 as right after AutoQAC last cleaned it
+    Unchanged,
+    /// Same CRC32 as a version LOOT lists as clean, with the tool that checked it
+    LootClean(String),
+}
+
+impl fmt::Display for Exclusion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exclusion::SkipList => write!(f, "in the skip list"),
+            Exclusion::IgnoreList => write!(f, "in the ignore list"),
+            Exclusion::Inactive => write!(f, "inactive in the load order"),
+            Exclusion::MissingMaster(master) => write!(f, "missing master {}", master),
+            Exclusion::Unchanged => write!(f, "unchanged since it was last cleaned"),
+            Exclusion::LootClean(util) if util.is_empty() => write!(f, "clean according to LOOT"),
+            Exclusion::LootClean(util) => write!(f, "clean according to LOOT ({})", util),
+        }
+    }
+}
+
+/// Decision for one plugin of the load order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanEntry {
+    pub plugin: String,
+
+    /// Why the plugin is left out; None if it will be cleaned
+    pub exclusion: Option<Exclusion>,
+}
+
+impl PlanEntry {
+    /// Whether the plugin will be cleaned
+    pub fn is_included(&self) -> bool {
+        self.exclusion.is_none()
+    }
+}
+
+/// Everything a plan is built from
+#[derive(Debug, Clone, Copy)]
+pub struct PlanSources<'a> {
+    pub registry: &'a GameRegistry,
+    pub main_config: &'a MainConfig,
+    pub ignore_config: &'a IgnoreConfig,
+
+    /// Game of the load order; without it the skip and ignore lists are not applied
+    pub game: Option<&'a GameDefinition>,
+
+    /// Data folder the plugins are read from for masters and checksums
+    pub data_path: Option<&'a Utf8Path>,
+
+    /// History with the checksums of cleaned plugins; None cleans unchanged plugins again
+    pub history: Option<&'a CleaningHistory>,
+
+    /// LOOT masterlist; None ignores what LOOT knows
+    pub loot: Option<&'a LootMasterlist>,
+}
+
+/// Ordered decisions for every plugin of a load order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CleaningPlan {
+    pub entries: Vec<PlanEntry>,
+}
+
+impl CleaningPlan {
+    /// Decide for every plugin of the load order whether it will be cleaned
+    pub fn build(load_order: &[LoadOrderEntry], sources: &PlanSources) -> Self {
+        // Active plugins by lower-case name; inactive ones are kept to tell them apart from
+        // plugins the load order does not list at all
+        let listed: HashMap<String, bool> = load_order
+            .iter()
+            .map(|entry| (entry.plugin.to_lowercase(), entry.active))
+            .collect();
+
+        let entries = load_order
+            .iter()
+            .map(|entry| PlanEntry {
+                plugin: entry.plugin.clone(),
+                exclusion: Self::exclusion(entry, &listed, sources),
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Decide whether a single plugin, cleaned on its own without the load order, will be
+    /// cleaned.
+    ///
+    /// Cleaning was asked for, so the plugin is not left out as unchanged or clean according
+    /// to LOOT; masters must be loaded by the game or present in the Data folder.
+    pub fn single(plugin: &str, sources: &PlanSources) -> Self {
+        let load_order = [LoadOrderEntry {
+            plugin: plugin.to_string(),
+            active: true,
+        }];
+        let sources = PlanSources {
+            history: None,
+            loot: None,
+            ..*sources
+        };
+        Self::build(&load_order, &sources)
+    }
+
+    /// First reason that applies to a plugin, in the order listed in the module docs
+    fn exclusion(
+        entry: &LoadOrderEntry,
+        listed: &HashMap<String, bool>,
+        sources: &PlanSources,
+    ) -> Option<Exclusion> {
+        let plugin = entry.plugin.as_str();
+
+        if let Some(game) = sources.game {
+            if sources
+                .registry
+                .is_protected(sources.main_config, &game.id, plugin)
+            {
+                return Some(Exclusion::SkipList);
+            }
+            if sources
+                .registry
+                .ignore_keys(&game.id)
+                .into_iter()
+                .any(|key| sources.ignore_config.is_ignored(key, plugin))
+            {
+                return Some(Exclusion::IgnoreList);
+            }
+        }
+
+        if !entry.active {
+            return Some(Exclusion::Inactive);
+        }
+
+        let data_path = sources.data_path?;
+        let path = data_path.join(plugin);
+
+        match read_masters(&path) {
+            Ok(masters) => {
+                let missing = masters
+                    .into_iter()
+                    .find(|master| !Self::master_available(master, listed, data_path, sources));
+                if let Some(master) = missing {
+                    return Some(Exclusion::MissingMaster(master));
+                }
+            }
+            Err(e) => tracing::debug!("Could not read masters of {}: {}", plugin, e),
+        }
+
+        // Checksums are only worth reading for plugins that something is known about
+        let known_to_history = sources
+            .history
+            .and_then(|history| history.cleaned_crc(plugin));
+        let known_to_loot = sources.loot.filter(|loot| loot.lists(plugin));
+        if known_to_history.is_none() && known_to_loot.is_none() {
+            return None;
+        }
+
+        let crc = match plugin_crc(&path) {
+            Ok(crc) => crc,
+            Err(e) => {
+                tracing::debug!("Could not read checksum of {}: {}", plugin, e);
+                return None;
+            }
+        };
+
+        if known_to_history == Some(crc) {
+            return Some(Exclusion::Unchanged);
+        }
+        known_to_loot
+            .and_then(|loot| loot.clean_info(plugin, crc))
+            .map(|info| Exclusion::LootClean(info.util.clone()))
+    }
+
+    /// Whether a master is loaded: active in the load order, implicitly loaded by the game,
+    /// or in the Data folder without being disabled (e.g. Creation Club content)
+    fn master_available(
+        master: &str,
+        listed: &HashMap<String, bool>,
+        data_path: &Utf8Path,
+        sources: &PlanSources,
+    ) -> bool {
+        match listed.get(&master.to_lowercase()) {
+            Some(&active) => active,
+            None => {
+                sources.game.is_some_and(|game| {
+                    sources
+                        .registry
+                        .lineage(&game.id)
+                        .iter()
+                        .any(|game| game.is_implicit_master(master))
+                }) || data_path.join(master).is_file()
+            }
+        }
+    }
+
+    /// Plugins that will be cleaned, in load order
+    pub fn plugins_to_clean(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.is_included())
+            .map(|entry| entry.plugin.clone())
+            .collect()
+    }
+
+    /// Plugins that are left out, with the reason
+    pub fn excluded(&self) -> impl Iterator<Item = (&str, &Exclusion)> {
+        self.entries.iter().filter_map(|entry| {
+            entry
+                .exclusion
+                .as_ref()
+                .map(|exclusion| (entry.plugin.as_str(), exclusion))
+        })
+    }
+}
+
+impl fmt::Display for CleaningPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            match entry.exclusion {
+                None => writeln!(f, "  clean  {}", entry.plugin)?,
+                Some(ref exclusion) => writeln!(f, "  skip   {} ({})", entry.plugin, exclusion)?,
+            }
+        }
+        writeln!(
+            f,
+            "{} of {} plugin(s) will be cleaned",
+            self.plugins_to_clean().len(),
+            self.entries.len()
+        )
+    }
+}
+
+/// A plugin in the cleaning queue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedPlugin {
+    pub plugin: String,
+
+    /// Whether the next run cleans it
+    pub selected: bool,
+}
+
+/// Plugins a run may clean, in the order they are cleaned, with the user's selection
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginQueue {
+    pub entries: Vec<QueuedPlugin>,
+}
+
+impl PluginQueue {
+    /// Bring the queue in line with a plan.
+    ///
+    /// Plugins the plan no longer cleans are dropped and new ones are added, selected, at the
+    /// end in load order. The rest keep their place and selection.
+    pub fn sync(&mut self, plan: &CleaningPlan) {
+        let plugins = plan.plugins_to_clean();
+
+        self.entries.retain(|entry| plugins.contains(&entry.plugin));
+        for plugin in plugins {
+            if !self.contains(&plugin) {
+                self.entries.push(QueuedPlugin {
+                    plugin,
+                    selected: true,
+                });
+            }
+        }
+    }
+
+    /// Whether a plugin is in the queue
+    pub fn contains(&self, plugin: &str) -> bool {
+        self.position(plugin).is_some()
+    }
+
+    /// Select or deselect a plugin; plugins not in the queue are ignored
+    pub fn set_selected(&mut self, plugin: &str, selected: bool) {
+        if let Some(index) = self.position(plugin) {
+            self.entries[index].selected = selected;
+        }
+    }
+
+    /// Move a plugin to the place of another one, shifting the plugins in between
+    pub fn move_to(&mut self, plugin: &str, target: &str) {
+        if let (Some(from), Some(to)) = (self.position(plugin), self.position(target)) {
+            let entry = self.entries.remove(from);
+            self.entries.insert(to, entry);
+        }
+    }
+
+    /// Selected plugins, in queue order
+    pub fn selected(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.selected)
+            .map(|entry| entry.plugin.clone())
+            .collect()
+    }
+
+    fn position(&self, plugin: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.plugin == plugin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::plugin_header::TestPlugin;
+    use camino::Utf8PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_load_order_active_markers() {
+        let entries =
+            parse_load_order("# comment\n*Skyrim.esm\nDisabled.esp\n*Active.esp\nnotes.txt\n");
+        assert_eq!(
+            entries,
+            vec![
+                LoadOrderEntry {
+                    plugin: "Skyrim.esm".into(),
+                    active: true
+                },
+                LoadOrderEntry {
+                    plugin: "Disabled.esp".into(),
+                    active: false
+                },
+                LoadOrderEntry {
+                    plugin: "Active.esp".into(),
+                    active: true
+                },
+            ]
+        );
+
+        // Older games list only active plugins, without markers
+        let entries = parse_load_order("Oblivion.esm\nMyMod.esp\n");
+        assert!(entries.iter().all(|entry| entry.active));
+    }
+
+    #[test]
+    fn test_plan_reasons() {
+        let temp = TempDir::new().unwrap();
+        let data = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+        fs::write(
+            data.join("Clean.esp"),
+            TestPlugin::new().masters(&["Skyrim.esm"]).build(),
+        )
+        .unwrap();
+        fs::write(
+            data.join("Orphan.esp"),
+            TestPlugin::new().masters(&["Missing.esm"]).build(),
+        )
+        .unwrap();
+        fs::write(
+            data.join("NeedsOff.esp"),
+            TestPlugin::new().masters(&["Disabled.esp"]).build(),
+        )
+        .unwrap();
+        fs::write(
+            data.join("Cc.esp"),
+            TestPlugin::new().masters(&["ccBGSSSE001-Fish.esm"]).build(),
+        )
+        .unwrap();
+        fs::write(data.join("ccBGSSSE001-Fish.esm"), TestPlugin::new().build()).unwrap();
+        fs::write(
+            data.join("Done.esp"),
+            TestPlugin::new().trailing(b"done").build(),
+        )
+        .unwrap();
+        fs::write(
+            data.join("Loot.esp"),
+            TestPlugin::new().trailing(b"loot").build(),
+        )
+        .unwrap();
+
+        let mut history = CleaningHistory::default();
+        history.record_cleaned("Done.esp", plugin_crc(&data.join("Done.esp")).unwrap());
+        let loot_crc = plugin_crc(&data.join("Loot.esp")).unwrap();
+        let loot = LootMasterlist::parse(&format!(
+            "plugins:\n  - name: Loot.esp\n    clean:\n      - crc: {}\n        util: SSEEdit v4.1.5\n",
+            loot_crc
+        ))
+        .unwrap();
+
+        let registry = GameRegistry::builtin();
+        let main_config = MainConfig::default();
+        let mut ignore_config = IgnoreConfig::default();
+        ignore_config.sse.push("Ignored.esp".to_string());
+
+        let load_order = parse_load_order(
+            "*Skyrim.esm\n*Ignored.esp\nDisabled.esp\n*Clean.esp\n*Orphan.esp\n*NeedsOff.esp\n\
+             *Cc.esp\n*Done.esp\n*Loot.esp\n",
+        );
+        let sources = PlanSources {
+            registry: &registry,
+            main_config: &main_config,
+            ignore_config: &ignore_config,
+            game: registry.get("SSE"),
+            data_path: Some(&data),
+            history: Some(&history),
+            loot: Some(&loot),
+        };
+        let plan = CleaningPlan::build(&load_order, &sources);
+
+        let reasons: Vec<_> = plan
+            .entries
+            .iter()
+            .map(|entry| (entry.plugin.as_str(), entry.exclusion.clone()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("Skyrim.esm", Some(Exclusion::SkipList)),
+                ("Ignored.esp", Some(Exclusion::IgnoreList)),
+                ("Disabled.esp", Some(Exclusion::Inactive)),
+                ("Clean.esp", None),
+                (
+                    "Orphan.esp",
+                    Some(Exclusion::MissingMaster("Missing.esm".into()))
+                ),
+                (
+                    "NeedsOff.esp",
+                    Some(Exclusion::MissingMaster("Disabled.esp".into()))
+                ),
+                ("Cc.esp", None),
+                ("Done.esp", Some(Exclusion::Unchanged)),
+                (
+                    "Loot.esp",
+                    Some(Exclusion::LootClean("SSEEdit v4.1.5".into()))
+                ),
+            ]
+        );
+        assert_eq!(plan.plugins_to_clean(), ["Clean.esp", "Cc.esp"]);
+        assert!(
+            plan.to_string()
+                .ends_with("2 of 9 plugin(s) will be cleaned\n")
+        );
+
+        // Without history and LOOT, unchanged and LOOT-clean plugins are cleaned again
+        let plan = CleaningPlan::build(
+            &load_order,
+            &PlanSources {
+                history: None,
+                loot: None,
+                ..sources
+            },
+        );
+        assert_eq!(
+            plan.plugins_to_clean(),
+            ["Clean.esp", "Cc.esp", "Done.esp", "Loot.esp"]
+        );
+    }
+
+    #[test]
+    fn test_plan_without_game_or_data() {
+        let registry = GameRegistry::builtin();
+        let main_config = MainConfig::default();
+        let ignore_config = IgnoreConfig::default();
+        let sources = PlanSources {
+            registry: &registry,
+            main_config: &main_config,
+            ignore_config: &ignore_config,
+            game: None,
+            data_path: None,
+            history: None,
+            loot: None,
+        };
+
+        let plan = CleaningPlan::build(
+            &parse_load_order("*Skyrim.esm\nOff.esp\n*A.esp\n"),
+            &sources,
+        );
+        assert_eq!(plan.plugins_to_clean(), ["Skyrim.esm", "A.esp"]);
+        assert_eq!(
+            plan.excluded().collect::<Vec<_>>(),
+            [("Off.esp", &Exclusion::Inactive)]
+        );
+    }
+
+    #[test]
+    fn test_single_plugin_plan() {
+        let temp = TempDir::new().unwrap();
+        let data = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+        fs::write(
+            data.join("New.esp"),
+            TestPlugin::new().masters(&["Skyrim.esm"]).build(),
+        )
+        .unwrap();
+        fs::write(
+            data.join("Orphan.esp"),
+            TestPlugin::new().masters(&["Missing.esm"]).build(),
+        )
+        .unwrap();
+
+        let mut history = CleaningHistory::default();
+        history.record_cleaned("New.esp", plugin_crc(&data.join("New.esp")).unwrap());
+
+        let registry = GameRegistry::builtin();
+        let main_config = MainConfig::default();
+        let ignore_config = IgnoreConfig::default();
+        let sources = PlanSources {
+            registry: &registry,
+            main_config: &main_config,
+            ignore_config: &ignore_config,
+            game: registry.get("SSE"),
+            data_path: Some(&data),
+            history: Some(&history),
+            loot: None,
+        };
+
+        // Cleaned even though it is unchanged since the last cleaning
+        let plan = CleaningPlan::single("New.esp", &sources);
+        assert_eq!(plan.plugins_to_clean(), ["New.esp"]);
+
+        let plan = CleaningPlan::single("Orphan.esp", &sources);
+        assert_eq!(
+            plan.excluded().collect::<Vec<_>>(),
+            [(
+                "Orphan.esp",
+                &Exclusion::MissingMaster("Missing.esm".into())
+            )]
+        );
+        let plan = CleaningPlan::single("Skyrim.esm", &sources);
+        assert!(plan.plugins_to_clean().is_empty());
+    }
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(
+            plugin_name("C:/Downloads/MyPlugin.ESP").as_deref(),
+            Some("MyPlugin.ESP")
+        );
+        assert_eq!(
+            plugin_name(" MyPlugin.esl ").as_deref(),
+            Some("MyPlugin.esl")
+        );
+        assert_eq!(
+            plugin_name(r"C:\Downloads\MyPlugin.esm").as_deref(),
+            Some("MyPlugin.esm")
+        );
+        assert_eq!(plugin_name("/mods/Readme.txt"), None);
+        assert_eq!(plugin_name("esp"), None);
+    }
+
+    #[test]
+    fn test_queue_keeps_order_and_selection() {
+        let plan_of = |included: &[&str]| CleaningPlan {
+            entries: ["A.esp", "B.esp", "C.esp", "D.esp"]
+                .iter()
+                .map(|plugin| PlanEntry {
+                    plugin: plugin.to_string(),
+                    exclusion: (!included.contains(plugin)).then_some(Exclusion::Inactive),
+                })
+                .collect(),
+        };
+
+        let mut queue = PluginQueue::default();
+        queue.sync(&plan_of(&["A.esp", "B.esp", "C.esp"]));
+        assert_eq!(queue.selected(), ["A.esp", "B.esp", "C.esp"]);
+
+        queue.move_to("C.esp", "A.esp");
+        queue.set_selected("B.esp", false);
+        assert_eq!(queue.selected(), ["C.esp", "A.esp"]);
+
+        queue.move_to("C.esp", "B.esp");
+        assert_eq!(queue.selected(), ["A.esp", "C.esp"]);
+
+        // D becomes cleanable and A does not: A is dropped, D is added at the end
+        queue.sync(&plan_of(&["B.esp", "C.esp", "D.esp"]));
+        assert_eq!(queue.selected(), ["C.esp", "D.esp"]);
+        assert!(!queue.contains("A.esp"));
+        assert_eq!(queue.entries[0].plugin, "B.esp");
+
+        // Plugins outside the queue are ignored
+        queue.set_selected("A.esp", true);
+        queue.move_to("A.esp", "B.esp");
+        assert_eq!(queue.entries.len(), 3);
+    }
+}
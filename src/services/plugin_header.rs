@@ -0,0 +1,389 @@
+//! Plugin header reading for telling masters, light and medium plugins apart.
+//!
+//! Every Bethesda plugin starts with a `TES4` record whose flags say whether it is a master,
+//! a light (small) master or, in Starfield, a medium master. The flag values differ between
+//! games, so they come from the [`GameDefinition`].
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::plugin_header::{PluginHeader, read_masters};
+//! use camino::Utf8Path;
+//!
+//! let header = PluginHeader::read(Utf8Path::new("Data/MyPlugin.esm"))?;
+//! println!("{} records, form version {:?}", header.record_count, header.form_version);
+//!
+//! let masters = read_masters(Utf8Path::new("Data/MyPlugin.esm"))?;
+//! ```
+
+use crate::models::GameDefinition;
+use anyhow::{Context, Result, bail};
+use camino::Utf8Path;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+
+/// `TES4` record flag marking a plugin as a master
+pub const MASTER_FLAG: u32 = 0x1;
+
+/// Record header size for every game since Skyrim (includes the form version)
+const RECORD_HEADER_LEN: usize = 24;
+
+/// Record header size for Oblivion, which has no form version field
+const OBLIVION_RECORD_HEADER_LEN: usize = 20;
+
+/// Bytes read from the start of a plugin; enough for the record header and `HEDR`
+const HEADER_READ_LEN: usize = RECORD_HEADER_LEN + 6 + 12;
+
+/// Largest `TES4` record read for its master list
+const MAX_TES4_LEN: usize = 1024 * 1024;
+
+/// Parsed `TES4` header of a plugin file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PluginHeader {
+    /// `TES4` record flags
+    pub flags: u32,
+
+    /// Form version (None for Oblivion-era 20-byte record headers)
+    pub form_version: Option<u16>,
+
+    /// Header version from `HEDR` (e.g. 0.94 for Skyrim LE, 1.71 for SSE)
+    pub version: f32,
+
+    /// Number of records and groups according to `HEDR`
+    pub record_count: u32,
+}
+
+/// How a plugin takes part in the load order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginKind {
+    /// Regular plugin (.esp without master flags)
+    Plugin,
+    /// Full master
+    Master,
+    /// Light (small) master, loaded in the FE/FF space
+    Light,
+    /// Starfield medium master, loaded in the FD space
+    Medium,
+}
+
+impl fmt::Display for PluginKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginKind::Plugin => write!(f, "plugin"),
+            PluginKind::Master => write!(f, "master"),
+            PluginKind::Light => write!(f, "light master"),
+            PluginKind::Medium => write!(f, "medium master"),
+        }
+    }
+}
+
+impl PluginHeader {
+    /// Read the header of a plugin file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or does not start with a `TES4` record
+    pub fn read(path: &Utf8Path) -> Result<Self> {
+        let mut file =
+            File::open(path).with_context(|| format!("Failed to open plugin: {}", path))?;
+
+        let mut buf = Vec::with_capacity(HEADER_READ_LEN);
+        file.by_ref()
+            .take(HEADER_READ_LEN as u64)
+            .read_to_end(&mut buf)
+            .with_context(|| format!("Failed to read plugin header: {}", path))?;
+
+        Self::parse(&buf).with_context(|| format!("Invalid plugin header: {}", path))
+    }
+
+    /// Parse a header from the first bytes of a plugin file.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < OBLIVION_RECORD_HEADER_LEN || &bytes[0..4] != b"TES4" {
+            bail!("Missing TES4 record");
+        }
+
+        let flags = read_u32(bytes, 8);
+
+        // The HEDR subrecord follows the record header; its offset tells the header size
+        let (header_len, form_version) =
+            if bytes.get(RECORD_HEADER_LEN..RECORD_HEADER_LEN + 4) == Some(b"HEDR") {
+                (RECORD_HEADER_LEN, Some(read_u16(bytes, 20)))
+            } else if bytes.get(OBLIVION_RECORD_HEADER_LEN..OBLIVION_RECORD_HEADER_LEN + 4)
+                == Some(b"HEDR")
+            {
+                (OBLIVION_RECORD_HEADER_LEN, None)
+            } else {
+                bail!("Missing HEDR subrecord");
+            };
+
+        // HEDR: type (4), size (2), version f32, record count u32, next object id u32
+        let data = header_len + 6;
+        if bytes.len() < data + 8 {
+            bail!("Truncated HEDR subrecord");
+        }
+
+        Ok(Self {
+            flags,
+            form_version,
+            version: f32::from_bits(read_u32(bytes, data)),
+            record_count: read_u32(bytes, data + 4),
+        })
+    }
+
+    /// Check whether the master flag is set
+    pub fn is_master(&self) -> bool {
+        self.flags & MASTER_FLAG != 0
+    }
+
+    /// Classify the plugin using the game's light and medium flags.
+    ///
+    /// `.esl` files are always light in games that support light plugins.
+    pub fn kind(&self, plugin: &str, game: &GameDefinition) -> PluginKind {
+        let is_esl = plugin.to_lowercase().ends_with(".esl");
+
+        if game.light_flag != 0 && (self.flags & game.light_flag != 0 || is_esl) {
+            PluginKind::Light
+        } else if game.medium_flag != 0 && self.flags & game.medium_flag != 0 {
+            PluginKind::Medium
+        } else if self.is_master() || plugin.to_lowercase().ends_with(".esm") {
+            PluginKind::Master
+        } else {
+            PluginKind::Plugin
+        }
+    }
+}
+
+/// Read the masters a plugin depends on (`MAST` subrecords of its `TES4` record), in order.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or does not start with a `TES4` record
+pub fn read_masters(path: &Utf8Path) -> Result<Vec<String>> {
+    let mut file = File::open(path).with_context(|| format!("Failed to open plugin: {}", path))?;
+
+    let mut buf = Vec::with_capacity(RECORD_HEADER_LEN);
+    file.by_ref()
+        .take(RECORD_HEADER_LEN as u64)
+        .read_to_end(&mut buf)
+        .with_context(|| format!("Failed to read plugin header: {}", path))?;
+    if buf.len() < OBLIVION_RECORD_HEADER_LEN || &buf[0..4] != b"TES4" {
+        bail!("Invalid plugin header: {}", path);
+    }
+
+    // The record header is followed by the record's data
+    let data_len = (read_u32(&buf, 4) as usize).min(MAX_TES4_LEN);
+    file.take(data_len as u64)
+        .read_to_end(&mut buf)
+        .with_context(|| format!("Failed to read plugin header: {}", path))?;
+
+    parse_masters(&buf).with_context(|| format!("Invalid plugin header: {}", path))
+}
+
+/// Parse the master list from a complete `TES4` record.
+pub fn parse_masters(bytes: &[u8]) -> Result<Vec<String>> {
+    let header_len = match PluginHeader::parse(bytes)?.form_version {
+        Some(_) => RECORD_HEADER_LEN,
+        None => OBLIVION_RECORD_HEADER_LEN,
+    };
+
+    let mut masters = Vec::new();
+    let mut offset = header_len;
+    while offset + 6 <= bytes.len() {
+        let kind = &bytes[offset..offset + 4];
+        let size = read_u16(bytes, offset + 4) as usize;
+        let data = bytes.get(offset + 6..offset + 6 + size).ok_or_else(|| {
+            anyhow::anyhow!("Truncated {} subrecord", String::from_utf8_lossy(kind))
+        })?;
+
+        if kind == b"MAST" {
+            let name = data.split(|&b| b == 0).next().unwrap_or_default();
+            masters.push(String::from_utf8_lossy(name).into_owned());
+        }
+        offset += 6 + size;
+    }
+
+    Ok(masters)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+/// Plugin file bytes for tests: a `TES4` record with `HEDR` and `MAST` subrecords
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub(crate) struct TestPlugin {
+    flags: u32,
+    form_version: u16,
+    version: f32,
+    record_count: u32,
+    masters: Vec<String>,
+    trailing: Vec<u8>,
+}
+
+#[cfg(test)]
+impl TestPlugin {
+    /// Skyrim SE plugin (form version 44, header version 1.71) without flags or masters
+    pub(crate) fn new() -> Self {
+        Self {
+            flags: 0,
+            form_version: 44,
+            version: 1.71,
+            record_count: 0,
+            masters: Vec::new(),
+            trailing: Vec::new(),
+        }
+    }
+
+    pub(crate) fn flags(mut self, flags: u32) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    pub(crate) fn form_version(mut self, form_version: u16) -> Self {
+        self.form_version = form_version;
+        self
+    }
+
+    pub(crate) fn version(mut self, version: f32) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub(crate) fn record_count(mut self, record_count: u32) -> Self {
+        self.record_count = record_count;
+        self
+    }
+
+    pub(crate) fn masters(mut self, masters: &[&str]) -> Self {
+        self.masters = masters.iter().map(|master| master.to_string()).collect();
+        self
+    }
+
+    /// Bytes after the `TES4` record, standing in for the rest of the plugin
+    pub(crate) fn trailing(mut self, bytes: &[u8]) -> Self {
+        self.trailing = bytes.to_vec();
+        self
+    }
+
+    pub(crate) fn build(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"HEDR");
+        data.extend_from_slice(&12u16.to_le_bytes());
+        data.extend_from_slice(&self.version.to_bits().to_le_bytes());
+        data.extend_from_slice(&self.record_count.to_le_bytes());
+        data.extend_from_slice(&0x800u32.to_le_bytes());
+        for master in &self.masters {
+            let name = format!("{}\0", master);
+            data.extend_from_slice(b"MAST");
+            data.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            data.extend_from_slice(name.as_bytes());
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"TES4");
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.flags.to_le_bytes());
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.extend_from_slice(&self.form_version.to_le_bytes());
+        bytes.extend_from_slice(&[0; 2]);
+        bytes.extend_from_slice(&data);
+        bytes.extend_from_slice(&self.trailing);
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::GameRegistry;
+
+    #[test]
+    fn test_parse_header() {
+        let header =
+            PluginHeader::parse(&TestPlugin::new().flags(0x1).record_count(1234).build()).unwrap();
+
+        assert!(header.is_master());
+        assert_eq!(header.form_version, Some(44));
+        assert_eq!(header.record_count, 1234);
+        assert!((header.version - 1.71).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_parse_masters() {
+        let mut bytes = TestPlugin::new().build();
+        for (kind, data) in [
+            (b"CNAM", &b"Author\0"[..]),
+            (b"MAST", b"Skyrim.esm\0"),
+            (b"DATA", &[0; 8]),
+            (b"MAST", b"Update.esm\0"),
+            (b"DATA", &[0; 8]),
+        ] {
+            bytes.extend_from_slice(kind);
+            bytes.extend_from_slice(&(data.len() as u16).to_le_bytes());
+            bytes.extend_from_slice(data);
+        }
+        let data_len = (bytes.len() - RECORD_HEADER_LEN) as u32;
+        bytes[4..8].copy_from_slice(&data_len.to_le_bytes());
+
+        assert_eq!(parse_masters(&bytes).unwrap(), ["Skyrim.esm", "Update.esm"]);
+        assert!(
+            parse_masters(&TestPlugin::new().build())
+                .unwrap()
+                .is_empty()
+        );
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = camino::Utf8PathBuf::try_from(temp.path().join("MyPlugin.esp")).unwrap();
+        bytes.extend_from_slice(b"GRUP and the rest of the plugin");
+        std::fs::write(&path, &bytes).unwrap();
+        assert_eq!(read_masters(&path).unwrap(), ["Skyrim.esm", "Update.esm"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_non_plugin() {
+        assert!(PluginHeader::parse(b"not a plugin file at all").is_err());
+    }
+
+    #[test]
+    fn test_starfield_medium_and_light_flags() {
+        let registry = GameRegistry::builtin();
+        let starfield = registry.get("Starfield").unwrap();
+
+        let header = |flags| {
+            let bytes = TestPlugin::new()
+                .flags(flags)
+                .form_version(555)
+                .version(0.96)
+                .build();
+            PluginHeader::parse(&bytes).unwrap()
+        };
+
+        assert_eq!(header(0x401).kind("Mod.esm", starfield), PluginKind::Medium);
+        assert_eq!(header(0x101).kind("Mod.esm", starfield), PluginKind::Light);
+        assert_eq!(header(0x1).kind("Mod.esm", starfield), PluginKind::Master);
+    }
+
+    #[test]
+    fn test_light_flag_depends_on_game() {
+        let registry = GameRegistry::builtin();
+        let header = PluginHeader::parse(&TestPlugin::new().flags(0x200).build()).unwrap();
+
+        assert_eq!(
+            header.kind("Mod.esp", registry.get("SSE").unwrap()),
+            PluginKind::Light
+        );
+        assert_eq!(
+            header.kind("Mod.esp", registry.get("FO3").unwrap()),
+            PluginKind::Plugin
+        );
+    }
+}
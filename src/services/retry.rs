@@ -0,0 +1,217 @@
+//! Retries for transient xEdit failures.
+//!
+//! xEdit sometimes fails for reasons that have nothing to do with the plugin: a file is still
+//! locked by the previous run, or MO2's virtual file system takes too long to start. The
+//! [`RetryPolicy`] from the user config runs the plugin again when an attempt ends in one of
+//! the `Retry On` outcomes, waiting `Retry Delay` seconds before the second attempt and twice
+//! as long before each further one.
+//!
+//! Failed attempts are kept as [`AttemptFailure`]s and listed in the plugin result.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::retry::{AttemptFailure, RetryPolicy};
+//!
+//! let policy = RetryPolicy::from_settings(&settings);
+//! let failure = AttemptFailure::ExitCode(1);
+//! if policy.should_retry(&failure, 1) {
+//!     tokio::time::sleep(policy.delay_after(1)).await;
+//! }
+//! ```
+
+use crate::i18n;
+use crate::models::{PactSettings, RetryReason};
+use std::fmt;
+use std::time::Duration;
+
+/// Longest wait between two attempts, however many attempts are allowed
+const MAX_DELAY: Duration = Duration::from_secs(300);
+
+/// When and how often a plugin is cleaned again after a failed attempt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts per plugin, including the first (at least 1)
+    pub max_attempts: u32,
+
+    /// Wait before the second attempt; doubled for each further attempt
+    pub delay: Duration,
+
+    /// Outcomes that are worth another attempt
+    pub retry_on: Vec<RetryReason>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 2,
+            delay: Duration::from_secs(10),
+            retry_on: RetryReason::DEFAULT.to_vec(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy from the `Retry Attempts`, `Retry Delay` and `Retry On` settings
+    pub fn from_settings(settings: &PactSettings) -> Self {
+        Self {
+            max_attempts: settings.retry_attempts.max(1),
+            delay: Duration::from_secs(u64::from(settings.retry_delay)),
+            retry_on: settings.retry_on.clone(),
+        }
+    }
+
+    /// Whether to try again after `attempt` (1-based) failed this way
+    pub fn should_retry(&self, failure: &AttemptFailure, attempt: u32) -> bool {
+        attempt < self.max_attempts && self.retry_on.contains(&failure.reason())
+    }
+
+    /// Wait after failed attempt `attempt` (1-based): the delay, doubled per earlier retry
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.delay.saturating_mul(factor).min(MAX_DELAY)
+    }
+}
+
+/// A failed attempt that may be retried
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttemptFailure {
+    /// xEdit did not finish within the cleaning timeout
+    Timeout(Duration),
+
+    /// xEdit stayed idle for the hang window and was stopped
+    Hung(Duration),
+
+    /// xEdit exited with a non-zero code
+    ExitCode(i32),
+
+    /// xEdit exited without writing its log
+    LogNotFound(String),
+}
+
+impl AttemptFailure {
+    /// The `Retry On` setting that covers this failure
+    pub fn reason(&self) -> RetryReason {
+        match self {
+            AttemptFailure::Timeout(_) => RetryReason::Timeout,
+            AttemptFailure::Hung(_) => RetryReason::Hang,
+            AttemptFailure::ExitCode(_) => RetryReason::ExitCode,
+            AttemptFailure::LogNotFound(_) => RetryReason::LogNotFound,
+        }
+    }
+}
+
+impl fmt::Display for AttemptFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttemptFailure::Timeout(duration) => {
+                write!(f, "timed out after {}s", duration.as_secs())
+            }
+            AttemptFailure::Hung(window) => {
+                write!(f, "xEdit hung (idle for {}s)", window.as_secs())
+            }
+            AttemptFailure::ExitCode(code) => write!(f, "xEdit exited with code {}", code),
+            AttemptFailure::LogNotFound(path) => write!(f, "log file not found: {}", path),
+        }
+    }
+}
+
+impl AttemptFailure {
+    /// Translated description for the plugin result; `Display` stays English for the logs
+    pub fn describe(&self) -> String {
+        match self {
+            AttemptFailure::Timeout(duration) => i18n::tr_format(
+                "GuiController",
+                "timed out after {}s",
+                &[&duration.as_secs()],
+            ),
+            AttemptFailure::Hung(window) => i18n::tr_format(
+                "GuiController",
+                "xEdit hung (idle for {}s)",
+                &[&window.as_secs()],
+            ),
+            AttemptFailure::ExitCode(code) => {
+                i18n::tr_format("GuiController", "xEdit exited with code {}", &[code])
+            }
+            AttemptFailure::LogNotFound(path) => {
+                i18n::tr_format("GuiController", "log file not found: {}", &[path])
+            }
+        }
+    }
+}
+
+/// One line for the plugin result listing the failed attempts, e.g.
+/// `attempt 1: timed out after 300s; attempt 2: xEdit exited with code 1`
+pub fn describe_attempts(failures: &[AttemptFailure]) -> String {
+    failures
+        .iter()
+        .enumerate()
+        .map(|(index, failure)| {
+            i18n::tr_format(
+                "GuiController",
+                "attempt {}: {}",
+                &[&(index + 1), &failure.describe()],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_limits_and_reasons() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            delay: Duration::from_secs(5),
+            retry_on: vec![RetryReason::Timeout, RetryReason::LogNotFound],
+        };
+        let timeout = AttemptFailure::Timeout(Duration::from_secs(300));
+
+        assert!(policy.should_retry(&timeout, 1));
+        assert!(policy.should_retry(&timeout, 2));
+        assert!(!policy.should_retry(&timeout, 3));
+        assert!(!policy.should_retry(&AttemptFailure::ExitCode(1), 1));
+        assert!(policy.should_retry(&AttemptFailure::LogNotFound("x".to_string()), 1));
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_limit() {
+        let policy = RetryPolicy {
+            delay: Duration::from_secs(10),
+            ..Default::default()
+        };
+
+        assert_eq!(policy.delay_after(1), Duration::from_secs(10));
+        assert_eq!(policy.delay_after(2), Duration::from_secs(20));
+        assert_eq!(policy.delay_after(3), Duration::from_secs(40));
+        assert_eq!(policy.delay_after(40), MAX_DELAY);
+    }
+
+    #[test]
+    fn test_settings_and_description() {
+        let settings = PactSettings {
+            retry_attempts: 0,
+            retry_delay: 2,
+            retry_on: vec![RetryReason::ExitCode],
+            ..Default::default()
+        };
+        let policy = RetryPolicy::from_settings(&settings);
+        assert_eq!(policy.max_attempts, 1);
+        assert!(!policy.should_retry(&AttemptFailure::ExitCode(1), 1));
+
+        assert_eq!(
+            describe_attempts(&[
+                AttemptFailure::Timeout(Duration::from_secs(300)),
+                AttemptFailure::ExitCode(1),
+            ]),
+            "attempt 1: timed out after 300s; attempt 2: xEdit exited with code 1"
+        );
+        assert_eq!(
+            RetryPolicy::from_settings(&PactSettings::default()),
+            RetryPolicy::default()
+        );
+    }
+}
@@ -0,0 +1,344 @@
+//! Runners for launching the Windows-only xEdit and MO2 executables.
+//!
+//! On Windows xEdit runs natively. On Linux it has to go through Wine (with a `WINEPREFIX`)
+//! or through Proton (with the game's Steam compat data folder). Besides launching the
+//! process, a runner translates paths between the two sides:
+//! - Linux paths passed to xEdit become `Z:\` paths (or `C:\` inside the prefix)
+//! - Windows paths from the config (e.g. `C:\Modding\SSEEdit.exe`) map back into the prefix
+//! - The prefix's `AppData/Local` holds each game's `plugins.txt`
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::models::RunnerKind;
+//! use autoqac::services::runner::Runner;
+//! use camino::Utf8Path;
+//!
+//! let runner = Runner {
+//!     kind: RunnerKind::Wine,
+//!     prefix: Some("/home/user/.wine-skyrim".into()),
+//!     ..Default::default()
+//! };
+//! assert_eq!(runner.windows_path(Utf8Path::new("/mods/SSEEdit.exe")), "Z:\\mods\\SSEEdit.exe");
+//! ```
+
+use crate::models::{PactSettings, RunnerKind};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::env;
+use tokio::process::Command;
+
+/// Wine binary used when neither the settings nor `$WINE` name one
+const DEFAULT_WINE: &str = "wine";
+
+/// User name Proton creates inside its prefixes
+const PROTON_USER: &str = "steamuser";
+
+/// Launch configuration for xEdit and MO2
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Runner {
+    pub kind: RunnerKind,
+
+    /// Wine binary (defaults to `$WINE`, then `wine`)
+    pub wine: Option<Utf8PathBuf>,
+
+    /// Wine prefix (defaults to `$WINEPREFIX`, then `~/.wine`)
+    pub prefix: Option<Utf8PathBuf>,
+
+    /// Path to Proton's `proton` script
+    pub proton: Option<Utf8PathBuf>,
+
+    /// Steam compat data folder (`steamapps/compatdata/<appid>`); the prefix is its `pfx`
+    pub compat_data: Option<Utf8PathBuf>,
+}
+
+impl Runner {
+    /// Build a runner from the user settings.
+    pub fn from_settings(settings: &PactSettings) -> Self {
+        let path = |value: &str| (!value.is_empty()).then(|| Utf8PathBuf::from(value));
+
+        Self {
+            kind: settings.runner,
+            wine: path(&settings.wine_binary),
+            prefix: path(&settings.wine_prefix),
+            proton: path(&settings.proton_path),
+            compat_data: path(&settings.compat_data_path),
+        }
+    }
+
+    /// Check whether executables run directly, without Wine or Proton
+    pub fn is_native(&self) -> bool {
+        self.kind == RunnerKind::Native
+    }
+
+    /// Wine prefix the executables run in (None for native runs)
+    pub fn prefix(&self) -> Option<Utf8PathBuf> {
+        match self.kind {
+            RunnerKind::Native => None,
+            RunnerKind::Wine => self
+                .prefix
+                .clone()
+                .or_else(|| env::var("WINEPREFIX").ok().map(Utf8PathBuf::from))
+                .or_else(|| {
+                    env::var("HOME")
+                        .ok()
+                        .map(|home| Utf8PathBuf::from(home).join(".wine"))
+                }),
+            RunnerKind::Proton => self.compat_data.as_ref().map(|data| data.join("pfx")),
+        }
+    }
+
+    /// Windows `%LOCALAPPDATA%` folder, where games keep `plugins.txt`
+    pub fn local_appdata(&self) -> Option<Utf8PathBuf> {
+        let user = match self.kind {
+            RunnerKind::Native => return env::var("LOCALAPPDATA").ok().map(Utf8PathBuf::from),
+            RunnerKind::Wine => env::var("USER").ok()?,
+            RunnerKind::Proton => PROTON_USER.to_string(),
+        };
+
+        Some(
+            self.prefix()?
+                .join("drive_c/users")
+                .join(user)
+                .join("AppData/Local"),
+        )
+    }
+
+    /// Translate a host path into the path xEdit sees.
+    ///
+    /// Native runs and paths that are already Windows paths are returned unchanged. Paths
+    /// inside the prefix's `drive_c` become `C:\` paths; other absolute paths go through
+    /// Wine's `Z:` drive.
+    pub fn windows_path(&self, path: &Utf8Path) -> String {
+        if self.is_native() || split_drive(path.as_str()).is_some() {
+            return path.to_string();
+        }
+
+        if let Some(prefix) = self.prefix()
+            && let Ok(rest) = path.strip_prefix(prefix.join("drive_c"))
+        {
+            return format!("C:\\{}", rest.as_str().replace('/', "\\"));
+        }
+
+        if path.is_absolute() {
+            format!("Z:{}", path.as_str().replace('/', "\\"))
+        } else {
+            path.as_str().replace('/', "\\")
+        }
+    }
+
+    /// Translate a path from the config into a host path.
+    ///
+    /// The inverse of [`windows_path`](Self::windows_path): `Z:\` paths map to the host
+    /// root, `C:\` to the prefix's `drive_c` and other drives to its `dosdevices` links.
+    pub fn host_path(&self, path: &str) -> Utf8PathBuf {
+        if self.is_native() {
+            return Utf8PathBuf::from(path);
+        }
+
+        let Some((drive, rest)) = split_drive(path) else {
+            return Utf8PathBuf::from(path);
+        };
+        let rest = rest.replace('\\', "/");
+        let rest = rest.trim_start_matches('/');
+
+        match (drive.to_ascii_lowercase(), self.prefix()) {
+            ('z', _) => Utf8PathBuf::from("/").join(rest),
+            ('c', Some(prefix)) => prefix.join("drive_c").join(rest),
+            (drive, Some(prefix)) => prefix
+                .join("dosdevices")
+                .join(format!("{drive}:"))
+                .join(rest),
+            (_, None) => Utf8PathBuf::from(path),
+        }
+    }
+
+    /// Create the process for a command line built by
+    /// [`CleaningService::build_cleaning_command`](super::CleaningService::build_cleaning_command).
+    ///
+    /// Native runs go through the platform shell as before. Wine and Proton runs split the
+    /// quoted command line into arguments and hand them to `wine` / `proton run`.
+    pub fn command(&self, command_line: &str) -> Command {
+        match self.kind {
+            RunnerKind::Native => {
+                if cfg!(target_os = "windows") {
+                    let mut cmd = Command::new("cmd");
+                    cmd.args(["/C", command_line]);
+                    cmd
+                } else {
+                    let mut cmd = Command::new("sh");
+                    cmd.args(["-c", command_line]);
+                    cmd
+                }
+            }
+            RunnerKind::Wine => {
+                let wine = self
+                    .wine
+                    .as_ref()
+                    .map(|wine| wine.to_string())
+                    .or_else(|| env::var("WINE").ok())
+                    .unwrap_or_else(|| DEFAULT_WINE.to_string());
+
+                let mut cmd = Command::new(wine);
+                cmd.args(split_command_line(command_line));
+                if let Some(prefix) = self.prefix() {
+                    cmd.env("WINEPREFIX", prefix.as_str());
+                }
+                cmd
+            }
+            RunnerKind::Proton => {
+                let proton = self
+                    .proton
+                    .as_ref()
+                    .map(|proton| proton.to_string())
+                    .unwrap_or_else(|| "proton".to_string());
+
+                let mut cmd = Command::new(proton);
+                cmd.arg("run").args(split_command_line(command_line));
+                if let Some(compat_data) = &self.compat_data {
+                    cmd.env("STEAM_COMPAT_DATA_PATH", compat_data.as_str());
+
+                    // compat data lives in <steam>/steamapps/compatdata/<appid>
+                    if let Some(steam) = compat_data.ancestors().nth(3) {
+                        cmd.env("STEAM_COMPAT_CLIENT_INSTALL_PATH", steam.as_str());
+                    }
+                }
+                cmd
+            }
+        }
+    }
+}
+
+/// Split a Windows path into its drive letter and the rest (`C:\Games` -> `('C', "\Games")`)
+fn split_drive(path: &str) -> Option<(char, &str)> {
+    let mut chars = path.chars();
+    let drive = chars.next().filter(char::is_ascii_alphabetic)?;
+    (chars.next() == Some(':')).then(|| (drive, &path[2..]))
+}
+
+/// Split a command line into arguments, treating double-quoted runs as one argument
+fn split_command_line(command_line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+
+    for c in command_line.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+
+    if has_arg {
+        args.push(current);
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wine(prefix: &str) -> Runner {
+        Runner {
+            kind: RunnerKind::Wine,
+            prefix: Some(Utf8PathBuf::from(prefix)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_windows_path_translation() {
+        let runner = wine("/home/user/.wine");
+
+        assert_eq!(
+            runner.windows_path(Utf8Path::new("/mods/SSEEdit.exe")),
+            "Z:\\mods\\SSEEdit.exe"
+        );
+        assert_eq!(
+            runner.windows_path(Utf8Path::new("/home/user/.wine/drive_c/xEdit/SSEEdit.exe")),
+            "C:\\xEdit\\SSEEdit.exe"
+        );
+        assert_eq!(
+            runner.windows_path(Utf8Path::new("C:\\xEdit\\SSEEdit.exe")),
+            "C:\\xEdit\\SSEEdit.exe"
+        );
+
+        // Native runs keep paths as they are
+        assert_eq!(
+            Runner::default().windows_path(Utf8Path::new("/mods/SSEEdit.exe")),
+            "/mods/SSEEdit.exe"
+        );
+    }
+
+    #[test]
+    fn test_host_path_translation() {
+        let runner = wine("/home/user/.wine");
+
+        assert_eq!(
+            runner.host_path("Z:\\mods\\SSEEdit.exe"),
+            Utf8PathBuf::from("/mods/SSEEdit.exe")
+        );
+        assert_eq!(
+            runner.host_path("C:\\xEdit\\SSEEdit.exe"),
+            Utf8PathBuf::from("/home/user/.wine/drive_c/xEdit/SSEEdit.exe")
+        );
+        assert_eq!(
+            runner.host_path("D:/Games/Skyrim"),
+            Utf8PathBuf::from("/home/user/.wine/dosdevices/d:/Games/Skyrim")
+        );
+        assert_eq!(
+            runner.host_path("/mods/SSEEdit.exe"),
+            Utf8PathBuf::from("/mods/SSEEdit.exe")
+        );
+    }
+
+    #[test]
+    fn test_proton_prefix_and_appdata() {
+        let runner = Runner {
+            kind: RunnerKind::Proton,
+            compat_data: Some(Utf8PathBuf::from(
+                "/home/user/.steam/steam/steamapps/compatdata/489830",
+            )),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            runner.local_appdata(),
+            Some(Utf8PathBuf::from(
+                "/home/user/.steam/steam/steamapps/compatdata/489830/pfx/drive_c/users/steamuser/AppData/Local"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_split_command_line() {
+        let args = split_command_line(
+            "\"Z:\\MO2\\ModOrganizer.exe\" run \"Z:\\xEdit\\xEdit.exe -sse\" -QAC \"My Plugin.esp\"",
+        );
+
+        assert_eq!(
+            args,
+            vec![
+                "Z:\\MO2\\ModOrganizer.exe",
+                "run",
+                "Z:\\xEdit\\xEdit.exe -sse",
+                "-QAC",
+                "My Plugin.esp"
+            ]
+        );
+    }
+}
@@ -0,0 +1,357 @@
+//! Session folders that keep each plugin's xEdit logs.
+//!
+//! Every cleaning run gets its own folder under [`SESSIONS_DIR`] (e.g.
+//! `logs/sessions/2025-01-31_18-04-22`). xEdit writes each plugin's main log straight into
+//! it through `-R:"{log}"`, so AutoQAC no longer guesses log names from the executable or
+//! deletes the user's own `<xEdit>_log.txt`.
+//!
+//! xEdit has no option for its exception log, which always goes next to the executable.
+//! [`ExceptionLogWatch`] remembers how long each `*Exception.log` there was before the run
+//! and copies only what xEdit appended into the session folder, leaving the original intact.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::session::{CleaningSession, ExceptionLogWatch, SESSIONS_DIR};
+//! use camino::Utf8Path;
+//!
+//! let session = CleaningSession::create(Utf8Path::new(SESSIONS_DIR))?;
+//! let logs = session.plugin_logs("MyPlugin.esp");
+//! logs.clear()?;
+//!
+//! let watch = ExceptionLogWatch::start(Utf8Path::new("C:/Modding/SSEEdit"));
+//! // ... run xEdit with -R:"<logs.main_log>" ...
+//! watch.collect(&logs.exception_log)?;
+//! ```
+
+use anyhow::{Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Folder holding one sub-folder per cleaning run, next to the application logs
+pub const SESSIONS_DIR: &str = "logs/sessions";
+
+/// Session file listing the plugins that need manual attention
+const ATTENTION_FILE: &str = "Needs Manual Attention.txt";
+
+/// Suffix of the exception logs xEdit writes next to its executable
+const EXCEPTION_LOG_SUFFIX: &str = "exception.log";
+
+/// Folder for one cleaning run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CleaningSession {
+    dir: Utf8PathBuf,
+}
+
+impl CleaningSession {
+    /// Create a new session folder named after the current time (UTC) under `root`.
+    ///
+    /// The folder is absolute so xEdit, which runs in its own working directory, can
+    /// write into it.
+    ///
+    /// # Errors
+    /// Returns an error if the folder cannot be created
+    pub fn create(root: &Utf8Path) -> Result<Self> {
+        let root = if root.is_absolute() {
+            root.to_path_buf()
+        } else {
+            let cwd = std::env::current_dir().context("Failed to get the working directory")?;
+            Utf8PathBuf::try_from(cwd)
+                .context("Working directory is not valid UTF-8")?
+                .join(root)
+        };
+
+        let stamp = timestamp(SystemTime::now());
+        let mut dir = root.join(&stamp);
+        let mut suffix = 1;
+        while dir.exists() {
+            suffix += 1;
+            dir = root.join(format!("{}-{}", stamp, suffix));
+        }
+
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create session folder: {}", dir))?;
+        tracing::info!("Session folder: {}", dir);
+
+        Ok(Self { dir })
+    }
+
+    /// Continue in the folder of an earlier session, e.g. to resume an interrupted run.
+    ///
+    /// # Errors
+    /// Returns an error if the folder is gone and cannot be created again
+    pub fn open(dir: &Utf8Path) -> Result<Self> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create session folder: {}", dir))?;
+        tracing::info!("Session folder: {}", dir);
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+        })
+    }
+
+    /// Session folder
+    pub fn dir(&self) -> &Utf8Path {
+        &self.dir
+    }
+
+    /// Log files for one plugin in this session
+    pub fn plugin_logs(&self, plugin: &str) -> PluginLogs {
+        PluginLogs::new(&self.dir, plugin)
+    }
+
+    /// Log files for one QAC pass over a plugin; the first pass uses the plain names
+    pub fn pass_logs(&self, plugin: &str, pass: u32) -> PluginLogs {
+        if pass <= 1 {
+            return self.plugin_logs(plugin);
+        }
+        PluginLogs::new(&self.dir, &format!("{}_pass{}", plugin, pass))
+    }
+
+    /// Navmesh reports of every plugin marked as needing manual attention
+    pub fn attention_report(&self) -> Utf8PathBuf {
+        self.dir.join(ATTENTION_FILE)
+    }
+}
+
+/// Archived logs of one plugin's cleaning run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginLogs {
+    /// Main log, written by xEdit through `-R:` (`<plugin>_log.txt`, or
+    /// `<plugin>_pass<n>_log.txt` for later passes)
+    pub main_log: Utf8PathBuf,
+
+    /// What xEdit appended to its exception log during the run (`<plugin>_Exception.log`)
+    pub exception_log: Utf8PathBuf,
+
+    /// Deleted navmeshes QAC could not fix (`<plugin>_Navmeshes.txt`)
+    pub navmesh_report: Utf8PathBuf,
+}
+
+impl PluginLogs {
+    /// Log paths for a plugin in a folder
+    pub fn new(dir: &Utf8Path, plugin: &str) -> Self {
+        Self {
+            main_log: dir.join(format!("{}_log.txt", plugin)),
+            exception_log: dir.join(format!("{}_Exception.log", plugin)),
+            navmesh_report: dir.join(format!("{}_Navmeshes.txt", plugin)),
+        }
+    }
+
+    /// Remove logs left in the session folder by an earlier attempt at the same plugin
+    ///
+    /// # Errors
+    /// Returns an error if an existing log cannot be removed
+    pub fn clear(&self) -> Result<()> {
+        for log in [&self.main_log, &self.exception_log, &self.navmesh_report] {
+            if log.exists() {
+                fs::remove_file(log)
+                    .with_context(|| format!("Failed to remove old log: {}", log))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sizes of xEdit's exception logs before a run, to pick out what the run added
+#[derive(Debug, Clone, Default)]
+pub struct ExceptionLogWatch {
+    dir: Utf8PathBuf,
+    sizes: Vec<(Utf8PathBuf, u64)>,
+}
+
+impl ExceptionLogWatch {
+    /// Remember the exception logs in xEdit's folder and their current sizes
+    pub fn start(xedit_dir: &Utf8Path) -> Self {
+        let sizes = exception_logs(xedit_dir)
+            .into_iter()
+            .filter_map(|log| Some((log.clone(), fs::metadata(&log).ok()?.len())))
+            .collect();
+
+        Self {
+            dir: xedit_dir.to_path_buf(),
+            sizes,
+        }
+    }
+
+    /// Copy what xEdit appended to its exception logs since [`start`](Self::start) into
+    /// `archive`.
+    ///
+    /// A log that shrank was rewritten by xEdit and is copied whole. Returns whether any
+    /// exception output was found; `archive` is only created when there was some.
+    ///
+    /// # Errors
+    /// Returns an error if an exception log cannot be read or the archive cannot be written
+    pub fn collect(&self, archive: &Utf8Path) -> Result<bool> {
+        let mut output = Vec::new();
+
+        for log in exception_logs(&self.dir) {
+            let content =
+                fs::read(&log).with_context(|| format!("Failed to read exception log: {}", log))?;
+            let before = self
+                .sizes
+                .iter()
+                .find(|(path, _)| *path == log)
+                .map_or(0, |(_, size)| *size);
+
+            let start = usize::try_from(before)
+                .ok()
+                .filter(|&before| before <= content.len())
+                .unwrap_or(0);
+            output.extend_from_slice(&content[start..]);
+        }
+
+        if output.is_empty() {
+            return Ok(false);
+        }
+
+        fs::write(archive, output)
+            .with_context(|| format!("Failed to write exception log: {}", archive))?;
+        Ok(true)
+    }
+}
+
+/// `*Exception.log` files in a folder
+fn exception_logs(dir: &Utf8Path) -> Vec<Utf8PathBuf> {
+    let Ok(entries) = dir.read_dir_utf8() else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_lowercase().ends_with(EXCEPTION_LOG_SUFFIX))
+        })
+        .collect()
+}
+
+/// `YYYY-MM-DD_HH-MM-SS` in UTC
+fn timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}_{:02}-{:02}-{:02}",
+        year,
+        month,
+        day,
+        rest / 3_600,
+        rest % 3_600 / 60,
+        rest % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn temp_dir() -> (TempDir, Utf8PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let path = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+        (temp, path)
+    }
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(UNIX_EPOCH), "1970-01-01_00-00-00");
+        assert_eq!(
+            timestamp(UNIX_EPOCH + Duration::from_secs(1_709_251_199)),
+            "2024-02-29_23-59-59"
+        );
+    }
+
+    #[test]
+    fn test_session_folders_are_unique() {
+        let (_temp, root) = temp_dir();
+
+        let first = CleaningSession::create(&root).unwrap();
+        let second = CleaningSession::create(&root).unwrap();
+        assert!(first.dir().is_dir());
+        assert!(second.dir().is_dir());
+        assert_ne!(first.dir(), second.dir());
+
+        let logs = first.plugin_logs("My Plugin.esp");
+        assert_eq!(logs.main_log, first.dir().join("My Plugin.esp_log.txt"));
+        assert_eq!(
+            logs.exception_log,
+            first.dir().join("My Plugin.esp_Exception.log")
+        );
+
+        assert_eq!(first.pass_logs("My Plugin.esp", 1), logs);
+        let second_pass = first.pass_logs("My Plugin.esp", 2);
+        assert_eq!(
+            second_pass.main_log,
+            first.dir().join("My Plugin.esp_pass2_log.txt")
+        );
+        assert_eq!(
+            second_pass.exception_log,
+            first.dir().join("My Plugin.esp_pass2_Exception.log")
+        );
+    }
+
+    #[test]
+    fn test_exception_log_watch_archives_new_output_only() {
+        let (_temp, root) = temp_dir();
+        let xedit_dir = root.join("xEdit");
+        fs::create_dir_all(&xedit_dir).unwrap();
+        let user_log = xedit_dir.join("SSEEditException.log");
+        fs::write(&user_log, "old exception\n").unwrap();
+        fs::write(xedit_dir.join("SSEEdit_log.txt"), "user log\n").unwrap();
+
+        let archive = root.join("Test.esp_Exception.log");
+
+        // Nothing new: no archive, and the user's log is untouched
+        let watch = ExceptionLogWatch::start(&xedit_dir);
+        assert!(!watch.collect(&archive).unwrap());
+        assert!(!archive.exists());
+
+        // Appended output lands in the archive; the original keeps everything
+        fs::write(
+            &user_log,
+            "old exception\nRequired master which can not be found\n",
+        )
+        .unwrap();
+        assert!(watch.collect(&archive).unwrap());
+        assert_eq!(
+            fs::read_to_string(&archive).unwrap(),
+            "Required master which can not be found\n"
+        );
+        assert!(
+            fs::read_to_string(&user_log)
+                .unwrap()
+                .starts_with("old exception")
+        );
+        assert!(xedit_dir.join("SSEEdit_log.txt").exists());
+    }
+
+    #[test]
+    fn test_plugin_logs_clear() {
+        let (_temp, root) = temp_dir();
+        let logs = PluginLogs::new(&root, "Test.esp");
+        fs::write(&logs.main_log, "previous attempt").unwrap();
+
+        logs.clear().unwrap();
+        assert!(!logs.main_log.exists());
+        logs.clear().unwrap();
+    }
+}
@@ -0,0 +1,262 @@
+//! Per-plugin cleaning timeouts.
+//!
+//! A single `Cleaning Timeout` is too short for large masters and far too long for a tiny
+//! patch that hangs. [`plugin_timeout`] estimates each plugin's timeout from:
+//! - how long xEdit took for the same plugin before ([`CleaningHistory`](crate::models::CleaningHistory)),
+//!   with a safety margin
+//! - otherwise its file size and the record count from its header ([`PluginMetrics`])
+//!
+//! The [`AdaptiveTimeout`] setting keeps `Cleaning Timeout` as a floor (never stop a plugin
+//! earlier than before) or a ceiling (never wait longer than before), or turns estimates off.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::models::AdaptiveTimeout;
+//! use autoqac::services::timeout::{PluginMetrics, plugin_timeout};
+//!
+//! let metrics = PluginMetrics::read(&data_path.join("MyPlugin.esp"));
+//! let timeout = plugin_timeout(global, AdaptiveTimeout::Ceiling, &metrics, &history);
+//! println!("Timeout: {}", timeout);
+//! ```
+
+use super::plugin_header::PluginHeader;
+use crate::models::AdaptiveTimeout;
+use camino::Utf8Path;
+use std::fmt;
+use std::fs;
+use std::time::Duration;
+
+/// xEdit start-up and loading the plugin's masters
+const BASE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Shortest timeout an estimate may give
+const MIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest timeout an estimate may give, unless `Cleaning Timeout` is longer
+const MAX_TIMEOUT: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// Seconds allowed per MiB of plugin data
+const SECS_PER_MIB: u64 = 10;
+
+/// Records xEdit is expected to check per second
+const RECORDS_PER_SEC: u64 = 500;
+
+/// Factor applied to the slowest earlier run
+const HISTORY_MARGIN: u32 = 3;
+
+/// Size of a plugin as seen before cleaning
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PluginMetrics {
+    /// File size in bytes
+    pub size: Option<u64>,
+
+    /// Record count from the `TES4` header
+    pub record_count: Option<u32>,
+}
+
+impl PluginMetrics {
+    /// Read the size and header of a plugin file; missing values stay `None`
+    pub fn read(path: &Utf8Path) -> Self {
+        Self {
+            size: fs::metadata(path).ok().map(|metadata| metadata.len()),
+            record_count: PluginHeader::read(path)
+                .ok()
+                .map(|header| header.record_count),
+        }
+    }
+
+    /// Time the plugin's size suggests, without the start-up time
+    fn processing_time(&self) -> Option<Duration> {
+        let by_size = self
+            .size
+            .map(|size| size.div_ceil(1024 * 1024) * SECS_PER_MIB);
+        let by_records = self
+            .record_count
+            .map(|count| u64::from(count).div_ceil(RECORDS_PER_SEC));
+
+        by_size.max(by_records).map(Duration::from_secs)
+    }
+}
+
+/// What a plugin's timeout was based on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutBasis {
+    /// `Cleaning Timeout` as set
+    Configured,
+    /// File size and record count
+    Size,
+    /// Earlier runs of the same plugin
+    History,
+}
+
+impl fmt::Display for TimeoutBasis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutBasis::Configured => write!(f, "configured"),
+            TimeoutBasis::Size => write!(f, "plugin size"),
+            TimeoutBasis::History => write!(f, "earlier runs"),
+        }
+    }
+}
+
+/// Timeout chosen for one plugin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginTimeout {
+    pub timeout: Duration,
+    pub basis: TimeoutBasis,
+}
+
+impl fmt::Display for PluginTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}s ({})", self.timeout.as_secs(), self.basis)
+    }
+}
+
+/// Timeout for one plugin.
+///
+/// Earlier run times win over the size estimate; without either the configured timeout is
+/// used as is. The estimate is then bounded by `configured` as the mode says.
+///
+/// # Arguments
+/// * `configured` - `Cleaning Timeout`
+/// * `mode` - Whether `configured` is a floor, a ceiling, or used for every plugin
+/// * `metrics` - The plugin's size and record count
+/// * `history` - Earlier run times of the plugin
+pub fn plugin_timeout(
+    configured: Duration,
+    mode: AdaptiveTimeout,
+    metrics: &PluginMetrics,
+    history: &[Duration],
+) -> PluginTimeout {
+    let estimate = match history.iter().max() {
+        _ if mode == AdaptiveTimeout::Off => None,
+        Some(slowest) => Some((
+            slowest.saturating_mul(HISTORY_MARGIN).max(BASE_TIMEOUT),
+            TimeoutBasis::History,
+        )),
+        None => metrics
+            .processing_time()
+            .map(|time| (BASE_TIMEOUT + time, TimeoutBasis::Size)),
+    };
+
+    let Some((estimate, basis)) = estimate else {
+        return PluginTimeout {
+            timeout: configured,
+            basis: TimeoutBasis::Configured,
+        };
+    };
+
+    let timeout = match mode {
+        AdaptiveTimeout::Floor => estimate.min(MAX_TIMEOUT).max(configured),
+        AdaptiveTimeout::Ceiling => estimate.max(MIN_TIMEOUT).min(configured),
+        AdaptiveTimeout::Off => configured,
+    };
+
+    // A bound that decides the timeout is the configured value, not an estimate
+    let basis = if timeout == configured && timeout != estimate {
+        TimeoutBasis::Configured
+    } else {
+        basis
+    };
+
+    PluginTimeout { timeout, basis }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::plugin_header::TestPlugin;
+
+    const CONFIGURED: Duration = Duration::from_secs(300);
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_size_estimate() {
+        let small = PluginMetrics {
+            size: Some(2048),
+            record_count: Some(3),
+        };
+        let large = PluginMetrics {
+            size: Some(250 * 1024 * 1024),
+            record_count: Some(2_000_000),
+        };
+
+        // Ceiling: small patches get a short timeout
+        let timeout = plugin_timeout(CONFIGURED, AdaptiveTimeout::Ceiling, &small, &[]);
+        assert_eq!(timeout.timeout, secs(70));
+        assert_eq!(timeout.basis, TimeoutBasis::Size);
+
+        // Floor: large masters get more time than configured
+        let timeout = plugin_timeout(CONFIGURED, AdaptiveTimeout::Floor, &large, &[]);
+        assert_eq!(timeout.timeout, secs(60 + 4000));
+        assert_eq!(timeout.basis, TimeoutBasis::Size);
+
+        // ...and the bound wins on the other side
+        let timeout = plugin_timeout(CONFIGURED, AdaptiveTimeout::Floor, &small, &[]);
+        assert_eq!(timeout.timeout, CONFIGURED);
+        assert_eq!(timeout.basis, TimeoutBasis::Configured);
+        let timeout = plugin_timeout(CONFIGURED, AdaptiveTimeout::Ceiling, &large, &[]);
+        assert_eq!(timeout.timeout, CONFIGURED);
+    }
+
+    #[test]
+    fn test_history_wins_over_size() {
+        let metrics = PluginMetrics {
+            size: Some(2048),
+            record_count: None,
+        };
+        let history = [secs(40), secs(150), secs(90)];
+
+        let timeout = plugin_timeout(CONFIGURED, AdaptiveTimeout::Floor, &metrics, &history);
+        assert_eq!(timeout.timeout, secs(450));
+        assert_eq!(timeout.basis, TimeoutBasis::History);
+        assert_eq!(timeout.to_string(), "450s (earlier runs)");
+
+        let timeout = plugin_timeout(CONFIGURED, AdaptiveTimeout::Ceiling, &metrics, &[secs(5)]);
+        assert_eq!(timeout.timeout, BASE_TIMEOUT);
+    }
+
+    #[test]
+    fn test_metrics_from_plugin_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = camino::Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+
+        let bytes = TestPlugin::new().record_count(25_000).build();
+        fs::write(dir.join("MyPlugin.esp"), &bytes).unwrap();
+
+        let metrics = PluginMetrics::read(&dir.join("MyPlugin.esp"));
+        assert_eq!(metrics.size, Some(bytes.len() as u64));
+        assert_eq!(metrics.record_count, Some(25_000));
+        assert_eq!(metrics.processing_time(), Some(secs(50)));
+
+        assert_eq!(
+            PluginMetrics::read(&dir.join("Missing.esp")),
+            PluginMetrics::default()
+        );
+    }
+
+    #[test]
+    fn test_configured_timeout_without_estimate() {
+        let metrics = PluginMetrics {
+            size: Some(250 * 1024 * 1024),
+            record_count: None,
+        };
+
+        let timeout = plugin_timeout(CONFIGURED, AdaptiveTimeout::Off, &metrics, &[secs(900)]);
+        assert_eq!(timeout.timeout, CONFIGURED);
+        assert_eq!(timeout.basis, TimeoutBasis::Configured);
+
+        let timeout = plugin_timeout(
+            CONFIGURED,
+            AdaptiveTimeout::Ceiling,
+            &PluginMetrics::default(),
+            &[],
+        );
+        assert_eq!(timeout.timeout, CONFIGURED);
+        assert_eq!(timeout.basis, TimeoutBasis::Configured);
+    }
+}
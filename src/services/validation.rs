@@ -0,0 +1,336 @@
+//! Configuration validation: do xEdit, the load order and the Data folder agree?
+//!
+//! Game detection picks a single game, so a mismatched setup (FO4Edit with a Skyrim
+//! `plugins.txt`) would otherwise only show up as failed cleaning runs. The validator runs
+//! every detector separately and reports conflicts as [`Diagnostic`]s whose text comes from
+//! the [`MessageCatalog`]:
+//! - `Ivalid_XEDIT_File`: the xEdit executable is missing or not a known xEdit
+//! - `Invalid_LO_File`: universal xEdit with `plugins.txt` instead of `loadorder.txt`
+//! - `Invalid_INI_Setup`: xEdit, load order and Data folder belong to different games
+//!
+//! [`validate_command`] separately checks the user's xEdit command template and extra
+//! arguments (`Invalid_XEdit_Command`).
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::validation::{ConfigPaths, validate_configuration};
+//!
+//! let diagnostics = validate_configuration(&registry, &main_config, &ConfigPaths {
+//!     xedit: Some("C:/Modding/FO4Edit.exe".into()),
+//!     load_order: Some("C:/Users/me/AppData/Local/Skyrim Special Edition/plugins.txt".into()),
+//!     data: None,
+//! });
+//! for diagnostic in &diagnostics {
+//!     println!("{}", diagnostic);
+//! }
+//! ```
+
+use crate::models::{GameRegistry, MainConfig};
+use crate::services::command::CommandOptions;
+use crate::services::game_detection::{
+    detect_game_from_load_order_with, refine_game_by_form_version,
+};
+use crate::services::messages::{Message, MessageCatalog, MessageId, Severity};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::fmt;
+
+/// Load order file name universal xEdit needs for game detection
+const LOADORDER_TXT: &str = "loadorder.txt";
+
+/// A configuration problem with the message to show for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Message from the catalog (e.g. `Invalid_INI_Setup`)
+    pub message: Message,
+
+    /// What exactly was found (e.g. "FO4Edit.exe is for FO4, but the load order is for SSE")
+    pub detail: String,
+}
+
+impl Diagnostic {
+    /// Whether cleaning must stop (`Errors`) or may go ahead (`Warnings`)
+    pub fn severity(&self) -> Severity {
+        self.message.id.severity()
+    }
+
+    /// Whether this diagnostic must stop cleaning
+    pub fn is_error(&self) -> bool {
+        self.severity() == Severity::Error
+    }
+
+    /// Message title (e.g. "YOUR PACT INI SETUP IS INCORRECT!")
+    pub fn title(&self) -> &str {
+        self.message.title()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {}: {}",
+            self.severity(),
+            self.message.id,
+            self.detail
+        )
+    }
+}
+
+/// Configured paths to validate
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigPaths {
+    pub xedit: Option<Utf8PathBuf>,
+    pub load_order: Option<Utf8PathBuf>,
+    pub data: Option<Utf8PathBuf>,
+}
+
+/// Validate that xEdit, the load order and the Data folder belong to the same game.
+///
+/// Missing paths are not reported here; the GUI already asks for them before cleaning.
+/// Diagnostics are returned errors first.
+pub fn validate_configuration(
+    registry: &GameRegistry,
+    config: &MainConfig,
+    paths: &ConfigPaths,
+) -> Vec<Diagnostic> {
+    let catalog = MessageCatalog::new(config);
+    let mut diagnostics = Vec::new();
+    let mut report = |id: MessageId, detail: String| {
+        diagnostics.push(Diagnostic {
+            message: catalog.get(id),
+            detail,
+        })
+    };
+
+    // xEdit: must exist and be a known dedicated or universal executable
+    let xedit_name = paths.xedit.as_deref().map(file_name);
+    let xedit_game = xedit_name.and_then(|name| registry.find_by_xedit(name));
+    let is_universal = xedit_name.is_some_and(|name| registry.is_universal_xedit(name));
+
+    if let (Some(xedit), Some(name)) = (paths.xedit.as_deref(), xedit_name) {
+        if !xedit.is_file() {
+            report(
+                MessageId::InvalidXEditFile,
+                format!("{} does not exist", xedit),
+            );
+        } else if !name.to_lowercase().ends_with(".exe") || (xedit_game.is_none() && !is_universal)
+        {
+            report(
+                MessageId::InvalidXEditFile,
+                format!("{} is not a known xEdit executable", name),
+            );
+        }
+    }
+
+    // Load order: universal xEdit detects the game from loadorder.txt only
+    let load_order_game = match paths.load_order.as_deref() {
+        Some(load_order) if load_order.is_file() => {
+            if is_universal && !file_name(load_order).eq_ignore_ascii_case(LOADORDER_TXT) {
+                report(
+                    MessageId::InvalidLoadOrderFile,
+                    format!(
+                        "{} needs loadorder.txt, but {} is selected",
+                        xedit_name.unwrap_or_default(),
+                        file_name(load_order)
+                    ),
+                );
+            }
+            detect_game_from_load_order_with(registry, load_order)
+                .inspect_err(|e| tracing::debug!("Could not read load order: {}", e))
+                .ok()
+                .flatten()
+        }
+        _ => None,
+    };
+
+    // Games are compatible when one is layered on the other (TTW on FNVEdit)
+    let compatible = |a: &str, b: &str| registry.is_based_on(a, b) || registry.is_based_on(b, a);
+
+    if let (Some(xedit_game), Some(lo_game)) = (xedit_game, load_order_game.as_deref())
+        && !compatible(&xedit_game.id, lo_game)
+    {
+        report(
+            MessageId::InvalidIniSetup,
+            format!(
+                "{} is for {}, but the load order is for {}",
+                xedit_name.unwrap_or_default(),
+                xedit_game.id,
+                lo_game
+            ),
+        );
+    }
+
+    // Data folder: the expected game's base master must be there, with the right header
+    let expected = xedit_game
+        .map(|game| game.id.clone())
+        .or(load_order_game.clone());
+    if let (Some(data), Some(expected)) = (paths.data.as_deref(), expected)
+        && let Some(game) = registry.get(&expected)
+        && data.is_dir()
+    {
+        if !data.join(&game.base_master).is_file() {
+            report(
+                MessageId::InvalidIniSetup,
+                format!(
+                    "The Data folder has no {}, so it is not a {} install",
+                    game.base_master, game.id
+                ),
+            );
+        } else {
+            let data_game = refine_game_by_form_version(registry, &game.id, data);
+            if !compatible(&data_game, &game.id) {
+                report(
+                    MessageId::InvalidIniSetup,
+                    format!(
+                        "{} in the Data folder is from {}, not {}",
+                        game.base_master, data_game, game.id
+                    ),
+                );
+            }
+        }
+    }
+
+    diagnostics.sort_by_key(Diagnostic::severity);
+    diagnostics
+}
+
+/// Check the xEdit command template and extra arguments from the user config.
+pub fn validate_command(config: &MainConfig, options: &CommandOptions) -> Option<Diagnostic> {
+    let error = options.validate().err()?;
+
+    Some(Diagnostic {
+        message: MessageCatalog::new(config)
+            .format(MessageId::InvalidCommand, &[("error", &error)]),
+        detail: error.to_string(),
+    })
+}
+
+/// File name component of a path, accepting both `/` and `\` separators
+fn file_name(path: &Utf8Path) -> &str {
+    path.as_str()
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(path.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PactData;
+    use indexmap::IndexMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    struct Setup {
+        _temp: TempDir,
+        root: Utf8PathBuf,
+    }
+
+    impl Setup {
+        fn new() -> Self {
+            let temp = TempDir::new().unwrap();
+            let root = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+            Self { _temp: temp, root }
+        }
+
+        fn file(&self, name: &str, content: &str) -> Utf8PathBuf {
+            let path = self.root.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, content).unwrap();
+            path
+        }
+    }
+
+    fn main_config() -> MainConfig {
+        MainConfig {
+            pact_data: PactData {
+                warnings: IndexMap::from([(
+                    MessageId::InvalidIniSetup.key().to_string(),
+                    "❌  WARNING : YOUR PACT INI SETUP IS INCORRECT!\nYou likely set the wrong XEdit."
+                        .to_string(),
+                )]),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_xedit_and_load_order_for_different_games() {
+        let setup = Setup::new();
+        let registry = GameRegistry::builtin();
+        let paths = ConfigPaths {
+            xedit: Some(setup.file("xEdit/FO4Edit.exe", "")),
+            load_order: Some(setup.file("plugins.txt", "*Skyrim.esm\n*Update.esm\n")),
+            data: None,
+        };
+
+        let diagnostics = validate_configuration(&registry, &main_config(), &paths);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity(), Severity::Warning);
+        assert_eq!(diagnostics[0].message.id, MessageId::InvalidIniSetup);
+        assert_eq!(diagnostics[0].title(), "YOUR PACT INI SETUP IS INCORRECT!");
+        assert!(diagnostics[0].detail.contains("FO4"));
+        assert!(diagnostics[0].detail.contains("SSE"));
+    }
+
+    #[test]
+    fn test_universal_xedit_needs_loadorder_txt() {
+        let setup = Setup::new();
+        let registry = GameRegistry::builtin();
+        let paths = ConfigPaths {
+            xedit: Some(setup.file("xEdit64.exe", "")),
+            load_order: Some(setup.file("plugins.txt", "*Fallout4.esm\n")),
+            data: None,
+        };
+
+        let diagnostics = validate_configuration(&registry, &main_config(), &paths);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].is_error());
+        assert_eq!(diagnostics[0].message.id, MessageId::InvalidLoadOrderFile);
+    }
+
+    #[test]
+    fn test_matching_setup_has_no_diagnostics() {
+        let setup = Setup::new();
+        let registry = GameRegistry::builtin();
+        let paths = ConfigPaths {
+            xedit: Some(setup.file("FNVEdit.exe", "")),
+            load_order: Some(
+                setup.file("plugins.txt", "*FalloutNV.esm\n*TaleOfTwoWastelands.esm\n"),
+            ),
+            data: Some(
+                setup
+                    .file("Data/FalloutNV.esm", "")
+                    .parent()
+                    .unwrap()
+                    .into(),
+            ),
+        };
+
+        assert!(validate_configuration(&registry, &main_config(), &paths).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_xedit_and_wrong_data_folder() {
+        let setup = Setup::new();
+        let registry = GameRegistry::builtin();
+        let paths = ConfigPaths {
+            xedit: Some(setup.file("Notepad.exe", "")),
+            load_order: None,
+            data: None,
+        };
+        let diagnostics = validate_configuration(&registry, &main_config(), &paths);
+        assert_eq!(diagnostics[0].message.id, MessageId::InvalidXEditFile);
+
+        let paths = ConfigPaths {
+            xedit: Some(setup.file("SSEEdit.exe", "")),
+            load_order: None,
+            data: Some(setup.file("Data/Fallout4.esm", "").parent().unwrap().into()),
+        };
+        let diagnostics = validate_configuration(&registry, &main_config(), &paths);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].detail.contains("Skyrim.esm"));
+    }
+}
@@ -0,0 +1,263 @@
+//! Vortex deployment support for mapping deployed plugins back to their staging mods.
+//!
+//! Vortex deploys mods into the game's `Data` folder (hard links by default) and records
+//! every deployed file in a `vortex.deployment.json` manifest next to them. This module
+//! reads that manifest so the cleaning workflow can:
+//! - Tell which mod each plugin in the load order came from
+//! - Warn when cleaning a hard-linked plugin will also change the staged copy in Vortex
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::vortex::VortexDeployment;
+//! use camino::Utf8Path;
+//!
+//! let deployment = VortexDeployment::load(Utf8Path::new("C:/Games/Fallout 4/Data"))?;
+//! if let Some(source) = deployment.source_mod("MyPlugin.esp") {
+//!     println!("MyPlugin.esp comes from {}", source);
+//! }
+//! ```
+
+use anyhow::{Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::fs;
+
+/// File name of the Vortex deployment manifest inside the game's Data folder
+pub const DEPLOYMENT_MANIFEST: &str = "vortex.deployment.json";
+
+/// Deployment method Vortex uses for hard-link deployments
+const HARDLINK_METHOD: &str = "hardlink";
+
+/// Parsed contents of a `vortex.deployment.json` manifest
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VortexDeployment {
+    #[serde(default)]
+    pub version: u32,
+
+    #[serde(default)]
+    pub instance: String,
+
+    /// Deployment method (`hardlink`, `symlink`, `move`, ...)
+    #[serde(default)]
+    pub deployment_method: String,
+
+    /// Vortex staging folder the deployed files are linked from
+    #[serde(default)]
+    pub staging_path: String,
+
+    /// Folder the files were deployed into (normally the game's Data folder)
+    #[serde(default)]
+    pub target_path: String,
+
+    #[serde(default)]
+    pub game_id: String,
+
+    #[serde(default)]
+    pub files: Vec<DeployedFile>,
+}
+
+/// A single file entry from the deployment manifest
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployedFile {
+    /// Path relative to the deployment target (e.g. `MyPlugin.esp`)
+    pub rel_path: String,
+
+    /// Name of the staging folder (mod) the file was deployed from
+    pub source: String,
+
+    #[serde(default)]
+    pub target: String,
+
+    #[serde(default)]
+    pub time: u64,
+}
+
+impl VortexDeployment {
+    /// Load the deployment manifest from the game's Data folder.
+    ///
+    /// # Errors
+    /// Returns an error if the manifest is missing or is not valid JSON
+    pub fn load(data_folder: &Utf8Path) -> Result<Self> {
+        let manifest_path = data_folder.join(DEPLOYMENT_MANIFEST);
+
+        let contents = fs::read_to_string(&manifest_path).with_context(|| {
+            format!(
+                "Failed to read Vortex deployment manifest: {}",
+                manifest_path
+            )
+        })?;
+
+        let deployment = Self::from_json(&contents).with_context(|| {
+            format!(
+                "Failed to parse Vortex deployment manifest: {}",
+                manifest_path
+            )
+        })?;
+
+        tracing::info!(
+            "Loaded Vortex deployment manifest from {} ({} files, method: {})",
+            manifest_path,
+            deployment.files.len(),
+            deployment.deployment_method
+        );
+
+        Ok(deployment)
+    }
+
+    /// Parse a deployment manifest from its JSON contents.
+    pub fn from_json(contents: &str) -> Result<Self> {
+        serde_json::from_str(contents).context("Invalid Vortex deployment manifest")
+    }
+
+    /// Check whether files were deployed as hard links.
+    ///
+    /// Cleaning a hard-linked plugin modifies the same file Vortex keeps in its staging folder.
+    pub fn is_hardlink(&self) -> bool {
+        self.deployment_method.eq_ignore_ascii_case(HARDLINK_METHOD)
+    }
+
+    /// Find the manifest entry for a plugin deployed to the root of the Data folder.
+    pub fn find_plugin(&self, plugin: &str) -> Option<&DeployedFile> {
+        self.files
+            .iter()
+            .find(|file| normalize_rel_path(&file.rel_path).eq_ignore_ascii_case(plugin))
+    }
+
+    /// Get the name of the mod a plugin was deployed from.
+    pub fn source_mod(&self, plugin: &str) -> Option<&str> {
+        self.find_plugin(plugin).map(|file| file.source.as_str())
+    }
+
+    /// Get the path of the staged copy of a plugin inside the Vortex staging folder.
+    pub fn staged_path(&self, plugin: &str) -> Option<Utf8PathBuf> {
+        if self.staging_path.is_empty() {
+            return None;
+        }
+
+        self.find_plugin(plugin).map(|file| {
+            Utf8PathBuf::from(&self.staging_path)
+                .join(&file.source)
+                .join(normalize_rel_path(&file.rel_path))
+        })
+    }
+
+    /// Map each of the given plugins to the mod it was deployed from.
+    ///
+    /// Plugins that are not part of the deployment (base game files, manually installed
+    /// plugins) are left out of the result.
+    pub fn plugin_sources<'a>(
+        &self,
+        plugins: impl IntoIterator<Item = &'a String>,
+    ) -> IndexMap<String, String> {
+        plugins
+            .into_iter()
+            .filter_map(|plugin| {
+                self.source_mod(plugin)
+                    .map(|source| (plugin.clone(), source.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Normalize a manifest-relative path to forward slashes without a leading separator
+fn normalize_rel_path(rel_path: &str) -> String {
+    rel_path
+        .replace('\\', "/")
+        .trim_start_matches('/')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const MANIFEST: &str = r#"{
+        "version": 1,
+        "instance": "1700000000000",
+        "deploymentMethod": "hardlink",
+        "deploymentTime": 1700000000000,
+        "stagingPath": "D:\\Vortex Mods\\fallout4",
+        "targetPath": "C:\\Games\\Fallout 4\\Data",
+        "gameId": "fallout4",
+        "files": [
+            {
+                "relPath": "MyPlugin.esp",
+                "source": "My Mod-1234-1-0-1700000000",
+                "target": "",
+                "time": 1700000000000
+            },
+            {
+                "relPath": "Textures\\MyMod\\diffuse.dds",
+                "source": "My Mod-1234-1-0-1700000000",
+                "time": 1700000000000
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_manifest() {
+        let deployment = VortexDeployment::from_json(MANIFEST).unwrap();
+
+        assert_eq!(deployment.version, 1);
+        assert_eq!(deployment.game_id, "fallout4");
+        assert_eq!(deployment.files.len(), 2);
+        assert!(deployment.is_hardlink());
+    }
+
+    #[test]
+    fn test_source_mod_lookup_is_case_insensitive() {
+        let deployment = VortexDeployment::from_json(MANIFEST).unwrap();
+
+        assert_eq!(
+            deployment.source_mod("myplugin.ESP"),
+            Some("My Mod-1234-1-0-1700000000")
+        );
+        assert_eq!(deployment.source_mod("Fallout4.esm"), None);
+    }
+
+    #[test]
+    fn test_staged_path() {
+        let deployment = VortexDeployment::from_json(MANIFEST).unwrap();
+        let staged = deployment.staged_path("MyPlugin.esp").unwrap();
+
+        assert_eq!(staged.file_name(), Some("MyPlugin.esp"));
+        assert_eq!(
+            staged.parent().and_then(|p| p.file_name()),
+            Some("My Mod-1234-1-0-1700000000")
+        );
+    }
+
+    #[test]
+    fn test_plugin_sources_skips_undeployed_plugins() {
+        let deployment = VortexDeployment::from_json(MANIFEST).unwrap();
+        let plugins = vec!["Fallout4.esm".to_string(), "MyPlugin.esp".to_string()];
+
+        let sources = deployment.plugin_sources(&plugins);
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources["MyPlugin.esp"], "My Mod-1234-1-0-1700000000");
+    }
+
+    #[test]
+    fn test_symlink_deployment_is_not_hardlink() {
+        let deployment =
+            VortexDeployment::from_json(r#"{"deploymentMethod": "symlink", "files": []}"#).unwrap();
+        assert!(!deployment.is_hardlink());
+    }
+
+    #[test]
+    fn test_load_from_data_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_folder = Utf8Path::from_path(temp_dir.path()).unwrap();
+
+        assert!(VortexDeployment::load(data_folder).is_err());
+
+        fs::write(data_folder.join(DEPLOYMENT_MANIFEST), MANIFEST).unwrap();
+        let deployment = VortexDeployment::load(data_folder).unwrap();
+        assert_eq!(deployment.files.len(), 2);
+    }
+}
@@ -0,0 +1,349 @@
+//! xEdit version detection and the capability table used before cleaning.
+//!
+//! xEdit releases differ in what they support: partial forms need 4.1.5b, newer games need
+//! the release that added them (`min_xedit_version` of the [`GameDefinition`], 4.1.5 for
+//! Starfield) and older 3.x builds run Quick Auto Clean differently.
+//! This module reads the version from the executable's `VS_FIXEDFILEINFO` resource and
+//! compares it with the features a cleaning run needs.
+//!
+//! xEdit writes letter releases (4.1.5b) as the fourth version component (4.1.5.2).
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use autoqac::services::xedit_version::{XEditFeature, XEditVersion};
+//! use camino::Utf8Path;
+//!
+//! let version = XEditVersion::read(Utf8Path::new("C:/Modding/SSEEdit.exe"))?;
+//! if !version.supports(&XEditFeature::PartialForms) {
+//!     println!("xEdit {} is too old for partial forms", version);
+//! }
+//! ```
+
+use crate::models::{GameDefinition, GameRegistry};
+use anyhow::{Context, Result, bail};
+use camino::Utf8Path;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+/// Signature that starts a `VS_FIXEDFILEINFO` structure
+const FIXED_FILE_INFO_SIGNATURE: [u8; 4] = 0xFEEF04BDu32.to_le_bytes();
+
+/// xEdit version as `major.minor.patch` plus the release letter (`b` = 2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct XEditVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+    pub build: u16,
+}
+
+impl XEditVersion {
+    pub const fn new(major: u16, minor: u16, patch: u16, build: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            build,
+        }
+    }
+
+    /// Read the file version from an xEdit executable's version resource.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, is not a Windows executable or has no
+    /// version resource
+    pub fn read(path: &Utf8Path) -> Result<Self> {
+        let bytes =
+            fs::read(path).with_context(|| format!("Failed to read xEdit executable: {}", path))?;
+        Self::parse_executable(&bytes)
+            .with_context(|| format!("Failed to read xEdit version from {}", path))
+    }
+
+    /// Find the `VS_FIXEDFILEINFO` in the bytes of a Windows executable.
+    pub fn parse_executable(bytes: &[u8]) -> Result<Self> {
+        if !bytes.starts_with(b"MZ") {
+            bail!("Not a Windows executable");
+        }
+
+        // Signature, struct version, then file version MS and LS words
+        let Some(offset) = bytes
+            .windows(FIXED_FILE_INFO_SIGNATURE.len())
+            .position(|window| window == FIXED_FILE_INFO_SIGNATURE)
+        else {
+            bail!("No version resource");
+        };
+        let Some(info) = bytes.get(offset + 8..offset + 16) else {
+            bail!("Truncated version resource");
+        };
+
+        let word = |i: usize| u16::from_le_bytes([info[i], info[i + 1]]);
+        Ok(Self::new(word(2), word(0), word(6), word(4)))
+    }
+
+    /// Check whether this version has a feature
+    pub fn supports(&self, feature: &XEditFeature) -> bool {
+        *self >= feature.min_version()
+    }
+}
+
+impl fmt::Display for XEditVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        match self.build {
+            0 => Ok(()),
+            build @ 1..=26 => write!(f, "{}", char::from(b'a' + build as u8 - 1)),
+            build => write!(f, ".{}", build),
+        }
+    }
+}
+
+impl FromStr for XEditVersion {
+    type Err = anyhow::Error;
+
+    /// Parse `4.1.5`, `4.1.5b` or `4.1.5.2`
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().trim_start_matches(['v', 'V']);
+        let (numbers, letter) = match s.chars().last() {
+            Some(c) if c.is_ascii_alphabetic() => (&s[..s.len() - 1], Some(c)),
+            _ => (s, None),
+        };
+
+        let parts = numbers
+            .split('.')
+            .map(|part| part.parse::<u16>())
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("Invalid xEdit version: {}", s))?;
+        if parts.is_empty() || parts.len() > 4 || (letter.is_some() && parts.len() > 3) {
+            bail!("Invalid xEdit version: {}", s);
+        }
+
+        let part = |i: usize| parts.get(i).copied().unwrap_or(0);
+        let build = match letter {
+            Some(c) => c.to_ascii_lowercase() as u16 - 'a' as u16 + 1,
+            None => part(3),
+        };
+
+        Ok(Self::new(part(0), part(1), part(2), build))
+    }
+}
+
+/// xEdit features a cleaning run may depend on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XEditFeature {
+    /// `-QAC` with the current three-pass cleaning
+    QuickAutoClean,
+    /// `-iknowwhatimdoing -allowmakepartial`
+    PartialForms,
+    /// Support for a game, from its [`GameDefinition::min_xedit_version`]
+    GameMode {
+        /// Display name of the game
+        game: String,
+        /// Oldest xEdit release that supports the game
+        min_version: XEditVersion,
+    },
+}
+
+impl XEditFeature {
+    /// Oldest xEdit release with the feature
+    pub const fn min_version(&self) -> XEditVersion {
+        match self {
+            XEditFeature::QuickAutoClean => XEditVersion::new(4, 0, 0, 0),
+            XEditFeature::PartialForms => XEditVersion::new(4, 1, 5, 2),
+            XEditFeature::GameMode { min_version, .. } => *min_version,
+        }
+    }
+
+    /// Whether cleaning must not start without the feature.
+    ///
+    /// Older Quick Auto Clean still works, just less thoroughly, so it only warns.
+    pub const fn is_blocking(&self) -> bool {
+        !matches!(self, XEditFeature::QuickAutoClean)
+    }
+
+    /// Game mode feature needed for a game, if its definition names a minimum xEdit
+    fn for_game(game: &GameDefinition) -> Option<Self> {
+        let version = game.min_xedit_version.as_deref()?;
+        match version.parse() {
+            Ok(min_version) => Some(XEditFeature::GameMode {
+                game: game.name.clone(),
+                min_version,
+            }),
+            Err(e) => {
+                tracing::warn!("Ignoring min_xedit_version of {}: {:#}", game.id, e);
+                None
+            }
+        }
+    }
+}
+
+impl fmt::Display for XEditFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XEditFeature::QuickAutoClean => write!(f, "Quick Auto Clean"),
+            XEditFeature::PartialForms => write!(f, "Partial forms"),
+            XEditFeature::GameMode { game, .. } => write!(f, "{} support", game),
+        }
+    }
+}
+
+/// A feature the selected xEdit is too old for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilityIssue {
+    pub feature: XEditFeature,
+    pub found: XEditVersion,
+}
+
+impl CapabilityIssue {
+    /// Whether this issue must stop cleaning (otherwise it is a warning)
+    pub fn is_blocking(&self) -> bool {
+        self.feature.is_blocking()
+    }
+}
+
+impl fmt::Display for CapabilityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} needs xEdit {} or newer, but the selected xEdit is {}",
+            self.feature,
+            self.feature.min_version(),
+            self.found
+        )
+    }
+}
+
+/// Features a cleaning run needs for a game and the enabled options
+pub fn required_features(
+    registry: &GameRegistry,
+    game: Option<&GameDefinition>,
+    partial_forms: bool,
+) -> Vec<XEditFeature> {
+    let mut features = vec![XEditFeature::QuickAutoClean];
+
+    if partial_forms {
+        features.push(XEditFeature::PartialForms);
+    }
+
+    // Total conversions need their base game's mode
+    if let Some(game) = game
+        && let Some(mode) = registry
+            .lineage(&game.id)
+            .iter()
+            .find_map(|g| XEditFeature::for_game(g))
+    {
+        features.push(mode);
+    }
+
+    features
+}
+
+/// Features the given xEdit version lacks, blocking issues first
+pub fn check_capabilities(
+    version: XEditVersion,
+    features: &[XEditFeature],
+) -> Vec<CapabilityIssue> {
+    let mut issues: Vec<CapabilityIssue> = features
+        .iter()
+        .filter(|feature| !version.supports(feature))
+        .map(|feature| CapabilityIssue {
+            feature: feature.clone(),
+            found: version,
+        })
+        .collect();
+
+    issues.sort_by_key(|issue| !issue.is_blocking());
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal executable bytes with a VS_FIXEDFILEINFO for the given version
+    fn exe_bytes(version: XEditVersion) -> Vec<u8> {
+        let mut bytes = b"MZ".to_vec();
+        bytes.extend_from_slice(&[0; 62]);
+        bytes.extend_from_slice(&FIXED_FILE_INFO_SIGNATURE);
+        bytes.extend_from_slice(&0x0001_0000u32.to_le_bytes());
+        for word in [version.minor, version.major, version.build, version.patch] {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_parse_version_strings() {
+        assert_eq!(
+            "4.1.5b".parse::<XEditVersion>().unwrap(),
+            XEditVersion::new(4, 1, 5, 2)
+        );
+        assert_eq!(
+            "4.1.5.2".parse::<XEditVersion>().unwrap(),
+            XEditVersion::new(4, 1, 5, 2)
+        );
+        assert_eq!(
+            "v4.0.4".parse::<XEditVersion>().unwrap(),
+            XEditVersion::new(4, 0, 4, 0)
+        );
+        assert!("4.x".parse::<XEditVersion>().is_err());
+
+        assert_eq!(XEditVersion::new(4, 1, 5, 6).to_string(), "4.1.5f");
+        assert_eq!(XEditVersion::new(4, 1, 4, 0).to_string(), "4.1.4");
+    }
+
+    #[test]
+    fn test_parse_executable_version_resource() {
+        let version = XEditVersion::new(4, 1, 5, 6);
+        assert_eq!(
+            XEditVersion::parse_executable(&exe_bytes(version)).unwrap(),
+            version
+        );
+        assert!(XEditVersion::parse_executable(b"MZ without resources").is_err());
+        assert!(XEditVersion::parse_executable(b"ELF").is_err());
+    }
+
+    #[test]
+    fn test_capability_checks() {
+        let registry = GameRegistry::builtin();
+        let starfield = registry.get("Starfield");
+        let starfield_mode = XEditFeature::GameMode {
+            game: "Starfield".to_string(),
+            min_version: XEditVersion::new(4, 1, 5, 0),
+        };
+        let features = required_features(&registry, starfield, true);
+        assert_eq!(
+            features,
+            vec![
+                XEditFeature::QuickAutoClean,
+                XEditFeature::PartialForms,
+                starfield_mode.clone()
+            ]
+        );
+
+        let issues = check_capabilities(XEditVersion::new(4, 1, 4, 0), &features);
+        let blocked: Vec<_> = issues.iter().map(|issue| issue.feature.clone()).collect();
+        assert_eq!(blocked, vec![XEditFeature::PartialForms, starfield_mode]);
+        assert_eq!(
+            issues[1].to_string(),
+            "Starfield support needs xEdit 4.1.5 or newer, but the selected xEdit is 4.1.4"
+        );
+
+        // VR games need nothing beyond Quick Auto Clean
+        assert_eq!(
+            required_features(&registry, registry.get("SkyrimVR"), false),
+            vec![XEditFeature::QuickAutoClean]
+        );
+
+        // 3.x only warns about Quick Auto Clean for games without a special mode
+        let issues = check_capabilities(
+            XEditVersion::new(3, 3, 7, 0),
+            &required_features(&registry, registry.get("FO4"), false),
+        );
+        assert_eq!(issues.len(), 1);
+        assert!(!issues[0].is_blocking());
+
+        assert!(check_capabilities(XEditVersion::new(4, 1, 5, 6), &features).is_empty());
+    }
+}
@@ -3,8 +3,15 @@
 // This module provides the StateManager which wraps AppState with thread-safe access
 // using Arc<RwLock<T>> and emits change events for GUI updates.
 
-use crate::models::AppState;
-use camino::Utf8PathBuf;
+use crate::models::{
+    AppState, GameDefinition, GameRegistry, IgnoreConfig, MainConfig, PluginOutcome,
+    SessionCheckpoint,
+};
+use crate::services::{
+    CleaningPlan, CommandOptions, LootMasterlist, PlanSources, RetryPolicy, Runner, read_load_order,
+};
+use anyhow::Result;
+use camino::{Utf8Path, Utf8PathBuf};
 use std::sync::{Arc, PoisonError, RwLock};
 use std::time::Duration;
 use tokio::sync::broadcast;
@@ -26,7 +33,9 @@ struct ChangeSnapshot {
     journal_expiration: u32,
     cleaning_timeout: Duration,
     cpu_threshold: u32,
+    hang_window: Duration,
     mo2_mode: bool,
+    vortex_mode: bool,
     partial_forms_enabled: bool,
 }
 
@@ -44,7 +53,9 @@ impl From<&AppState> for ChangeSnapshot {
             journal_expiration: state.journal_expiration,
             cleaning_timeout: state.cleaning_timeout,
             cpu_threshold: state.cpu_threshold,
+            hang_window: state.hang_window,
             mo2_mode: state.mo2_mode,
+            vortex_mode: state.vortex_mode,
             partial_forms_enabled: state.partial_forms_enabled,
         }
     }
@@ -279,7 +290,9 @@ impl StateManager {
         if old.journal_expiration != new.journal_expiration
             || old.cleaning_timeout != new.cleaning_timeout
             || old.cpu_threshold != new.cpu_threshold
+            || old.hang_window != new.hang_window
             || old.mo2_mode != new.mo2_mode
+            || old.vortex_mode != new.vortex_mode
             || old.partial_forms_enabled != new.partial_forms_enabled
         {
             changes.push(StateChange::SettingsChanged);
@@ -326,6 +339,13 @@ impl StateManager {
         })
     }
 
+    /// Set the game Data folder path (used for Vortex deployment manifests)
+    pub fn set_data_path(&self, path: Option<Utf8PathBuf>) -> Vec<StateChange> {
+        self.update(|state| {
+            state.data_path = path;
+        })
+    }
+
     /// Start a cleaning operation
     pub fn start_cleaning(&self, plugins: Vec<String>) -> Vec<StateChange> {
         self.update(|state| {
@@ -338,9 +358,20 @@ impl StateManager {
             state.cleaned_plugins.clear();
             state.failed_plugins.clear();
             state.skipped_plugins.clear();
+            state.plugin_sources.clear();
+            state.plugin_passes.clear();
+            state.navmesh_reports.clear();
+            state.needs_attention.clear();
         })
     }
 
+    /// Resume an interrupted cleaning operation with the results it already has
+    pub fn resume_cleaning(&self, checkpoint: &SessionCheckpoint) -> Vec<StateChange> {
+        let mut changes = self.start_cleaning(checkpoint.queue.clone());
+        changes.extend(self.update(|state| state.restore_results(checkpoint)));
+        changes
+    }
+
     /// Stop the cleaning operation
     pub fn stop_cleaning(&self) -> Vec<StateChange> {
         self.update(|state| {
@@ -375,20 +406,25 @@ impl StateManager {
         let mut changes = self.update(|state| {
             state.add_result(plugin.clone(), status);
 
-            // Update statistics if provided
+            // Update current statistics and aggregate into totals if provided
             if let Some(ref cleaning_stats) = stats {
-                // Update current statistics from CleaningStats
-                state.current_undeleted = cleaning_stats.undeleted;
-                state.current_removed = cleaning_stats.removed;
-                state.current_skipped = cleaning_stats.skipped;
-                state.current_partial_forms = cleaning_stats.partial_forms;
-                state.current_total_processed = cleaning_stats.undeleted
-                    + cleaning_stats.removed
-                    + cleaning_stats.skipped
-                    + cleaning_stats.partial_forms;
-
-                // Aggregate into totals
-                state.aggregate_current_stats();
+                state.record_stats(cleaning_stats);
+            }
+
+            // Keep the outcome for resuming the run if it is interrupted
+            let needs_attention = state.needs_attention.contains(&plugin);
+            let source = state.plugin_source(&plugin).map(str::to_string);
+            if let Some(ref mut checkpoint) = state.checkpoint {
+                checkpoint.record(
+                    &plugin,
+                    PluginOutcome {
+                        status: status.to_string(),
+                        message: message.clone(),
+                        stats: stats.clone(),
+                        needs_attention,
+                        source,
+                    },
+                );
             }
         });
 
@@ -471,25 +507,47 @@ impl StateManager {
         self.update(|state| {
             let settings = &user_config.pact_settings;
 
+            // Under Wine/Proton, Windows paths in the config point into the prefix
+            let runner = Runner::from_settings(settings);
+
             // Load path configurations
             if !settings.loadorder_txt.is_empty() {
-                state.load_order_path = Some(Utf8PathBuf::from(&settings.loadorder_txt));
+                state.load_order_path = Some(runner.host_path(&settings.loadorder_txt));
                 state.is_load_order_configured = true;
             }
 
             if !settings.xedit_exe.is_empty() {
-                state.xedit_exe_path = Some(Utf8PathBuf::from(&settings.xedit_exe));
+                state.xedit_exe_path = Some(runner.host_path(&settings.xedit_exe));
                 state.is_xedit_configured = true;
             }
 
             if !settings.mo2_exe.is_empty() {
-                state.mo2_exe_path = Some(Utf8PathBuf::from(&settings.mo2_exe));
+                state.mo2_exe_path = Some(runner.host_path(&settings.mo2_exe));
                 state.is_mo2_configured = true;
             }
 
+            if !settings.game_data_path.is_empty() {
+                state.data_path = Some(runner.host_path(&settings.game_data_path));
+            }
+
+            state.loot_masterlist = (!settings.loot_masterlist.is_empty())
+                .then(|| runner.host_path(&settings.loot_masterlist));
+
+            state.runner = runner;
+            state.command_options = CommandOptions::from_settings(settings);
+            state.retry_policy = RetryPolicy::from_settings(settings);
+
             // Load settings
+            state.vortex_mode = settings.vortex_mode;
             state.partial_forms_enabled = settings.partial_forms;
+            state.flag_navmeshes = settings.flag_navmeshes;
+            state.skip_unchanged = settings.skip_unchanged;
+            state.skip_loot_clean = settings.skip_loot_clean;
             state.cleaning_timeout = Duration::from_secs(settings.cleaning_timeout as u64);
+            state.adaptive_timeout = settings.adaptive_timeout;
+            state.cleaning_passes = settings.cleaning_passes.max(1);
+            state.cpu_threshold = settings.cpu_threshold;
+            state.hang_window = Duration::from_secs(u64::from(settings.hang_window));
             state.journal_expiration = settings.journal_expiration;
 
             tracing::info!(
@@ -503,6 +561,85 @@ impl StateManager {
         })
     }
 
+    /// Decide which plugins of a load order will be cleaned with the current settings
+    ///
+    /// Reads the plugins from the Data folder, compares them with the cleaning history
+    /// (with `Skip Unchanged`) and with the LOOT masterlist (with `Skip LOOT Clean`).
+    /// Neither comparison is made in MO2 mode, where xEdit cleans the copy in a mod folder
+    /// rather than the one whose checksum the Data folder holds.
+    ///
+    /// # Errors
+    /// Returns an error if the load order file cannot be read
+    pub fn cleaning_plan(
+        &self,
+        registry: &GameRegistry,
+        main_config: &MainConfig,
+        ignore_config: &IgnoreConfig,
+        game: Option<&GameDefinition>,
+        load_order_path: &Utf8Path,
+    ) -> Result<CleaningPlan> {
+        let load_order = read_load_order(load_order_path)?;
+        let (data_path, history, loot_path) = self.read(|s| {
+            let loot_path = if s.skip_loot_clean && !s.mo2_mode {
+                s.loot_masterlist.clone().or_else(|| {
+                    game.and_then(|game| LootMasterlist::find(registry, &s.runner, &game.id))
+                })
+            } else {
+                None
+            };
+            (
+                s.data_path.clone(),
+                (s.skip_unchanged && !s.mo2_mode).then(|| s.cleaning_history.clone()),
+                loot_path,
+            )
+        });
+
+        let loot = loot_path.and_then(|path| match LootMasterlist::load(&path) {
+            Ok(masterlist) => {
+                tracing::info!("Using LOOT masterlist: {}", path);
+                Some(masterlist)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load LOOT masterlist: {:#}", e);
+                None
+            }
+        });
+
+        let sources = PlanSources {
+            registry,
+            main_config,
+            ignore_config,
+            game,
+            data_path: data_path.as_deref(),
+            history: history.as_ref(),
+            loot: loot.as_ref(),
+        };
+        Ok(CleaningPlan::build(&load_order, &sources))
+    }
+
+    /// Decide whether a single plugin, cleaned on its own without the load order, will be
+    /// cleaned; see [`CleaningPlan::single`]
+    pub fn single_plugin_plan(
+        &self,
+        registry: &GameRegistry,
+        main_config: &MainConfig,
+        ignore_config: &IgnoreConfig,
+        game: Option<&GameDefinition>,
+        plugin: &str,
+    ) -> CleaningPlan {
+        let data_path = self.read(|s| s.data_path.clone());
+        let sources = PlanSources {
+            registry,
+            main_config,
+            ignore_config,
+            game,
+            data_path: data_path.as_deref(),
+            history: None,
+            loot: None,
+        };
+        CleaningPlan::single(plugin, &sources)
+    }
+
     /// Get an Arc reference to the state for use in worker threads
     ///
     /// Use this when you need to share state across threads but want
@@ -13,6 +13,9 @@ use slint::{ComponentHandle, Weak};
 use std::future::Future;
 use tokio::sync::mpsc;
 
+/// A boxed UI update closure queued for execution on the Slint event loop
+type UiUpdate<T> = Box<dyn FnOnce(&T) + Send>;
+
 /// Coordinates between tokio async runtime and Slint event loop
 ///
 /// This bridge enables:
@@ -45,7 +48,7 @@ pub struct EventLoopBridge<T: ComponentHandle> {
 
     /// Channel for sending UI update requests from tokio tasks to the Slint event loop
     /// Bounded to 100 updates to prevent unbounded memory growth if UI lags
-    ui_update_tx: mpsc::Sender<Box<dyn FnOnce(&T) + Send>>,
+    ui_update_tx: mpsc::Sender<UiUpdate<T>>,
 }
 
 impl<T: ComponentHandle + 'static> EventLoopBridge<T> {
@@ -63,7 +66,7 @@ impl<T: ComponentHandle + 'static> EventLoopBridge<T> {
     pub fn new(ui: &T, tokio_handle: tokio::runtime::Handle) -> Self {
         let ui_weak = ui.as_weak();
         // Use bounded channel with capacity 100 to prevent OOM if UI lags
-        let (ui_update_tx, mut ui_update_rx) = mpsc::channel::<Box<dyn FnOnce(&T) + Send>>(100);
+        let (ui_update_tx, mut ui_update_rx) = mpsc::channel::<UiUpdate<T>>(100);
 
         // Spawn a background thread to handle UI updates
         // This thread bridges between tokio tasks and the Slint event loop
@@ -177,7 +180,7 @@ impl<T: ComponentHandle + 'static> EventLoopBridge<T> {
 pub struct EventLoopBridgeHandle<T: ComponentHandle> {
     ui_weak: Weak<T>,
     tokio_handle: tokio::runtime::Handle,
-    ui_update_tx: mpsc::Sender<Box<dyn FnOnce(&T) + Send>>,
+    ui_update_tx: mpsc::Sender<UiUpdate<T>>,
 }
 
 // Manual Clone implementation to avoid requiring T: Clone
@@ -234,7 +237,6 @@ impl<T: ComponentHandle + 'static> EventLoopBridgeHandle<T> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
     use std::sync::Arc;
     use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
     use std::time::Duration;
@@ -245,7 +247,7 @@ mod tests {
     #[test]
     fn test_bridge_handle_clone() {
         // Test that the handle is cloneable
-        let rt = tokio::runtime::Runtime::new().unwrap();
+        let _rt = tokio::runtime::Runtime::new().unwrap();
 
         // We can't create a real Slint component in unit tests without a display,
         // but we can test that the handle type implements Clone
@@ -13,20 +13,54 @@
 // - Cleaning orchestration
 
 use crate::config::ConfigManager;
-use crate::models::{MAX_CONCURRENT_XEDIT_PROCESSES, MainConfig};
-use crate::services::cleaning::{CleaningService, CleaningStats};
-use crate::services::game_detection::detect_xedit_game;
+use crate::i18n;
+use crate::models::{
+    AppState, CleaningHistory, GameDefinition, GameRegistry, IgnoreConfig,
+    MAX_CONCURRENT_XEDIT_PROCESSES, MainConfig, PluginFilter, SessionCheckpoint,
+};
+use crate::services::cleaning::{CleaningError, CleaningService, CleaningStats};
+use crate::services::command::{CommandError, CommandValues};
+use crate::services::discovery::{DiscoverySources, SetupCandidate, discover};
+use crate::services::game_detection::{detect_game, detect_xedit_game_with, find_load_order};
+use crate::services::hang::HangDetector;
+use crate::services::instance::{
+    InstanceLock, PROGRAM_POLL_INTERVAL, blocking_executables, running_programs,
+};
+use crate::services::loot::plugin_crc;
+use crate::services::messages::{Message, MessageCatalog, MessageId};
+use crate::services::navmesh::NavmeshReport;
+use crate::services::plan::{CleaningPlan, Exclusion, plugin_name, read_load_order};
+use crate::services::plugin_header::PluginHeader;
+use crate::services::retry::{AttemptFailure, describe_attempts};
+use crate::services::session::{CleaningSession, ExceptionLogWatch, PluginLogs};
+use crate::services::timeout::{PluginMetrics, plugin_timeout};
+use crate::services::validation::{
+    ConfigPaths, Diagnostic, validate_command, validate_configuration,
+};
+use crate::services::vortex::VortexDeployment;
+use crate::services::xedit_version::{
+    CapabilityIssue, XEditVersion, check_capabilities, required_features,
+};
 use crate::state::{StateChange, StateManager};
 use crate::ui::bridge::EventLoopBridge;
 use anyhow::{Context, Result, anyhow};
 use camino::{Utf8Path, Utf8PathBuf};
-use std::fs;
+use slint::winit_030::winit::event::WindowEvent;
+use slint::winit_030::{EventResult, WinitWindowAccessor};
+use slint::{ComponentHandle, ModelRc, VecModel};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{Semaphore, watch};
 
 // Include the generated Slint code
 slint::include_modules!();
 
+/// Plugin name used for the command preview
+const PREVIEW_PLUGIN: &str = "Example Plugin.esp";
+
+/// Result of cleaning one plugin: (status, message, stats)
+type PluginResult = (String, String, Option<CleaningStats>);
+
 /// GUI Controller that wires up the Slint UI with application state and logic
 ///
 /// This is the main coordinator for the GUI layer. It:
@@ -38,7 +72,8 @@ slint::include_modules!();
 /// # Example
 /// ```ignore
 /// let state_manager = Arc::new(StateManager::new());
-/// let config_manager = Arc::new(ConfigManager::new("AutoQAC Data")?);
+/// let paths = AppPaths::beside_executable()?;
+/// let config_manager = Arc::new(ConfigManager::new(&paths.config)?);
 /// let main_config = Arc::new(config_manager.load_main_config()?);
 /// let runtime = tokio::runtime::Runtime::new()?;
 ///
@@ -90,6 +125,7 @@ impl GuiController {
     ) -> Result<Self> {
         // Create the Slint UI
         let ui = MainWindow::new().context("Failed to create Slint UI")?;
+        Self::apply_language(&ui, i18n::language());
 
         // Create the event loop bridge
         let bridge = EventLoopBridge::new(&ui, tokio_handle);
@@ -97,15 +133,53 @@ impl GuiController {
         // Create cancellation channel for graceful shutdown
         let (cancel_tx, cancel_rx) = watch::channel(false);
 
+        // Built-in game definitions layered with any from the main config
+        let registry = Arc::new(GameRegistry::from_main_config(&main_config));
+
+        // First run: fill in the setup from discovered installs before the UI shows it
+        let is_unconfigured =
+            state_manager.read(|s| !s.is_load_order_configured && !s.is_xedit_configured);
+        if is_unconfigured && let Some(candidate) = Self::apply_discovery(&registry, &state_manager)
+        {
+            tracing::info!("First-run setup detected {}", candidate.game_id);
+        }
+
         // Initialize UI with current state
         Self::sync_ui_with_state(&ui, &state_manager);
+        Self::show_diagnostics(&ui.as_weak(), &registry, &main_config, &state_manager);
 
         // Set up Slint callbacks with cancellation receiver
-        Self::setup_callbacks(&ui, &bridge, &state_manager, &main_config, cancel_rx);
+        Self::setup_callbacks(
+            &ui,
+            &bridge,
+            &state_manager,
+            &config_manager,
+            &main_config,
+            &registry,
+            cancel_rx,
+        );
 
         // Subscribe to state changes and update UI
         Self::setup_state_subscription(&bridge, &state_manager);
 
+        // A plugin dropped on the window is cleaned on its own
+        Self::setup_file_drop(&ui, &state_manager, &main_config);
+
+        // Offer to resume a run that was interrupted, unless a single plugin is cleaned first
+        if state_manager.read(|s| s.single_plugin.is_none()) {
+            Self::offer_resume(&ui, &config_manager);
+        }
+
+        // A plugin given on the command line is cleaned as soon as the window shows
+        if state_manager.read(|s| s.single_plugin.is_some()) {
+            let ui_weak = ui.as_weak();
+            slint::Timer::single_shot(Duration::ZERO, move || {
+                if let Some(ui) = ui_weak.upgrade() {
+                    ui.invoke_start_cleaning();
+                }
+            });
+        }
+
         tracing::info!("GUI controller initialized");
 
         Ok(Self {
@@ -137,6 +211,25 @@ impl GuiController {
         self.state_manager.stop_cleaning();
     }
 
+    /// Select the bundled Slint translation and the language picker entry for a language
+    fn apply_language(ui: &MainWindow, language: &str) {
+        if let Err(e) = slint::select_bundled_translation(language) {
+            tracing::warn!("Failed to select {} translation: {}", language, e);
+        }
+
+        let names: Vec<slint::SharedString> = i18n::LANGUAGES
+            .iter()
+            .map(|(_, name)| (*name).into())
+            .collect();
+        ui.set_language_names(ModelRc::new(VecModel::from(names)));
+
+        let index = i18n::LANGUAGES
+            .iter()
+            .position(|(code, _)| *code == language)
+            .unwrap_or(0);
+        ui.set_language_index(index as i32);
+    }
+
     /// Synchronize UI with current state
     ///
     /// This is called once at startup to initialize the UI with the current state.
@@ -168,6 +261,14 @@ impl GuiController {
                 .unwrap_or_default()
                 .into(),
         );
+        ui.set_data_path(
+            state
+                .data_path
+                .as_ref()
+                .map(|p| p.as_str().to_string())
+                .unwrap_or_default()
+                .into(),
+        );
 
         // Set runtime state
         ui.set_is_cleaning(state.is_cleaning);
@@ -178,12 +279,16 @@ impl GuiController {
 
         // Set settings
         ui.set_mo2_mode(state.mo2_mode);
+        ui.set_vortex_mode(state.vortex_mode);
         ui.set_partial_forms_enabled(state.partial_forms_enabled);
 
         // Set results
         ui.set_cleaned_count(state.cleaned_plugins.len() as i32);
         ui.set_failed_count(state.failed_plugins.len() as i32);
         ui.set_skipped_count(state.skipped_plugins.len() as i32);
+        ui.set_attention_count(state.needs_attention.len() as i32);
+        ui.set_attention_plugins(state.attention_plugins().join(", ").into());
+        Self::show_plugins(ui, &state);
 
         // Set current plugin statistics
         ui.set_current_undeleted(state.current_undeleted as i32);
@@ -221,13 +326,20 @@ impl GuiController {
                 .map(|p| p.exists())
                 .unwrap_or(false),
         );
+        ui.set_data_path_valid(
+            state
+                .data_path
+                .as_ref()
+                .map(|p| p.is_dir())
+                .unwrap_or(false),
+        );
 
         // Set detected game type
         ui.set_detected_game_type(state.game_type.clone().unwrap_or_default().into());
 
         // Set configuration status and count plugins if load order is configured
         let plugin_count = if let Some(ref lo_path) = state.load_order_path {
-            Self::load_plugins_from_file(lo_path)
+            read_load_order(lo_path)
                 .ok()
                 .map(|plugins| plugins.len())
                 .unwrap_or(0)
@@ -251,12 +363,16 @@ impl GuiController {
         ui: &MainWindow,
         bridge: &EventLoopBridge<MainWindow>,
         state_manager: &Arc<StateManager>,
+        config_manager: &Arc<ConfigManager>,
         main_config: &Arc<MainConfig>,
+        registry: &Arc<GameRegistry>,
         cancel_rx: watch::Receiver<bool>,
     ) {
         let bridge_handle = bridge.clone_handle();
         let state_manager_clone = Arc::clone(state_manager);
+        let config_manager_clone = Arc::clone(config_manager);
         let main_config_clone = Arc::clone(main_config);
+        let registry_clone = Arc::clone(registry);
         let cancel_rx_clone = cancel_rx.clone();
         let ui_weak_for_start = ui.as_weak();
 
@@ -264,59 +380,137 @@ impl GuiController {
         ui.on_start_cleaning(move || {
             tracing::info!("Start cleaning button clicked");
 
+            // A single plugin or interrupted run that cannot start now is not started by the
+            // next click either
+            let cancel_request = || {
+                state_manager_clone.update(|s| {
+                    s.single_plugin = None;
+                    s.checkpoint = None;
+                })
+            };
+
             // Validate configuration
-            if !state_manager_clone.read(|s| s.is_fully_configured()) {
+            if !state_manager_clone.read(|s| s.is_ready_to_clean()) {
                 tracing::error!("Cannot start cleaning: configuration incomplete");
 
                 // Show error dialog
                 let missing = state_manager_clone.read(|s| {
                     let mut items = Vec::new();
-                    if s.load_order_path.is_none() {
-                        items.push("Load Order file");
+                    if s.load_order_path.is_none() && !s.needs_no_load_order() {
+                        items.push(i18n::tr("GuiController", "Load Order file"));
                     }
                     if s.xedit_exe_path.is_none() {
-                        items.push("xEdit executable");
+                        items.push(i18n::tr("GuiController", "xEdit executable"));
                     }
                     if s.mo2_mode && s.mo2_exe_path.is_none() {
-                        items.push("Mod Organizer 2 executable");
+                        items.push(i18n::tr("GuiController", "Mod Organizer 2 executable"));
                     }
                     items.join(", ")
                 });
 
-                Self::show_error_dialog(
-                    &ui_weak_for_start,
-                    "Configuration Incomplete",
-                    format!(
-                        "Please configure the following before starting:\n\n{}",
-                        missing
-                    ),
-                    "",
-                );
+                let message = MessageCatalog::new(&main_config_clone)
+                    .format(MessageId::ConfigIncomplete, &[("missing", &missing)]);
+                Self::show_error_message(&ui_weak_for_start, &message, "");
+                cancel_request();
+                return;
+            }
+
+            // Vortex mode needs the game Data folder to find the deployment manifest
+            if state_manager_clone.read(|s| s.vortex_mode && s.data_path.is_none()) {
+                tracing::error!("Cannot start cleaning: Vortex mode requires the game Data folder");
+
+                let message =
+                    MessageCatalog::new(&main_config_clone).get(MessageId::DataFolderNotConfigured);
+                Self::show_error_message(&ui_weak_for_start, &message, "");
+                cancel_request();
+                return;
+            }
+
+            // xEdit, load order and Data folder must belong to the same game
+            let diagnostics = Self::show_diagnostics(
+                &ui_weak_for_start,
+                &registry_clone,
+                &main_config_clone,
+                &state_manager_clone,
+            );
+            if let Some(error) = diagnostics.iter().find(|d| d.is_error()) {
+                tracing::error!("Cannot start cleaning: {}", error);
+
+                Self::show_error_message(&ui_weak_for_start, &error.message, error.detail.clone());
+                cancel_request();
+                return;
+            }
+
+            // The selected xEdit must support the game mode and enabled options
+            let issues = Self::check_xedit_capabilities(&registry_clone, &state_manager_clone);
+            if issues.iter().any(CapabilityIssue::is_blocking) {
+                tracing::error!("Cannot start cleaning: selected xEdit is too old");
+
+                let details: Vec<String> = issues.iter().map(ToString::to_string).collect();
+                let message =
+                    MessageCatalog::new(&main_config_clone).get(MessageId::XEditUpdateRequired);
+                Self::show_error_message(&ui_weak_for_start, &message, details.join("\n"));
+                cancel_request();
                 return;
             }
+            if !issues.is_empty() {
+                let details = issues
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let message = MessageCatalog::new(&main_config_clone)
+                    .format(MessageId::OldXEditVersion, &[("issues", &details)]);
+                Self::show_warning_message(&ui_weak_for_start, &message);
+            }
 
             // Clone for async task
             let bridge = bridge_handle.clone();
             let bridge_clone = bridge.clone();
             let state = Arc::clone(&state_manager_clone);
             let config = Arc::clone(&main_config_clone);
+            let registry = Arc::clone(&registry_clone);
             let cancel = cancel_rx_clone.clone();
             let ui_weak = ui_weak_for_start.clone();
+            let config_manager = Arc::clone(&config_manager_clone);
+
+            // Reload the ignore list so edits to PACT Ignore.yaml apply to this run
+            let ignore_config = config_manager_clone
+                .load_ignore_config()
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load ignore config, using defaults: {}", e);
+                    IgnoreConfig::default()
+                });
 
             // Spawn async cleaning workflow with cancellation support
             bridge.spawn_async(move || async move {
-                if let Err(e) =
-                    Self::run_cleaning_workflow(state, config, bridge_clone, cancel).await
-                {
+                let result = Self::run_cleaning_workflow(
+                    Arc::clone(&state),
+                    config_manager,
+                    Arc::clone(&config),
+                    registry,
+                    ignore_config,
+                    bridge_clone,
+                    cancel,
+                )
+                .await;
+
+                // The next run cleans the load order again
+                state.update(|s| {
+                    s.single_plugin = None;
+                    s.checkpoint = None;
+                });
+
+                if let Err(e) = result {
                     tracing::error!("Cleaning workflow error: {}", e);
 
-                    // Show error dialog
-                    Self::show_error_dialog(
-                        &ui_weak,
-                        "Cleaning Failed",
-                        "An error occurred during the cleaning process.",
-                        format!("{:?}", e),
-                    );
+                    // Show error dialog, with the catalog text for known cleaning errors
+                    let catalog = MessageCatalog::new(&config);
+                    let message = match e.downcast_ref::<CleaningError>() {
+                        Some(error) => error.to_message(&catalog),
+                        None => catalog.get(MessageId::CleaningFailed),
+                    };
+                    Self::show_error_message(&ui_weak, &message, format!("{:?}", e));
                 }
             });
         });
@@ -337,8 +531,182 @@ impl GuiController {
             );
         });
 
-        let _config_manager_clone = Arc::clone(main_config);
+        let preview_bridge = bridge.clone_handle();
+        let state = state_manager.clone();
+        let config_manager_clone = Arc::clone(config_manager);
+        let main_config_clone = Arc::clone(main_config);
+        let registry_clone = Arc::clone(registry);
+
+        // Plugins callback - list the plugins the next run cleans, without running xEdit
+        ui.on_preview_plan(move || {
+            tracing::info!("Plugins button clicked");
+
+            let state = Arc::clone(&state);
+            let config_manager = Arc::clone(&config_manager_clone);
+            let main_config = Arc::clone(&main_config_clone);
+            let registry = Arc::clone(&registry_clone);
+            let bridge = preview_bridge.clone();
+
+            preview_bridge.spawn_async(move || async move {
+                let ignore_config = config_manager.load_ignore_config().unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load ignore config, using defaults: {}", e);
+                    IgnoreConfig::default()
+                });
+
+                match Self::plan_cleaning(
+                    &registry,
+                    &state,
+                    &main_config,
+                    &ignore_config,
+                    &config_manager,
+                ) {
+                    Ok((_, plan)) => {
+                        state.update(|s| {
+                            s.plugin_queue.sync(&plan);
+                            s.cleaning_plan = plan;
+                        });
+                        bridge.update_ui(move |ui| {
+                            state.read(|s| Self::show_plugins(ui, s));
+                            ui.set_show_plan(true);
+                        });
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to build the cleaning plan: {:#}", e);
+
+                        let catalog = MessageCatalog::new(&main_config);
+                        let message = match e.downcast_ref::<CleaningError>() {
+                            Some(error) => error.to_message(&catalog),
+                            None => catalog.get(MessageId::CleaningFailed),
+                        };
+                        bridge.update_ui(move |ui| {
+                            Self::show_error_message(&ui.as_weak(), &message, format!("{:?}", e));
+                        });
+                    }
+                }
+            });
+        });
+
+        let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+
+        // Plugin checkbox callback - include or leave out one plugin
+        ui.on_plugin_toggled(move |plugin, selected| {
+            state.update(|s| s.plugin_queue.set_selected(&plugin, selected));
+            if let Some(ui) = ui_weak.upgrade() {
+                state.read(|s| Self::show_plugins(&ui, s));
+            }
+        });
+
+        let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+
+        // Select All/None callback - applies to the plugins the filters show
+        ui.on_select_plugins(move |selected| {
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+            let filter = PluginFilter::from_index(ui.get_plugin_status_filter());
+            let name = ui.get_plugin_filter();
+
+            state.update(|s| {
+                let visible: Vec<String> = s
+                    .plugin_list(&name, filter)
+                    .iter()
+                    .filter(|item| item.exclusion.is_none())
+                    .map(|item| item.plugin.to_string())
+                    .collect();
+                for plugin in &visible {
+                    s.plugin_queue.set_selected(plugin, selected);
+                }
+            });
+            state.read(|s| Self::show_plugins(&ui, s));
+        });
+
+        let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+
+        // Drag callback - move a plugin by a number of rows of the filtered list
+        ui.on_plugin_moved(move |plugin, offset| {
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+            if offset == 0 {
+                return;
+            }
+            let filter = PluginFilter::from_index(ui.get_plugin_status_filter());
+            let name = ui.get_plugin_filter();
+
+            state.update(|s| {
+                let visible: Vec<String> = s
+                    .plugin_list(&name, filter)
+                    .iter()
+                    .filter(|item| item.exclusion.is_none())
+                    .map(|item| item.plugin.to_string())
+                    .collect();
+                let Some(from) = visible.iter().position(|p| p.as_str() == plugin.as_str()) else {
+                    return;
+                };
+                let target = from
+                    .saturating_add_signed(offset as isize)
+                    .min(visible.len() - 1);
+                s.plugin_queue.move_to(&plugin, &visible[target]);
+            });
+            state.read(|s| Self::show_plugins(&ui, s));
+        });
+
+        let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+
+        // Filter callback - the name or status filter of the plugin list changed
+        ui.on_plugin_filter_changed(move || {
+            if let Some(ui) = ui_weak.upgrade() {
+                state.read(|s| Self::show_plugins(&ui, s));
+            }
+        });
+
+        let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+        let config_manager_clone = Arc::clone(config_manager);
+
+        // Resume callback - go on with an interrupted run, with the settings it started with
+        ui.on_resume_session(move || {
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+            ui.set_show_resume_dialog(false);
+
+            match config_manager_clone.load_checkpoint() {
+                Ok(Some(checkpoint)) => {
+                    state.update(|s| {
+                        s.apply_checkpoint_settings(&checkpoint.settings);
+                        s.checkpoint = Some(checkpoint);
+                    });
+                    Self::sync_ui_with_state(&ui, &state);
+                    ui.invoke_start_cleaning();
+                }
+                Ok(None) => tracing::warn!("Session checkpoint is gone, nothing to resume"),
+                Err(e) => tracing::error!("Failed to load session checkpoint: {:#}", e),
+            }
+        });
+
+        let ui_weak = ui.as_weak();
+        let config_manager_clone = Arc::clone(config_manager);
+
+        // Discard callback - forget the interrupted run
+        ui.on_discard_session(move || {
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.set_show_resume_dialog(false);
+            }
+            tracing::info!("Interrupted session discarded");
+            if let Err(e) = config_manager_clone.clear_checkpoint() {
+                tracing::warn!("Failed to remove session checkpoint: {}", e);
+            }
+        });
+
         let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+        let main_config_clone = Arc::clone(main_config);
+        let registry_clone = Arc::clone(registry);
 
         // Refresh configuration callback
         ui.on_refresh_configuration(move || {
@@ -349,62 +717,86 @@ impl GuiController {
             let (xedit_path, load_order_path) =
                 state.read(|s| (s.xedit_exe_path.clone(), s.load_order_path.clone()));
 
-            if let (Some(xedit), Some(lo_path)) = (xedit_path, load_order_path) {
-                if let Some(detected_game) = detect_xedit_game(xedit.as_str(), Some(&lo_path)) {
-                    tracing::info!("Re-detected game type on refresh: {}", detected_game);
-                    state.update(|s| {
-                        s.game_type = Some(detected_game);
-                    });
-                }
+            // Fall back to the game's plugins.txt in %LOCALAPPDATA% (or the Wine prefix)
+            let load_order_path = load_order_path.or_else(|| {
+                let game = xedit_path.as_ref().and_then(|xedit| {
+                    detect_xedit_game_with(&registry_clone, xedit.as_str(), None)
+                });
+                Self::discover_load_order(&registry_clone, &state, game.as_deref())
+            });
+
+            if let (Some(xedit), Some(lo_path)) = (xedit_path, load_order_path)
+                && let Some(detected_game) =
+                    Self::detect_game_type(&registry_clone, &state, xedit.as_str(), Some(&lo_path))
+            {
+                tracing::info!("Re-detected game type on refresh: {}", detected_game);
+                state.update(|s| {
+                    s.game_type = Some(detected_game);
+                });
             }
 
+            Self::show_diagnostics(&ui_weak, &registry_clone, &main_config_clone, &state);
             tracing::info!("Configuration refreshed");
         });
 
         let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+        let main_config_clone = Arc::clone(main_config);
+        let registry_clone = Arc::clone(registry);
 
         // Browse load order callback
         ui.on_browse_load_order(move || {
             tracing::debug!("Browse load order clicked");
 
-            if let Some(path) =
-                Self::show_file_picker("Select Load Order File", vec![("Text files", &["txt"])])
-            {
+            if let Some(path) = Self::show_file_picker(
+                &i18n::tr("GuiController", "Select Load Order File"),
+                vec![(i18n::tr("GuiController", "Text files").as_str(), &["txt"])],
+            ) {
                 tracing::info!("Load order path selected: {}", path);
                 state.set_load_order_path(Some(path.clone()));
 
                 // Auto-detect game type if xEdit is already configured
                 let xedit_path = state.read(|s| s.xedit_exe_path.clone());
-                if let Some(xedit) = xedit_path {
-                    if let Some(detected_game) = detect_xedit_game(xedit.as_str(), Some(&path)) {
-                        tracing::info!(
-                            "Auto-detected game type from load order: {}",
-                            detected_game
-                        );
-                        state.update(|s| {
-                            s.game_type = Some(detected_game);
-                        });
-                    }
+                if let Some(xedit) = xedit_path
+                    && let Some(detected_game) =
+                        Self::detect_game_type(&registry_clone, &state, xedit.as_str(), Some(&path))
+                {
+                    tracing::info!("Auto-detected game type from load order: {}", detected_game);
+                    state.update(|s| {
+                        s.game_type = Some(detected_game);
+                    });
                 }
+
+                Self::show_diagnostics(&ui_weak, &registry_clone, &main_config_clone, &state);
             }
         });
 
         let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+        let main_config_clone = Arc::clone(main_config);
+        let registry_clone = Arc::clone(registry);
 
         // Browse xEdit callback
         ui.on_browse_xedit(move || {
             tracing::debug!("Browse xEdit clicked");
 
-            if let Some(path) =
-                Self::show_file_picker("Select xEdit Executable", vec![("Executables", &["exe"])])
-            {
+            if let Some(path) = Self::show_file_picker(
+                &i18n::tr("GuiController", "Select xEdit Executable"),
+                vec![(i18n::tr("GuiController", "Executables").as_str(), &["exe"])],
+            ) {
                 tracing::info!("xEdit path selected: {}", path);
                 state.set_xedit_exe_path(Some(path.clone()));
 
-                // Auto-detect game type from xEdit executable
-                let load_order_path = state.read(|s| s.load_order_path.clone());
-                if let Some(detected_game) = detect_xedit_game(
-                    &path.to_string(),
+                // Auto-detect game type from xEdit executable, finding its plugins.txt if no
+                // load order is configured yet
+                let load_order_path = state.read(|s| s.load_order_path.clone()).or_else(|| {
+                    let game = detect_xedit_game_with(&registry_clone, path.as_str(), None);
+                    Self::discover_load_order(&registry_clone, &state, game.as_deref())
+                });
+                if let Some(detected_game) = Self::detect_game_type(
+                    &registry_clone,
+                    &state,
+                    path.as_str(),
                     load_order_path.as_ref().map(|p| p.as_ref()),
                 ) {
                     tracing::info!("Auto-detected game type: {}", detected_game);
@@ -412,6 +804,8 @@ impl GuiController {
                         s.game_type = Some(detected_game);
                     });
                 }
+
+                Self::show_diagnostics(&ui_weak, &registry_clone, &main_config_clone, &state);
             }
         });
 
@@ -422,8 +816,8 @@ impl GuiController {
             tracing::debug!("Browse MO2 clicked");
 
             if let Some(path) = Self::show_file_picker(
-                "Select Mod Organizer 2 Executable",
-                vec![("Executables", &["exe"])],
+                &i18n::tr("GuiController", "Select Mod Organizer 2 Executable"),
+                vec![(i18n::tr("GuiController", "Executables").as_str(), &["exe"])],
             ) {
                 tracing::info!("MO2 path selected: {}", path);
                 state.set_mo2_exe_path(Some(path));
@@ -431,18 +825,144 @@ impl GuiController {
         });
 
         let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+        let registry_clone = Arc::clone(registry);
 
         // MO2 mode toggled
         ui.on_mo2_mode_toggled(move || {
-            let enabled = state.read(|s| s.mo2_mode);
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+
+            let enabled = ui.get_mo2_mode();
             tracing::debug!("MO2 mode toggled: {}", enabled);
             state.update_settings(|s| {
                 s.mo2_mode = enabled;
+                if enabled {
+                    s.vortex_mode = false;
+                }
+            });
+
+            if enabled {
+                ui.set_vortex_mode(false);
+            }
+            Self::show_command_preview(&ui, &registry_clone, &state);
+        });
+
+        let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+        let registry_clone = Arc::clone(registry);
+
+        // Vortex mode toggled - Vortex and MO2 modes are mutually exclusive
+        ui.on_vortex_mode_toggled(move || {
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+
+            let enabled = ui.get_vortex_mode();
+            tracing::debug!("Vortex mode toggled: {}", enabled);
+
+            state.update_settings(|s| {
+                s.vortex_mode = enabled;
+                if enabled {
+                    s.mo2_mode = false;
+                }
             });
+
+            if enabled {
+                ui.set_mo2_mode(false);
+            }
+            Self::show_command_preview(&ui, &registry_clone, &state);
+        });
+
+        let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+        let main_config_clone = Arc::clone(main_config);
+        let registry_clone = Arc::clone(registry);
+
+        // Browse game Data folder callback
+        ui.on_browse_data_folder(move || {
+            tracing::debug!("Browse Data folder clicked");
+
+            if let Some(path) =
+                Self::show_folder_picker(&i18n::tr("GuiController", "Select Game Data Folder"))
+            {
+                tracing::info!("Game Data folder selected: {}", path);
+
+                if let Some(ui) = ui_weak.upgrade() {
+                    ui.set_data_path(path.as_str().into());
+                    ui.set_data_path_valid(path.is_dir());
+                }
+
+                state.set_data_path(Some(path));
+
+                // The base master's header can tell apart games sharing it (Skyrim LE/SE)
+                let (xedit_path, load_order_path) =
+                    state.read(|s| (s.xedit_exe_path.clone(), s.load_order_path.clone()));
+                if let Some(xedit) = xedit_path
+                    && let Some(detected_game) = Self::detect_game_type(
+                        &registry_clone,
+                        &state,
+                        xedit.as_str(),
+                        load_order_path.as_deref(),
+                    )
+                {
+                    tracing::info!("Re-detected game type from Data folder: {}", detected_game);
+                    state.update(|s| {
+                        s.game_type = Some(detected_game);
+                    });
+                }
+
+                Self::show_diagnostics(&ui_weak, &registry_clone, &main_config_clone, &state);
+            }
+        });
+
+        let state = state_manager.clone();
+        let ui_weak = ui.as_weak();
+        let main_config_clone = Arc::clone(main_config);
+        let registry_clone = Arc::clone(registry);
+
+        // Detect callback - fill in the setup from discovered installs
+        ui.on_detect_setup(move || {
+            tracing::info!("Detect setup button clicked");
+
+            match Self::apply_discovery(&registry_clone, &state) {
+                Some(candidate) => {
+                    let found = [
+                        candidate
+                            .load_order
+                            .as_ref()
+                            .map(|p| i18n::tr_format("GuiController", "Load order: {}", &[p])),
+                        candidate
+                            .xedit
+                            .as_ref()
+                            .map(|p| i18n::tr_format("GuiController", "xEdit: {}", &[p])),
+                        candidate
+                            .data_path()
+                            .map(|p| i18n::tr_format("GuiController", "Data folder: {}", &[&p])),
+                    ];
+                    let details: Vec<String> = found.into_iter().flatten().collect();
+
+                    if let Some(ui) = ui_weak.upgrade() {
+                        Self::sync_ui_with_state(&ui, &state);
+                    }
+                    Self::show_diagnostics(&ui_weak, &registry_clone, &main_config_clone, &state);
+                    Self::show_message_dialog(
+                        &ui_weak,
+                        i18n::tr_format("GuiController", "Detected {}", &[&candidate.game_id]),
+                        details.join("\n"),
+                    );
+                }
+                None => Self::show_warning_message(
+                    &ui_weak,
+                    &MessageCatalog::new(&main_config_clone).get(MessageId::NothingDetected),
+                ),
+            }
         });
 
         let state = state_manager.clone();
         let ui_weak = ui.as_weak();
+        let registry_clone = Arc::clone(registry);
 
         // Partial forms toggled - show warning dialog if enabling
         ui.on_partial_forms_toggled(move || {
@@ -469,11 +989,15 @@ impl GuiController {
                 state.update_settings(|s| {
                     s.partial_forms_enabled = false;
                 });
+                if let Some(ui) = ui_weak.upgrade() {
+                    Self::show_command_preview(&ui, &registry_clone, &state);
+                }
             }
         });
 
         let state = state_manager.clone();
         let ui_weak = ui.as_weak();
+        let registry_clone = Arc::clone(registry);
 
         // User confirmed partial forms warning
         ui.on_partial_forms_warning_confirmed(move || {
@@ -488,6 +1012,7 @@ impl GuiController {
             if let Some(ui) = ui_weak.upgrade() {
                 ui.set_show_partial_forms_warning(false);
                 ui.set_partial_forms_enabled(true);
+                Self::show_command_preview(&ui, &registry_clone, &state);
             }
         });
 
@@ -565,6 +1090,37 @@ impl GuiController {
             }
         });
 
+        let state = state_manager.clone();
+        let config_manager_clone = Arc::clone(config_manager);
+        let ui_weak = ui.as_weak();
+
+        // Language selected - switch both Slint and Rust-side text and remember the choice
+        ui.on_language_selected(move |index| {
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+            let Some((code, _)) = usize::try_from(index)
+                .ok()
+                .and_then(|i| i18n::LANGUAGES.get(i))
+            else {
+                return;
+            };
+
+            let language = i18n::set_language(code);
+            Self::apply_language(&ui, language);
+            Self::sync_ui_with_state(&ui, &state);
+
+            let saved = config_manager_clone
+                .load_user_config()
+                .and_then(|mut config| {
+                    config.pact_settings.language = language.to_string();
+                    config_manager_clone.save_user_config(&config)
+                });
+            if let Err(e) = saved {
+                tracing::error!("Failed to save language setting: {}", e);
+            }
+        });
+
         let ui_weak = ui.as_weak();
 
         // Show about dialog
@@ -705,7 +1261,7 @@ impl GuiController {
                                     // Update plugin count and configuration status
                                     let plugin_count =
                                         if let Some(ref lo_path) = state_snapshot.load_order_path {
-                                            Self::load_plugins_from_file(lo_path)
+                                            read_load_order(lo_path)
                                                 .ok()
                                                 .map(|plugins| plugins.len())
                                                 .unwrap_or(0)
@@ -757,7 +1313,9 @@ impl GuiController {
                                     ui.set_is_cleaning(true);
                                     ui.set_progress_current(0);
                                     ui.set_progress_total(total_plugins as i32);
-                                    ui.set_status_message("Starting cleaning...".into());
+                                    ui.set_status_message(
+                                        i18n::tr("GuiController", "Starting cleaning...").into(),
+                                    );
                                 });
                             }
 
@@ -775,24 +1333,30 @@ impl GuiController {
 
                                 let state_snapshot = state_manager_clone.read(|s| s.clone());
                                 let plugin_count = state_snapshot.total_plugins;
+                                let attention = state_snapshot.attention_plugins();
 
                                 bridge_handle.update_ui(move |ui| {
                             ui.set_is_cleaning(false);
                             ui.set_cleaned_count(cleaned as i32);
                             ui.set_failed_count(failed as i32);
                             ui.set_skipped_count(skipped as i32);
+                            ui.set_attention_count(attention.len() as i32);
+                            ui.set_attention_plugins(attention.join(", ").into());
+                            Self::show_plugins(ui, &state_snapshot);
 
                             // Generate completion summary message
                             let total = cleaned + failed + skipped;
                             let status = if failed > 0 {
-                                format!(
+                                i18n::tr_format(
+                                    "GuiController",
                                     "Completed with errors: {} cleaned, {} failed, {} skipped (Total: {})",
-                                    cleaned, failed, skipped, total
+                                    &[&cleaned, &failed, &skipped, &total],
                                 )
                             } else {
-                                format!(
+                                i18n::tr_format(
+                                    "GuiController",
                                     "Completed successfully: {} cleaned, {} skipped (Total: {})",
-                                    cleaned, skipped, total
+                                    &[&cleaned, &skipped, &total],
                                 )
                             };
                             ui.set_status_message(status.into());
@@ -867,6 +1431,8 @@ impl GuiController {
                                     ui.set_cleaned_count(0);
                                     ui.set_failed_count(0);
                                     ui.set_skipped_count(0);
+                                    ui.set_attention_count(0);
+                                    ui.set_attention_plugins("".into());
 
                                     // Reset current plugin statistics
                                     ui.set_current_undeleted(0);
@@ -948,6 +1514,22 @@ impl GuiController {
         }
     }
 
+    /// Show a catalog error message in the error dialog
+    ///
+    /// The message title becomes the dialog title and its body the dialog text.
+    fn show_error_message(
+        ui_weak: &slint::Weak<MainWindow>,
+        message: &Message,
+        details: impl Into<slint::SharedString>,
+    ) {
+        Self::show_error_dialog(ui_weak, message.title(), message.body(), details);
+    }
+
+    /// Show a catalog warning message in the message dialog
+    fn show_warning_message(ui_weak: &slint::Weak<MainWindow>, message: &Message) {
+        Self::show_message_dialog(ui_weak, message.title(), message.body());
+    }
+
     /// Show a native file picker dialog
     ///
     /// Uses the `rfd` crate to display a native file dialog on Windows.
@@ -979,100 +1561,512 @@ impl GuiController {
         })
     }
 
-    // ===== Cleaning Orchestration =====
-
-    /// Run the complete cleaning workflow
+    /// Show a native folder picker dialog
     ///
-    /// This is the main orchestration method that:
-    /// 1. Loads plugins from load order file
-    /// 2. Detects game type if not already set
-    /// 3. Filters plugins using skip lists from main config
-    /// 4. Creates CleaningService and Semaphore for serial execution
-    /// 5. Cleans each plugin sequentially
-    /// 6. Updates UI with progress and results
-    /// 7. Supports immediate cancellation via watch channel (no polling)
-    async fn run_cleaning_workflow(
-        state: Arc<StateManager>,
-        main_config: Arc<MainConfig>,
-        bridge: crate::ui::bridge::EventLoopBridgeHandle<MainWindow>,
-        cancel_rx: watch::Receiver<bool>,
-    ) -> Result<()> {
-        tracing::info!("Starting cleaning workflow");
-
+    /// # Arguments
+    /// * `title` - Dialog title
+    ///
+    /// # Returns
+    /// The selected folder path, or None if cancelled
+    fn show_folder_picker(title: &str) -> Option<Utf8PathBuf> {
+        use rfd::FileDialog;
+
+        FileDialog::new()
+            .set_title(title)
+            .pick_folder()
+            .and_then(|path| {
+                Utf8PathBuf::try_from(path)
+                    .map_err(|e| {
+                        tracing::error!("Failed to convert path to UTF-8: {}", e);
+                        e
+                    })
+                    .ok()
+            })
+    }
+
+    /// Ask whether to resume the run in the session checkpoint, if one was interrupted
+    fn offer_resume(ui: &MainWindow, config_manager: &ConfigManager) {
+        // The checkpoint of a run another AutoQAC is still cleaning is not interrupted
+        if let Some(pid) = InstanceLock::holder(config_manager.config_dir()) {
+            tracing::info!(
+                "AutoQAC process {} is cleaning, not offering to resume",
+                pid
+            );
+            return;
+        }
+
+        let checkpoint = match config_manager.load_checkpoint() {
+            Ok(Some(checkpoint)) => checkpoint,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!("Discarding unreadable session checkpoint: {:#}", e);
+                let _ = config_manager.clear_checkpoint();
+                return;
+            }
+        };
+
+        let pending = checkpoint.pending();
+        let Some(next) = pending.first() else {
+            // Every plugin finished; only the cleanup at the end of the run was missed
+            let _ = config_manager.clear_checkpoint();
+            return;
+        };
+
+        tracing::info!(
+            "Found interrupted session {} with {} plugin(s) left",
+            checkpoint.session_name(),
+            pending.len()
+        );
+        ui.set_resume_message(
+            i18n::tr_format(
+                "GuiController",
+                "The cleaning run started {} did not finish: {} of {} plugins are done.\n\nResume it with {}, or discard it?",
+                &[
+                    &checkpoint.session_name(),
+                    &checkpoint.results.len(),
+                    &checkpoint.queue.len(),
+                    next,
+                ],
+            )
+            .into(),
+        );
+        ui.set_show_resume_dialog(true);
+    }
+
+    /// Choose a plugin to clean on its own instead of the load order
+    ///
+    /// Takes a plugin path or name from the command line, an "Open with" file association or
+    /// a file dropped on the window. xEdit loads plugins by name from the Data folder (the
+    /// MO2 virtual one in MO2 mode), so only the file name is kept.
+    ///
+    /// # Errors
+    /// Returns an error if the target is not a plugin file, or
+    /// [`CleaningError::PluginNotInstalled`] if it is missing from the configured Data folder
+    /// outside MO2 mode
+    pub fn select_single_plugin(state: &StateManager, target: &str) -> Result<String> {
+        let plugin = plugin_name(target).ok_or_else(|| anyhow!("Not a plugin file: {}", target))?;
+
+        let (data_path, mo2_mode) = state.read(|s| (s.data_path.clone(), s.mo2_mode));
+        // xEdit only loads plugins from the Data folder (MO2's is virtual and can't be checked)
+        if let Some(data_path) = data_path
+            && !mo2_mode
+            && !data_path.join(&plugin).is_file()
+        {
+            tracing::error!("{} is not in the Data folder {}", plugin, data_path);
+            return Err(CleaningError::PluginNotInstalled(plugin).into());
+        }
+
+        tracing::info!("Cleaning single plugin {}", plugin);
+        state.update(|s| s.single_plugin = Some(plugin.clone()));
+        Ok(plugin)
+    }
+
+    /// Clean plugin files dropped on the window, one at a time
+    fn setup_file_drop(
+        ui: &MainWindow,
+        state_manager: &Arc<StateManager>,
+        main_config: &Arc<MainConfig>,
+    ) {
+        let state = Arc::clone(state_manager);
+        let config = Arc::clone(main_config);
+        let ui_weak = ui.as_weak();
+
+        ui.window().on_winit_window_event(move |_, event| {
+            let WindowEvent::DroppedFile(path) = event else {
+                return EventResult::Propagate;
+            };
+            let target = path.to_string_lossy();
+
+            if state.read(|s| s.is_cleaning || s.single_plugin.is_some()) {
+                tracing::warn!("Ignoring dropped file while cleaning: {}", target);
+                return EventResult::Propagate;
+            }
+
+            match Self::select_single_plugin(&state, &target) {
+                // Start once the drop is handled, like a click on Start Cleaning
+                Ok(_) => {
+                    let _ = ui_weak.upgrade_in_event_loop(|ui| ui.invoke_start_cleaning());
+                }
+                Err(e) => Self::show_single_plugin_error(&ui_weak, &config, &target, &e),
+            }
+            EventResult::Propagate
+        });
+    }
+
+    /// Tell the user why a plugin from the command line could not be cleaned on its own
+    ///
+    /// Shows the same dialog as a rejected drop on the window, once the window is open.
+    pub fn report_single_plugin_error(&self, target: &str, error: &anyhow::Error) {
+        Self::show_single_plugin_error(&self.ui.as_weak(), &self.main_config, target, error);
+    }
+
+    /// Show why a plugin was refused for cleaning on its own
+    fn show_single_plugin_error(
+        ui_weak: &slint::Weak<MainWindow>,
+        main_config: &MainConfig,
+        target: &str,
+        error: &anyhow::Error,
+    ) {
+        tracing::warn!("{}", error);
+        if let Some(error) = error.downcast_ref::<CleaningError>() {
+            let message = error.to_message(&MessageCatalog::new(main_config));
+            Self::show_error_message(ui_weak, &message, target);
+            return;
+        }
+        Self::show_message_dialog(
+            ui_weak,
+            i18n::tr("GuiController", "Not a plugin"),
+            i18n::tr_format(
+                "GuiController",
+                "Only .esp, .esm and .esl files can be cleaned: {}",
+                &[&target],
+            ),
+        );
+    }
+
+    // ===== Cleaning Orchestration =====
+
+    /// Resolve the game and load order and decide which plugins will be cleaned
+    ///
+    /// Shared by the plan preview, the cleaning run and `--dry-run`, so the preview shows
+    /// exactly what the run will do. Detects the game and discovers the load order when they
+    /// are not configured, and loads the cleaning history into the state. With a single
+    /// plugin to clean, the game comes from the configuration or xEdit alone and the load
+    /// order is not read.
+    ///
+    /// # Errors
+    /// Returns an error if no load order file is configured or found, or it cannot be read
+    pub fn plan_cleaning(
+        registry: &GameRegistry,
+        state: &StateManager,
+        main_config: &MainConfig,
+        ignore_config: &IgnoreConfig,
+        config_manager: &ConfigManager,
+    ) -> Result<(Option<Arc<GameDefinition>>, CleaningPlan)> {
         // Get current game type and paths from state
-        let (game_type, xedit_path, load_order_path) = state.read(|s| {
+        let (game_type, xedit_path, load_order_path, single_plugin) = state.read(|s| {
             (
                 s.game_type.clone(),
                 s.xedit_exe_path.clone(),
                 s.load_order_path.clone(),
+                s.single_plugin.clone(),
             )
         });
 
+        // Earlier runs size each plugin's timeout and tell which plugins are unchanged
+        Self::load_history(state, config_manager);
+
+        if let Some(plugin) = single_plugin {
+            let game_type = game_type.or_else(|| {
+                let xedit = xedit_path?;
+                Self::auto_detect_game_type(registry, state, xedit.as_str(), None)
+            });
+            let game = Self::game_definition(registry, game_type.as_deref());
+            let plan = state.single_plugin_plan(
+                registry,
+                main_config,
+                ignore_config,
+                game.as_deref(),
+                &plugin,
+            );
+            return Ok((game, plan));
+        }
+
+        let load_order_path = load_order_path.or_else(|| {
+            let game = game_type.clone().or_else(|| {
+                xedit_path
+                    .as_ref()
+                    .and_then(|xedit| detect_xedit_game_with(registry, xedit.as_str(), None))
+            });
+            Self::discover_load_order(registry, state, game.as_deref())
+        });
+
         // Auto-detect game type if not already set
-        let game_type = if game_type.is_none() {
-            if let (Some(xedit), Some(lo_path)) = (&xedit_path, &load_order_path) {
-                let detected = detect_xedit_game(xedit.as_str(), Some(&lo_path));
-                if let Some(ref detected_game) = detected {
-                    tracing::info!("Auto-detected game type: {}", detected_game);
-                    state.update(|s| {
-                        s.game_type = Some(detected_game.clone());
-                    });
-                }
-                detected
-            } else {
-                None
+        let game_type = match (game_type, &xedit_path, &load_order_path) {
+            (Some(game_type), _, _) => Some(game_type),
+            (None, Some(xedit), Some(lo_path)) => {
+                Self::auto_detect_game_type(registry, state, xedit.as_str(), Some(lo_path))
             }
-        } else {
-            game_type
+            (None, _, _) => None,
         };
 
-        // Load plugins from load order file
-        let load_order_path =
-            load_order_path.ok_or_else(|| anyhow!("Load order path not configured"))?;
+        let load_order_path = load_order_path.ok_or(CleaningError::LoadOrderNotConfigured)?;
+        let game = Self::game_definition(registry, game_type.as_deref());
 
-        let plugins =
-            Self::load_plugins_from_file(&load_order_path).context("Failed to load plugins")?;
+        let plan = state
+            .cleaning_plan(
+                registry,
+                main_config,
+                ignore_config,
+                game.as_deref(),
+                &load_order_path,
+            )
+            .context("Failed to load plugins")?;
 
-        tracing::info!("Loaded {} plugins from load order", plugins.len());
+        Ok((game, plan))
+    }
 
-        // Filter plugins using skip lists
-        let plugins_to_clean: Vec<String> = if let Some(ref game) = game_type {
-            let before_count = plugins.len();
-            let filtered: Vec<String> = plugins
-                .into_iter()
-                .filter(|plugin| {
-                    let should_skip = main_config.should_skip_plugin(game, plugin);
-                    if should_skip {
-                        tracing::debug!("Skipping plugin (in skip list): {}", plugin);
+    /// Load the cleaning history into the state
+    fn load_history(state: &StateManager, config_manager: &ConfigManager) {
+        let history = config_manager.load_cleaning_history().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load cleaning history, starting empty: {}", e);
+            CleaningHistory::default()
+        });
+        state.update(|s| s.cleaning_history = history);
+    }
+
+    /// Save the checkpoint of the current run, so it can be resumed if AutoQAC stops
+    fn save_checkpoint(state: &StateManager, config_manager: &ConfigManager) {
+        if let Some(checkpoint) = state.read(|s| s.checkpoint.clone())
+            && let Err(e) = config_manager.save_checkpoint(&checkpoint)
+        {
+            tracing::warn!("Failed to save session checkpoint: {}", e);
+        }
+    }
+
+    /// Detect the game type and remember it in the state
+    fn auto_detect_game_type(
+        registry: &GameRegistry,
+        state: &StateManager,
+        xedit_path: &str,
+        load_order_path: Option<&Utf8Path>,
+    ) -> Option<String> {
+        let detected = Self::detect_game_type(registry, state, xedit_path, load_order_path)?;
+        tracing::info!("Auto-detected game type: {}", detected);
+        state.update(|s| {
+            s.game_type = Some(detected.clone());
+        });
+        Some(detected)
+    }
+
+    /// Definition of a game type; without one the skip and ignore lists are not applied
+    fn game_definition(
+        registry: &GameRegistry,
+        game_type: Option<&str>,
+    ) -> Option<Arc<GameDefinition>> {
+        let game = game_type.and_then(|id| {
+            let definition = registry.get(id).cloned().map(Arc::new);
+            if definition.is_none() {
+                tracing::warn!("No game definition for game type: {}", id);
+            }
+            definition
+        });
+        if game.is_none() {
+            tracing::warn!("Game type not detected - skip and ignore lists are not applied");
+        }
+        game
+    }
+
+    /// Run the complete cleaning workflow
+    ///
+    /// This is the main orchestration method that:
+    /// 1. Loads plugins from load order file
+    /// 2. Detects game type if not already set
+    /// 3. Leaves out plugins as the [`CleaningPlan`] decides (skip and ignore lists, inactive,
+    ///    missing masters, unchanged since the last cleaning, clean according to LOOT)
+    /// 4. Creates CleaningService and Semaphore for serial execution
+    /// 5. Cleans each plugin sequentially
+    /// 6. Updates UI with progress and results
+    /// 7. Saves each plugin's run time to the cleaning history for adaptive timeouts
+    /// 8. Saves a [`SessionCheckpoint`] after every plugin and removes it when the run ends
+    /// 9. Supports immediate cancellation via watch channel (no polling)
+    ///
+    /// The run holds an [`InstanceLock`] so a second AutoQAC cannot clean at the same time,
+    /// refuses to start while the game, its editor or an xEdit is running, and pauses before
+    /// the next plugin if one of them is started during the run.
+    ///
+    /// With a checkpoint in the state, steps 1-3 are skipped and the interrupted run goes on
+    /// with the plugins it had not finished.
+    async fn run_cleaning_workflow(
+        state: Arc<StateManager>,
+        config_manager: Arc<ConfigManager>,
+        main_config: Arc<MainConfig>,
+        registry: Arc<GameRegistry>,
+        ignore_config: IgnoreConfig,
+        bridge: crate::ui::bridge::EventLoopBridgeHandle<MainWindow>,
+        cancel_rx: watch::Receiver<bool>,
+    ) -> Result<()> {
+        tracing::info!("Starting cleaning workflow");
+
+        // Held until the workflow returns
+        let _lock = InstanceLock::acquire(config_manager.config_dir())?;
+
+        // A checkpoint left in the state is an interrupted run to resume
+        let resume = state.read(|s| s.checkpoint.clone());
+
+        let (game, plugins_to_clean) = match resume {
+            Some(ref checkpoint) => {
+                Self::load_history(&state, &config_manager);
+                let game =
+                    Self::game_definition(&registry, checkpoint.settings.game_type.as_deref());
+                let pending = checkpoint.pending();
+
+                tracing::info!(
+                    "Resuming session {}: {} of {} plugins left",
+                    checkpoint.session_name(),
+                    pending.len(),
+                    checkpoint.queue.len()
+                );
+                (game, pending)
+            }
+            None => {
+                let (game, plan) = Self::plan_cleaning(
+                    &registry,
+                    &state,
+                    &main_config,
+                    &ignore_config,
+                    &config_manager,
+                )?;
+                for (plugin, exclusion) in plan.excluded() {
+                    tracing::debug!("Skipping plugin {}: {}", plugin, exclusion);
+                }
+
+                // Clean the plugins selected in the plugin list, in the list's order; a single
+                // plugin is cleaned on its own and leaves the list alone
+                let plugins_to_clean = if state.read(|s| s.single_plugin.is_some()) {
+                    if let Some((plugin, exclusion)) = plan.excluded().next() {
+                        let text = i18n::tr_format(
+                            "GuiController",
+                            "{} is not cleaned: {}",
+                            &[&plugin, &Self::exclusion_text(exclusion)],
+                        );
+                        bridge.update_ui(move |ui| ui.set_current_operation(text.into()));
+                        return Ok(());
                     }
-                    !should_skip
-                })
-                .collect();
+                    plan.plugins_to_clean()
+                } else {
+                    state.update(|s| {
+                        s.plugin_queue.sync(&plan);
+                        s.cleaning_plan = plan.clone();
+                    });
+                    state.read(|s| {
+                        for entry in s
+                            .plugin_queue
+                            .entries
+                            .iter()
+                            .filter(|entry| !entry.selected)
+                        {
+                            tracing::debug!("Skipping plugin {}: not selected", entry.plugin);
+                        }
+                        s.plugin_queue.selected()
+                    })
+                };
 
-            let skipped_count = before_count - filtered.len();
-            if skipped_count > 0 {
                 tracing::info!(
-                    "Filtered out {} plugins from skip list for game type: {}",
-                    skipped_count,
-                    game
+                    "Cleaning {} of {} plugins from the load order",
+                    plugins_to_clean.len(),
+                    plan.entries.len()
                 );
+                (game, plugins_to_clean)
             }
-            filtered
-        } else {
-            tracing::warn!("Game type not detected - cleaning all plugins without filtering");
-            plugins
         };
 
+        // Report light and medium masters when the Data folder is known
+        if let (Some(game), Some(data_path)) = (&game, state.read(|s| s.data_path.clone())) {
+            for plugin in &plugins_to_clean {
+                match PluginHeader::read(&data_path.join(plugin)) {
+                    Ok(header) => tracing::debug!(
+                        "{} is a {} ({} records)",
+                        plugin,
+                        header.kind(plugin, game),
+                        header.record_count
+                    ),
+                    Err(e) => tracing::debug!("Could not read header of {}: {}", plugin, e),
+                }
+            }
+        }
+
         if plugins_to_clean.is_empty() {
             tracing::warn!("No plugins to clean");
             bridge.update_ui(|ui| {
-                ui.set_current_operation("No plugins to clean".into());
+                ui.set_current_operation(i18n::tr("GuiController", "No plugins to clean").into());
             });
             return Ok(());
         }
 
-        // Start cleaning operation in state
-        state.start_cleaning(plugins_to_clean.clone());
+        // Plugins the game, its editor or another xEdit has open must not be cleaned
+        let blocking: Arc<Vec<String>> = Arc::new(
+            blocking_executables(&registry, game.as_ref().map(|g| g.id.as_str()))
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        );
+        let running = running_programs(&blocking);
+        if !running.is_empty() {
+            tracing::warn!("Not cleaning while running: {}", running.join(", "));
+            return Err(CleaningError::ProgramsRunning(running).into());
+        }
+
+        // In Vortex mode, map plugins to the mods they were deployed from
+        let vortex_deployment = if state.read(|s| s.vortex_mode) {
+            let data_path = state
+                .read(|s| s.data_path.clone())
+                .ok_or(CleaningError::DataFolderNotConfigured)?;
+            Some(VortexDeployment::load(&data_path)?)
+        } else {
+            None
+        };
+
+        // xEdit writes each plugin's logs into a folder for this run; a resumed run goes on
+        // in the folder it started in
+        let session = Arc::new(match resume {
+            Some(ref checkpoint) => CleaningSession::open(Utf8Path::new(&checkpoint.session))?,
+            None => CleaningSession::create(&state.read(|s| s.sessions_dir.clone()))?,
+        });
+
+        // Start cleaning operation in state, with a checkpoint saved after every plugin
+        match resume {
+            Some(ref checkpoint) => {
+                state.resume_cleaning(checkpoint);
+            }
+            None => {
+                state.start_cleaning(plugins_to_clean.clone());
+                state.update(|s| {
+                    s.checkpoint = Some(SessionCheckpoint {
+                        session: session.dir().to_string(),
+                        queue: plugins_to_clean.clone(),
+                        results: Default::default(),
+                        settings: s.checkpoint_settings(),
+                    });
+                });
+            }
+        }
+        Self::save_checkpoint(&state, &config_manager);
+
+        if let Some(ref deployment) = vortex_deployment {
+            let sources = deployment.plugin_sources(&plugins_to_clean);
+
+            tracing::info!(
+                "Vortex deployment maps {} of {} plugins to staging mods",
+                sources.len(),
+                plugins_to_clean.len()
+            );
+
+            // Hard-linked plugins share their data with the staged copy in Vortex
+            if deployment.is_hardlink() && !sources.is_empty() {
+                tracing::warn!(
+                    "Vortex uses hard-link deployment - cleaning will modify {} staged plugin(s) in {}",
+                    sources.len(),
+                    deployment.staging_path
+                );
+
+                let message = MessageCatalog::new(&main_config).format(
+                    MessageId::VortexHardLinks,
+                    &[
+                        ("count", &sources.len()),
+                        ("staging", &deployment.staging_path),
+                    ],
+                );
+                bridge.update_ui(move |ui| {
+                    Self::show_warning_message(&ui.as_weak(), &message);
+                });
+            }
+
+            state.update(|s| {
+                // A resumed run keeps the sources restored for its finished plugins
+                s.plugin_sources.extend(sources);
+            });
+        }
 
         // Create CleaningService
         let service = Arc::new(CleaningService::new());
@@ -1121,8 +2115,15 @@ impl GuiController {
             let service_clone = service.clone();
             let semaphore_clone = semaphore.clone();
             let cancel_rx_clone = cancel_rx.clone();
+            let game_clone = game.clone();
+            let config_clone = Arc::clone(&main_config);
+            let session_clone = Arc::clone(&session);
+            let config_manager_clone = Arc::clone(&config_manager);
+            let blocking_clone = Arc::clone(&blocking);
 
             let task = tokio::spawn(async move {
+                let catalog = MessageCatalog::new(&config_clone);
+
                 // Clone cancel receiver for use in select block
                 let mut cancel_rx_for_permit = cancel_rx_clone.clone();
 
@@ -1138,17 +2139,76 @@ impl GuiController {
                     }
                 };
 
+                // Wait while a program started during the run has the plugins open
+                if !Self::wait_for_programs(
+                    &blocking_clone,
+                    &plugin,
+                    &state_clone,
+                    &mut cancel_rx_for_permit,
+                )
+                .await
+                {
+                    tracing::warn!("Cleaning cancelled while paused before plugin: {}", plugin);
+                    return;
+                }
+
                 tracing::info!("Cleaning plugin {}: {}", index + 1, plugin);
 
                 // Update UI with current plugin
-                state_clone.update_progress(plugin.clone(), format!("Cleaning {}...", plugin));
+                state_clone.update_progress(
+                    plugin.clone(),
+                    i18n::tr_format("GuiController", "Cleaning {}...", &[&plugin]),
+                );
 
                 // CANCELLATION POINT 2: Inside clean_plugin() via tokio::select!
                 // Races xEdit subprocess execution against cancellation signal
-                match Self::clean_plugin(&plugin, &state_clone, &service_clone, cancel_rx_clone)
-                    .await
+                match Self::clean_plugin(
+                    &plugin,
+                    game_clone.as_deref(),
+                    &state_clone,
+                    &service_clone,
+                    &catalog,
+                    &session_clone,
+                    cancel_rx_clone,
+                )
+                .await
                 {
                     Ok((status, message, stats)) => {
+                        // Point to the navmesh report when the plugin needs a manual fix
+                        let message = if state_clone.read(|s| s.needs_attention.contains(&plugin)) {
+                            format!(
+                                "{} - {}",
+                                message,
+                                i18n::tr_format(
+                                    "GuiController",
+                                    "needs manual attention, see {}",
+                                    &[&session_clone.plugin_logs(&plugin).navmesh_report],
+                                )
+                            )
+                        } else {
+                            message
+                        };
+
+                        // Remember the checksum of the cleaned plugin, so it is left out
+                        // of later runs until it changes. In MO2 mode xEdit cleaned the copy
+                        // in a mod folder, not the one in the Data folder.
+                        if stats.is_some()
+                            && status != "failed"
+                            && let Some(data_path) =
+                                state_clone.read(|s| s.data_path.clone().filter(|_| !s.mo2_mode))
+                        {
+                            match plugin_crc(&data_path.join(&plugin)) {
+                                Ok(crc) => {
+                                    state_clone.update(|s| {
+                                        s.cleaning_history.record_cleaned(&plugin, crc)
+                                    });
+                                }
+                                Err(e) => {
+                                    tracing::debug!("Could not checksum {}: {}", plugin, e)
+                                }
+                            }
+                        }
+
                         tracing::info!("Plugin {} completed: {} - {}", plugin, status, message);
                         state_clone.add_plugin_result(
                             plugin.clone(),
@@ -1156,20 +2216,29 @@ impl GuiController {
                             message.clone(),
                             stats,
                         );
+                        Self::save_checkpoint(&state_clone, &config_manager_clone);
 
                         // Update UI
                         bridge_clone.update_ui(move |ui| {
-                            ui.set_current_operation(format!("Completed: {}", plugin).into());
+                            ui.set_current_operation(
+                                i18n::tr_format("GuiController", "Completed: {}", &[&plugin])
+                                    .into(),
+                            );
                         });
                     }
                     Err(e) => {
                         tracing::error!("Plugin {} failed: {}", plugin, e);
+                        let message = match e.downcast_ref::<CleaningError>() {
+                            Some(error) => error.to_message(&catalog).summary().to_string(),
+                            None => e.to_string(),
+                        };
                         state_clone.add_plugin_result(
                             plugin.clone(),
                             "failed",
-                            format!("Error: {}", e),
+                            i18n::tr_format("GuiController", "Error: {}", &[&message]),
                             None,
                         );
+                        Self::save_checkpoint(&state_clone, &config_manager_clone);
                     }
                 }
 
@@ -1186,6 +2255,35 @@ impl GuiController {
             }
         }
 
+        // Collect the navmesh reports of plugins that need a manual fix in one file
+        let attention: Vec<NavmeshReport> = state.read(|s| {
+            s.attention_plugins()
+                .iter()
+                .filter_map(|plugin| s.navmesh_reports.get(plugin).cloned())
+                .collect()
+        });
+        if !attention.is_empty() {
+            let text: String = attention.iter().map(NavmeshReport::render).collect();
+            let path = session.attention_report();
+            match std::fs::write(&path, text) {
+                Ok(()) => tracing::info!("Plugins needing manual attention listed in {}", path),
+                Err(e) => tracing::warn!("Failed to write {}: {}", path, e),
+            }
+        }
+
+        // Keep this run's times and checksums for the next run
+        if let Err(e) =
+            config_manager.save_cleaning_history(&state.read(|s| s.cleaning_history.clone()))
+        {
+            tracing::warn!("Failed to save cleaning history: {}", e);
+        }
+
+        // The run has ended, so there is nothing left to resume
+        state.update(|s| s.checkpoint = None);
+        if let Err(e) = config_manager.clear_checkpoint() {
+            tracing::warn!("Failed to remove session checkpoint: {}", e);
+        }
+
         // Finish cleaning
         state.stop_cleaning();
 
@@ -1193,12 +2291,48 @@ impl GuiController {
 
         // Update UI with completion message
         bridge.update_ui(|ui| {
-            ui.set_current_operation("Cleaning completed".into());
+            ui.set_current_operation(i18n::tr("GuiController", "Cleaning completed").into());
         });
 
         Ok(())
     }
 
+    /// Pause until none of the programs that keep the plugins open are running
+    ///
+    /// Returns false if the run is cancelled while paused.
+    async fn wait_for_programs(
+        executables: &[String],
+        plugin: &str,
+        state: &StateManager,
+        cancel_rx: &mut watch::Receiver<bool>,
+    ) -> bool {
+        loop {
+            let running = running_programs(executables);
+            if running.is_empty() {
+                return true;
+            }
+
+            tracing::warn!(
+                "Paused before {} while running: {}",
+                plugin,
+                running.join(", ")
+            );
+            state.update_progress(
+                plugin.to_string(),
+                i18n::tr_format(
+                    "GuiController",
+                    "Paused: close {} to continue cleaning",
+                    &[&running.join(", ")],
+                ),
+            );
+
+            tokio::select! {
+                _ = tokio::time::sleep(PROGRAM_POLL_INTERVAL) => {}
+                _ = cancel_rx.changed() => return false,
+            }
+        }
+    }
+
     /// Generate contextual status message based on current state
     ///
     /// Returns a user-friendly status message that reflects the current application state.
@@ -1210,136 +2344,584 @@ impl GuiController {
     ) -> String {
         if is_cleaning {
             if let Some(plugin) = current_plugin {
-                format!(
+                let position = state.cleaned_plugins.len()
+                    + state.failed_plugins.len()
+                    + state.skipped_plugins.len()
+                    + 1;
+                i18n::tr_format(
+                    "GuiController",
                     "Cleaning {} ({}/{})",
-                    plugin,
-                    state.cleaned_plugins.len()
-                        + state.failed_plugins.len()
-                        + state.skipped_plugins.len()
-                        + 1,
-                    state.total_plugins
+                    &[&plugin, &position, &state.total_plugins],
                 )
             } else {
-                "Starting cleaning...".to_string()
+                i18n::tr("GuiController", "Starting cleaning...")
             }
         } else if !state.is_fully_configured() {
             let mut missing = Vec::new();
             if state.load_order_path.is_none() {
-                missing.push("Load Order");
+                missing.push(i18n::tr("GuiController", "Load Order"));
             }
             if state.xedit_exe_path.is_none() {
-                missing.push("xEdit");
+                missing.push("xEdit".to_string());
             }
             if missing.is_empty() {
-                "Configuration incomplete".to_string()
+                i18n::tr("GuiController", "Configuration incomplete")
             } else {
-                format!("Setup required: {}", missing.join(", "))
+                i18n::tr_format(
+                    "GuiController",
+                    "Setup required: {}",
+                    &[&missing.join(", ")],
+                )
             }
         } else if plugin_count > 0 {
-            let game_info = if let Some(ref game) = state.game_type {
-                format!(" ({}) -", game)
-            } else {
-                String::new()
-            };
-            format!("Ready to clean{} {} plugins", game_info, plugin_count)
+            match state.game_type {
+                Some(ref game) => i18n::tr_format(
+                    "GuiController",
+                    "Ready to clean ({}) - {} plugins",
+                    &[game, &plugin_count],
+                ),
+                None => i18n::tr_format(
+                    "GuiController",
+                    "Ready to clean {} plugins",
+                    &[&plugin_count],
+                ),
+            }
         } else {
-            "Ready - No plugins in load order".to_string()
+            i18n::tr("GuiController", "Ready - No plugins in load order")
         }
     }
 
-    /// Load plugins from a load order file (plugins.txt or loadorder.txt)
+    /// Detect the game type from the configured paths
     ///
-    /// Reads the file and extracts plugin names, filtering out comments and invalid entries.
-    fn load_plugins_from_file(path: &Utf8Path) -> Result<Vec<String>> {
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read load order file: {}", path))?;
-
-        let plugins: Vec<String> = content
-            .lines()
-            .filter_map(|line| {
-                let line = line.trim();
-                // Skip empty lines and comments
-                if line.is_empty() || line.starts_with('#') {
-                    return None;
-                }
+    /// Uses the game Data folder from state, when set, to check the base master's header.
+    fn detect_game_type(
+        registry: &GameRegistry,
+        state: &StateManager,
+        xedit_path: &str,
+        load_order_path: Option<&Utf8Path>,
+    ) -> Option<String> {
+        let data_path = state.read(|s| s.data_path.clone());
+        detect_game(registry, xedit_path, load_order_path, data_path.as_deref())
+    }
 
-                // Handle plugins.txt format with asterisks
-                let plugin = if line.starts_with('*') {
-                    line[1..].trim()
-                } else {
-                    line
-                };
+    /// Discover installs and apply the best candidate to the state
+    ///
+    /// Fills in the load order, xEdit executable and Data folder that were found and sets
+    /// the game type. Returns the applied candidate, or None if nothing was found.
+    fn apply_discovery(registry: &GameRegistry, state: &StateManager) -> Option<SetupCandidate> {
+        let runner = state.read(|s| s.runner.clone());
+        let sources = DiscoverySources::for_runner(&runner);
+        let candidate = discover(registry, &runner, &sources).into_iter().next()?;
 
-                // Only include .esp, .esm, .esl files
-                if plugin.ends_with(".esp") || plugin.ends_with(".esm") || plugin.ends_with(".esl")
-                {
-                    Some(plugin.to_string())
-                } else {
-                    None
+        tracing::info!(
+            "Applying discovered setup for {} (score {})",
+            candidate.game_id,
+            candidate.score
+        );
+
+        if let Some(ref load_order) = candidate.load_order {
+            state.set_load_order_path(Some(load_order.clone()));
+        }
+        if let Some(ref xedit) = candidate.xedit {
+            state.set_xedit_exe_path(Some(xedit.clone()));
+        }
+        if let Some(data_path) = candidate.data_path() {
+            state.set_data_path(Some(data_path));
+        }
+        state.update(|s| {
+            s.game_type = Some(candidate.game_id.clone());
+        });
+
+        Some(candidate)
+    }
+
+    /// Check the selected xEdit's version against the features this run needs
+    ///
+    /// Returns no issues when the version cannot be read; cleaning then goes ahead as before.
+    fn check_xedit_capabilities(
+        registry: &GameRegistry,
+        state: &StateManager,
+    ) -> Vec<CapabilityIssue> {
+        let (xedit_path, load_order_path, game_type, partial_forms) = state.read(|s| {
+            (
+                s.xedit_exe_path.clone(),
+                s.load_order_path.clone(),
+                s.game_type.clone(),
+                s.partial_forms_enabled,
+            )
+        });
+        let Some(xedit) = xedit_path else {
+            return Vec::new();
+        };
+
+        let version = match XEditVersion::read(&xedit) {
+            Ok(version) => version,
+            Err(e) => {
+                tracing::warn!("Could not determine xEdit version: {:#}", e);
+                return Vec::new();
+            }
+        };
+        tracing::info!("Selected xEdit version: {}", version);
+
+        let game_type = game_type.or_else(|| {
+            Self::detect_game_type(registry, state, xedit.as_str(), load_order_path.as_deref())
+        });
+        let game = game_type.as_deref().and_then(|id| registry.get(id));
+
+        let issues = check_capabilities(version, &required_features(registry, game, partial_forms));
+        for issue in &issues {
+            tracing::warn!("{}", issue);
+        }
+        issues
+    }
+
+    /// Fill the plugin list from the state, applying the list's name and status filters
+    fn show_plugins(ui: &MainWindow, state: &AppState) {
+        let filter = PluginFilter::from_index(ui.get_plugin_status_filter());
+        let rows: Vec<PlanRow> = state
+            .plugin_list(&ui.get_plugin_filter(), filter)
+            .into_iter()
+            .map(|item| PlanRow {
+                plugin: item.plugin.into(),
+                source: item.source.unwrap_or_default().into(),
+                included: item.exclusion.is_none(),
+                selected: item.selected,
+                status: match (item.result, item.exclusion) {
+                    (Some(result), _) => Self::result_text(result),
+                    (None, Some(exclusion)) => Self::exclusion_text(exclusion),
+                    (None, None) => String::new(),
                 }
+                .into(),
             })
             .collect();
 
-        Ok(plugins)
+        ui.set_plan_summary(
+            i18n::tr_format(
+                "GuiController",
+                "{} of {} plugins will be cleaned",
+                &[
+                    &state.plugin_queue.selected().len(),
+                    &state.cleaning_plan.entries.len(),
+                ],
+            )
+            .into(),
+        );
+        ui.set_plan_rows(ModelRc::new(VecModel::from(rows)));
     }
 
-    /// Clean a single plugin
+    /// Translated result of a plugin ("cleaned", "failed" or "skipped")
+    fn result_text(result: &str) -> String {
+        match result {
+            "cleaned" => i18n::tr("GuiController", "Cleaned"),
+            "failed" => i18n::tr("GuiController", "Failed"),
+            _ => i18n::tr("GuiController", "Skipped"),
+        }
+    }
+
+    /// Translated reason a plugin is left out of the run
+    pub fn exclusion_text(exclusion: &Exclusion) -> String {
+        match exclusion {
+            Exclusion::SkipList => i18n::tr("GuiController", "In the skip list"),
+            Exclusion::IgnoreList => i18n::tr("GuiController", "In the ignore list"),
+            Exclusion::Inactive => i18n::tr("GuiController", "Inactive in the load order"),
+            Exclusion::MissingMaster(master) => {
+                i18n::tr_format("GuiController", "Missing master {}", &[master])
+            }
+            Exclusion::Unchanged => {
+                i18n::tr("GuiController", "Unchanged since it was last cleaned")
+            }
+            Exclusion::LootClean(util) if util.is_empty() => {
+                i18n::tr("GuiController", "Clean according to LOOT")
+            }
+            Exclusion::LootClean(util) => {
+                i18n::tr_format("GuiController", "Clean according to LOOT ({})", &[util])
+            }
+        }
+    }
+
+    /// Validate the configured paths and list the diagnostics in the Configuration card
     ///
-    /// This performs the full cleaning cycle for one plugin:
-    /// 1. Get log paths
-    /// 2. Clear old logs
-    /// 3. Build and execute cleaning command (with cancellation support)
-    /// 4. Check for errors
-    /// 5. Parse results
+    /// Returns the diagnostics so the start button can refuse to run on errors.
+    fn show_diagnostics(
+        ui_weak: &slint::Weak<MainWindow>,
+        registry: &GameRegistry,
+        main_config: &MainConfig,
+        state: &StateManager,
+    ) -> Vec<Diagnostic> {
+        let paths = state.read(|s| ConfigPaths {
+            xedit: s.xedit_exe_path.clone(),
+            load_order: s.load_order_path.clone(),
+            data: s.data_path.clone(),
+        });
+        let mut diagnostics = validate_configuration(registry, main_config, &paths);
+        let options = state.read(|s| s.command_options.clone());
+        if let Some(diagnostic) = validate_command(main_config, &options) {
+            diagnostics.insert(0, diagnostic);
+        }
+        for diagnostic in &diagnostics {
+            tracing::warn!("{}", diagnostic);
+        }
+
+        if let Some(ui) = ui_weak.upgrade() {
+            Self::show_command_preview(&ui, registry, state);
+
+            let items: Vec<ConfigDiagnostic> = diagnostics
+                .iter()
+                .map(|diagnostic| ConfigDiagnostic {
+                    is_error: diagnostic.is_error(),
+                    code: diagnostic.message.id.key().into(),
+                    title: diagnostic.title().into(),
+                    detail: diagnostic.detail.as_str().into(),
+                })
+                .collect();
+            ui.set_config_diagnostics(ModelRc::new(VecModel::from(items)));
+        }
+
+        diagnostics
+    }
+
+    /// Show the xEdit command a cleaning run would use, or why it cannot be built
+    fn show_command_preview(ui: &MainWindow, registry: &GameRegistry, state: &StateManager) {
+        let service = CleaningService::new();
+        let preview = state.read(|s| {
+            let xedit = s.xedit_exe_path.as_deref()?;
+            let game = s.game_type.as_deref().and_then(|id| registry.get(id));
+            let main_log = PluginLogs::new(&s.sessions_dir, PREVIEW_PLUGIN).main_log;
+            Some(Self::plugin_command(
+                &service,
+                s,
+                game,
+                PREVIEW_PLUGIN,
+                xedit,
+                &main_log,
+            ))
+        });
+
+        let (text, is_error) = match preview {
+            Some(Ok(command)) => (command, false),
+            Some(Err(e)) => (e.to_string(), true),
+            None => (String::new(), false),
+        };
+        ui.set_command_preview(text.into());
+        ui.set_command_preview_error(is_error);
+    }
+
+    /// Build the xEdit command for a plugin from the current settings
     ///
-    /// Uses `tokio::select!` to race the cleaning operation against cancellation,
-    /// providing immediate responsiveness to user cancellation requests.
+    /// Paths are passed as xEdit and MO2 see them (`Z:\` paths under Wine).
+    fn plugin_command(
+        service: &CleaningService,
+        state: &AppState,
+        game: Option<&GameDefinition>,
+        plugin: &str,
+        xedit_exe: &Utf8Path,
+        main_log: &Utf8Path,
+    ) -> Result<String, CommandError> {
+        let windows_path = |path: &Utf8Path| state.runner.windows_path(path);
+
+        // Vortex deploys into the real Data folder, so xEdit runs directly
+        let mo2_exe = state
+            .mo2_exe_path
+            .as_deref()
+            .filter(|_| !state.vortex_mode)
+            .map(|mo2| Utf8PathBuf::from(windows_path(mo2)));
+        let values = CommandValues {
+            partial_forms: state.partial_forms_enabled,
+            data: state.data_path.as_deref().map(windows_path),
+            load_order: state.load_order_path.as_deref().map(windows_path),
+            log: Some(windows_path(main_log)),
+        };
+
+        service.build_command(
+            &Utf8PathBuf::from(windows_path(xedit_exe)),
+            plugin,
+            game,
+            mo2_exe.as_deref(),
+            &state.command_options,
+            &values,
+        )
+    }
+
+    /// Find and store the game's plugins.txt when no load order file is configured
+    ///
+    /// Looks in `%LOCALAPPDATA%`, or in the Wine/Proton prefix when xEdit runs through one.
+    fn discover_load_order(
+        registry: &GameRegistry,
+        state: &StateManager,
+        game_id: Option<&str>,
+    ) -> Option<Utf8PathBuf> {
+        let runner = state.read(|s| s.runner.clone());
+        let path = find_load_order(registry, &runner, game_id)?;
+
+        tracing::info!("Using discovered load order file: {}", path);
+        state.set_load_order_path(Some(path.clone()));
+        Some(path)
+    }
+
+    /// Clean a single plugin in one or more QAC passes
+    ///
+    /// Undeleting references can turn other records into ITMs, so with `Cleaning Passes`
+    /// above 1 the plugin is cleaned again while the previous pass fixed records, up to that
+    /// many passes. Each pass writes its own logs in the session folder. Per-pass statistics
+    /// are kept in the state and combined for the result.
     ///
     /// Returns (status, message, stats) tuple
     async fn clean_plugin(
         plugin: &str,
+        game: Option<&GameDefinition>,
         state: &StateManager,
         service: &CleaningService,
+        catalog: &MessageCatalog<'_>,
+        session: &CleaningSession,
         mut cancel_rx: watch::Receiver<bool>,
-    ) -> Result<(String, String, Option<CleaningStats>)> {
-        // Get configuration from state
-        let (xedit_exe, game_type, mo2_exe, partial_forms, timeout, _mo2_install, _xedit_install) =
-            state.read(|s| {
-                (
-                    s.xedit_exe_path.clone(),
-                    s.game_type.clone(),
-                    s.mo2_exe_path.clone(),
-                    s.partial_forms_enabled,
-                    s.cleaning_timeout,
-                    s.mo2_install_path.clone(),
-                    s.xedit_install_path.clone(),
+    ) -> Result<PluginResult> {
+        let max_passes = state.read(|s| s.cleaning_passes).max(1);
+        let mut passes: Vec<CleaningStats> = Vec::new();
+
+        loop {
+            let pass = passes.len() as u32 + 1;
+            if pass > 1 {
+                tracing::info!("Cleaning pass {} of {} for {}", pass, max_passes, plugin);
+            }
+
+            let logs = session.pass_logs(plugin, pass);
+            let (status, message, stats) = Self::clean_with_retries(
+                plugin,
+                game,
+                state,
+                service,
+                catalog,
+                &logs,
+                &mut cancel_rx,
+            )
+            .await?;
+
+            let stats = match stats {
+                Some(stats) => stats,
+                // The first pass failed or was skipped: nothing to combine
+                None if passes.is_empty() => return Ok((status, message, None)),
+                // A later pass failed: the earlier passes already cleaned the plugin
+                None => {
+                    tracing::warn!("Pass {} for {} failed: {}", pass, plugin, message);
+                    let result = Self::combine_passes(plugin, state, &passes);
+                    return Ok((
+                        result.0,
+                        i18n::tr_format(
+                            "GuiController",
+                            "{} (pass {} {}: {})",
+                            &[&result.1, &pass, &Self::result_text(&status), &message],
+                        ),
+                        result.2,
+                    ));
+                }
+            };
+
+            let another_pass = stats.modified_plugin() && pass < max_passes && !*cancel_rx.borrow();
+            passes.push(stats);
+
+            if !another_pass {
+                if passes.len() == 1 {
+                    return Ok((status, message, passes.pop()));
+                }
+                return Ok(Self::combine_passes(plugin, state, &passes));
+            }
+        }
+    }
+
+    /// Result of a plugin cleaned in several passes, with the passes kept in the state
+    fn combine_passes(
+        plugin: &str,
+        state: &StateManager,
+        passes: &[CleaningStats],
+    ) -> PluginResult {
+        let total = CleaningStats::combined(passes);
+        let status = if total.has_changes() {
+            "cleaned"
+        } else {
+            "skipped"
+        };
+        let details = passes
+            .iter()
+            .enumerate()
+            .map(|(index, pass)| {
+                i18n::tr_format(
+                    "GuiController",
+                    "pass {}: {}",
+                    &[&(index + 1), &pass.summary()],
                 )
-            });
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
 
-        let xedit_exe = xedit_exe.ok_or_else(|| anyhow!("xEdit exe path not configured"))?;
+        state.update(|s| {
+            s.plugin_passes.insert(plugin.to_string(), passes.to_vec());
+        });
+
+        (
+            status.to_string(),
+            format!("{} ({})", total.summary(), details),
+            Some(total),
+        )
+    }
+
+    /// Run one QAC pass over a plugin, retrying transient failures
+    ///
+    /// Runs [`clean_attempt`](Self::clean_attempt) until it succeeds or fails in a way the
+    /// retry policy does not cover, waiting with a doubling delay between attempts. Earlier
+    /// failed attempts are listed in the result message.
+    ///
+    /// Uses `tokio::select!` to race the cleaning operation and the retry delay against
+    /// cancellation, providing immediate responsiveness to user cancellation requests.
+    async fn clean_with_retries(
+        plugin: &str,
+        game: Option<&GameDefinition>,
+        state: &StateManager,
+        service: &CleaningService,
+        catalog: &MessageCatalog<'_>,
+        logs: &PluginLogs,
+        cancel_rx: &mut watch::Receiver<bool>,
+    ) -> Result<PluginResult> {
+        let policy = state.read(|s| s.retry_policy.clone());
+        let mut failures: Vec<AttemptFailure> = Vec::new();
+
+        loop {
+            let attempt = failures.len() as u32 + 1;
+            let outcome =
+                Self::clean_attempt(plugin, game, state, service, catalog, logs, cancel_rx).await?;
+
+            let failure = match outcome {
+                Ok((status, message, stats)) if !failures.is_empty() => {
+                    let message = i18n::tr_format(
+                        "GuiController",
+                        "{} (attempt {}; {})",
+                        &[&message, &attempt, &describe_attempts(&failures)],
+                    );
+                    return Ok((status, message, stats));
+                }
+                Ok(result) => return Ok(result),
+                Err(failure) => failure,
+            };
 
-        // Get log paths
-        let (main_log, exception_log) = service.get_log_paths(&xedit_exe, game_type.as_deref())?;
+            let retry = policy.should_retry(&failure, attempt);
+            tracing::warn!("Attempt {} for {} failed: {}", attempt, plugin, failure);
+            failures.push(failure);
 
-        // Clear old logs
-        service.clear_logs(&main_log, &exception_log)?;
+            if !retry {
+                let message = match failures.as_slice() {
+                    [failure] => failure.describe(),
+                    _ => describe_attempts(&failures),
+                };
+                return Ok(("failed".to_string(), message, None));
+            }
 
-        // Build cleaning command
-        let command = service.build_cleaning_command(
-            &xedit_exe,
-            plugin,
-            game_type.as_deref(),
-            mo2_exe.as_deref(),
-            partial_forms,
+            let delay = policy.delay_after(attempt);
+            tracing::info!("Retrying {} in {:?}", plugin, delay);
+            state.update_progress(
+                plugin.to_string(),
+                i18n::tr_format(
+                    "GuiController",
+                    "Retrying {} in {}s...",
+                    &[&plugin, &delay.as_secs()],
+                ),
+            );
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = cancel_rx.changed() => {
+                    tracing::warn!("Cleaning cancelled while waiting to retry plugin: {}", plugin);
+                    return Err(anyhow!("Cleaning cancelled by user"));
+                }
+            }
+        }
+    }
+
+    /// Run xEdit once for a plugin
+    ///
+    /// This performs the full cleaning cycle for one plugin:
+    /// 1. Clear logs left in the pass's session logs by an earlier attempt
+    /// 2. Build and execute cleaning command (with cancellation support)
+    /// 3. Archive new exception log output and check it for errors
+    /// 4. Parse results
+    ///
+    /// A timeout, a hang, a non-zero exit code or a missing log is returned as an [`AttemptFailure`]
+    /// so the caller can decide whether to retry.
+    async fn clean_attempt(
+        plugin: &str,
+        game: Option<&GameDefinition>,
+        state: &StateManager,
+        service: &CleaningService,
+        catalog: &MessageCatalog<'_>,
+        logs: &PluginLogs,
+        cancel_rx: &mut watch::Receiver<bool>,
+    ) -> Result<Result<PluginResult, AttemptFailure>> {
+        // Get configuration from state
+        let (xedit_exe, runner, data_path) = state.read(|s| {
+            (
+                s.xedit_exe_path.clone(),
+                s.runner.clone(),
+                s.data_path.clone(),
+            )
+        });
+
+        let xedit_exe = xedit_exe.ok_or(CleaningError::XEditNotConfigured)?;
+
+        // Size the timeout from the plugin and how long it took before
+        let metrics = data_path
+            .map(|data| PluginMetrics::read(&data.join(plugin)))
+            .unwrap_or_default();
+        let timeout = state.read(|s| {
+            plugin_timeout(
+                s.cleaning_timeout,
+                s.adaptive_timeout,
+                &metrics,
+                &s.cleaning_history.durations(plugin),
+            )
+        });
+        tracing::info!("Timeout for {}: {}", plugin, timeout);
+        state.update_progress(
+            plugin.to_string(),
+            i18n::tr_format(
+                "GuiController",
+                "Cleaning {} (timeout: {}s)...",
+                &[&plugin, &timeout.timeout.as_secs()],
+            ),
         );
+        let timeout = timeout.timeout;
+        let hang_detector = state.read(|s| HangDetector::new(s.cpu_threshold, s.hang_window));
+
+        // xEdit writes the main log into the session folder through -R:
+        logs.clear()?;
+
+        // The exception log always goes next to xEdit; note its size to pick out new output
+        let xedit_dir = xedit_exe
+            .parent()
+            .ok_or(CleaningError::XEditNotConfigured)?;
+        let exception_watch = ExceptionLogWatch::start(xedit_dir);
+
+        // Build cleaning command from the user's template and extra arguments
+        let command = state
+            .read(|s| Self::plugin_command(service, s, game, plugin, &xedit_exe, &logs.main_log))
+            .map_err(CleaningError::from)?;
 
         tracing::debug!("Executing command: {}", command);
 
         // Execute cleaning command with cancellation support
         // Race the cleaning operation against cancellation for immediate responsiveness
+        let started = Instant::now();
         let exit_code = tokio::select! {
-            result = service.execute_cleaning_command(&command, timeout) => {
-                result?
+            result = service.execute_cleaning_command(&command, &runner, timeout, hang_detector) => {
+                match result {
+                    Ok(exit_code) => exit_code,
+                    Err(e) => match e.downcast_ref::<CleaningError>() {
+                        Some(CleaningError::Timeout(duration)) => {
+                            return Ok(Err(AttemptFailure::Timeout(*duration)));
+                        }
+                        Some(CleaningError::Hung { window, .. }) => {
+                            return Ok(Err(AttemptFailure::Hung(*window)));
+                        }
+                        _ => return Err(e),
+                    },
+                }
             }
             _ = cancel_rx.changed() => {
                 tracing::warn!("Cleaning cancelled during execution of plugin: {}", plugin);
@@ -1348,34 +2930,70 @@ impl GuiController {
         };
 
         // Check exception log for errors
-        if service.check_exception_log(&exception_log)? {
-            return Ok((
-                "skipped".to_string(),
-                "Missing requirements or empty plugin".to_string(),
+        exception_watch.collect(&logs.exception_log)?;
+        if let Some(exception) = service.check_exception_log(&logs.exception_log)? {
+            if exception.is_xedit_failure() {
+                tracing::error!("xEdit failed while cleaning {}: {}", plugin, exception);
+            }
+            let status = exception.status();
+            let message = CleaningError::Exception {
+                plugin: plugin.to_string(),
+                exception,
+            }
+            .to_message(catalog);
+
+            // Diagnosis and advice, on one line for the plugin result
+            let text = message.body().lines().filter(|line| !line.is_empty());
+            return Ok(Ok((
+                status.to_string(),
+                text.collect::<Vec<_>>().join(" "),
                 None,
-            ));
+            )));
         }
 
         // Check exit code
         if exit_code != 0 {
-            return Ok((
-                "failed".to_string(),
-                format!("xEdit exited with code {}", exit_code),
-                None,
-            ));
+            return Ok(Err(AttemptFailure::ExitCode(exit_code)));
         }
 
         // Parse log file for cleaning stats
-        let stats = service.parse_log_file(&main_log)?;
+        let stats = match service.parse_log_file(&logs.main_log) {
+            Ok(stats) => stats,
+            Err(e) => match e.downcast_ref::<CleaningError>() {
+                Some(CleaningError::LogFileNotFound(path)) => {
+                    return Ok(Err(AttemptFailure::LogNotFound(path.clone())));
+                }
+                _ => return Err(e),
+            },
+        };
+        state.update(|s| s.cleaning_history.record(plugin, started.elapsed()));
+
+        // QAC leaves deleted navmeshes in place; report them for a manual fix
+        if stats.skipped > 0 {
+            let report = NavmeshReport::new(plugin, service.deleted_navmeshes(&logs.main_log)?);
+            report.write(&logs.navmesh_report)?;
+            tracing::warn!(
+                "{} has {} deleted navmeshes, see {}",
+                plugin,
+                stats.skipped,
+                logs.navmesh_report
+            );
+            state.update(|s| {
+                if s.flag_navmeshes {
+                    s.needs_attention.insert(plugin.to_string());
+                }
+                s.navmesh_reports.insert(plugin.to_string(), report);
+            });
+        }
 
         if stats.has_changes() {
-            Ok(("cleaned".to_string(), stats.summary(), Some(stats)))
+            Ok(Ok(("cleaned".to_string(), stats.summary(), Some(stats))))
         } else {
-            Ok((
+            Ok(Ok((
                 "skipped".to_string(),
-                "Nothing to clean".to_string(),
+                i18n::tr("GuiController", "Nothing to clean"),
                 Some(stats),
-            ))
+            )))
         }
     }
 }
@@ -1390,7 +3008,7 @@ mod tests {
         // More comprehensive tests will be in integration tests
 
         let state_manager = Arc::new(StateManager::new());
-        let rt = tokio::runtime::Runtime::new().unwrap();
+        let _rt = tokio::runtime::Runtime::new().unwrap();
 
         // We can't actually create the controller in a test environment without a display,
         // but we can test the state manager integration
@@ -1416,4 +3034,28 @@ mod tests {
         assert_eq!(state.progress, 5);
         assert_eq!(state.total_plugins, 10);
     }
+
+    #[test]
+    fn test_single_plugin_must_be_installed() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let data = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+        std::fs::write(data.join("Installed.esp"), b"").unwrap();
+
+        let state = StateManager::new();
+        state.update(|s| s.data_path = Some(data.clone()));
+
+        let plugin =
+            GuiController::select_single_plugin(&state, "C:/Downloads/Installed.esp").unwrap();
+        assert_eq!(plugin, "Installed.esp");
+
+        let error = GuiController::select_single_plugin(&state, "Missing.esp").unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<CleaningError>(),
+            Some(CleaningError::PluginNotInstalled(plugin)) if plugin == "Missing.esp"
+        ));
+
+        // MO2's Data folder is virtual, so the plugin may live in a mod folder
+        state.update(|s| s.mo2_mode = true);
+        assert!(GuiController::select_single_plugin(&state, "Missing.esp").is_ok());
+    }
 }
@@ -160,6 +160,30 @@ fn test_ignore_config_load_and_save() {
     assert!(loaded_config.fo4.contains(&"AnotherMod.esp".to_string()));
 }
 
+#[test]
+fn test_checkpoint_save_load_and_clear() {
+    use autoqac::models::SessionCheckpoint;
+
+    let (_temp_dir, config_path) = create_test_config_dir();
+    let manager = ConfigManager::new(&config_path).unwrap();
+
+    // No interrupted run yet
+    assert_eq!(manager.load_checkpoint().unwrap(), None);
+
+    let checkpoint = SessionCheckpoint {
+        session: "logs/sessions/2025-01-31_18-04-22".to_string(),
+        queue: vec!["MyMod.esp".to_string(), "AnotherMod.esp".to_string()],
+        ..SessionCheckpoint::default()
+    };
+    manager.save_checkpoint(&checkpoint).unwrap();
+    assert_eq!(manager.load_checkpoint().unwrap(), Some(checkpoint));
+    assert!(!config_path.join("AutoQAC Session.yaml.tmp").exists());
+
+    manager.clear_checkpoint().unwrap();
+    assert_eq!(manager.load_checkpoint().unwrap(), None);
+    manager.clear_checkpoint().unwrap();
+}
+
 #[test]
 fn test_skip_list_functionality() {
     let (_temp_dir, config_path) = create_test_config_dir();
@@ -235,6 +259,43 @@ fn test_config_integration_with_state() {
     );
 }
 
+#[test]
+fn test_wine_runner_maps_config_paths_into_prefix() {
+    let (_temp_dir, config_path) = create_test_config_dir();
+    let manager = ConfigManager::new(&config_path).unwrap();
+
+    let mut user_config = autoqac::UserConfig::default();
+    user_config.pact_settings.runner = autoqac::models::RunnerKind::Wine;
+    user_config.pact_settings.wine_prefix = "/home/user/.wine-skyrim".to_string();
+    user_config.pact_settings.xedit_exe = "C:\\Modding\\SSEEdit.exe".to_string();
+    user_config.pact_settings.game_data_path = "Z:\\games\\Skyrim\\Data".to_string();
+    user_config.pact_settings.mo2_exe = "/opt/MO2/ModOrganizer.exe".to_string();
+
+    manager.save_user_config(&user_config).unwrap();
+
+    use autoqac::StateManager;
+
+    let state = StateManager::new();
+    state.load_from_user_config(&manager.load_user_config().unwrap());
+
+    let snapshot = state.snapshot();
+    assert_eq!(
+        snapshot.xedit_exe_path,
+        Some(Utf8PathBuf::from(
+            "/home/user/.wine-skyrim/drive_c/Modding/SSEEdit.exe"
+        ))
+    );
+    assert_eq!(
+        snapshot.data_path,
+        Some(Utf8PathBuf::from("/games/Skyrim/Data"))
+    );
+    assert_eq!(
+        snapshot.mo2_exe_path,
+        Some(Utf8PathBuf::from("/opt/MO2/ModOrganizer.exe"))
+    );
+    assert!(!snapshot.runner.is_native());
+}
+
 #[test]
 fn test_all_supported_games_have_configs() {
     let (_temp_dir, config_path) = create_test_config_dir();
@@ -320,3 +381,96 @@ fn test_concurrent_config_access() {
         handle.join().unwrap();
     }
 }
+
+#[test]
+fn test_validation_uses_main_config_messages() {
+    use autoqac::models::GameRegistry;
+    use autoqac::services::validation::{ConfigPaths, validate_configuration};
+
+    let (_temp_dir, config_path) = create_test_config_dir();
+    fs::copy(
+        "AutoQAC Data/AutoQAC Main.yaml",
+        config_path.join("AutoQAC Main.yaml"),
+    )
+    .unwrap();
+    let manager = ConfigManager::new(&config_path).unwrap();
+    let main_config = manager.load_main_config().unwrap();
+    let registry = GameRegistry::from_main_config(&main_config);
+
+    // FO4Edit with a Skyrim SE plugins.txt
+    let xedit = config_path.join("FO4Edit.exe");
+    let load_order = config_path.join("plugins.txt");
+    fs::write(&xedit, "").unwrap();
+    fs::write(&load_order, "*Skyrim.esm\n*Update.esm\n*Dawnguard.esm\n").unwrap();
+
+    let paths = ConfigPaths {
+        xedit: Some(xedit),
+        load_order: Some(load_order),
+        data: None,
+    };
+    let diagnostics = validate_configuration(&registry, &main_config, &paths);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(!diagnostics[0].is_error());
+    assert_eq!(diagnostics[0].message.id.key(), "Invalid_INI_Setup");
+    assert_eq!(diagnostics[0].title(), "YOUR PACT INI SETUP IS INCORRECT!");
+    assert!(
+        diagnostics[0]
+            .message
+            .body()
+            .contains("wrong XEdit version")
+    );
+}
+
+#[test]
+fn test_main_config_has_every_catalog_message() {
+    use autoqac::services::messages::{MessageCatalog, MessageId, Severity};
+
+    let (_temp_dir, config_path) = create_test_config_dir();
+    fs::copy(
+        "AutoQAC Data/AutoQAC Main.yaml",
+        config_path.join("AutoQAC Main.yaml"),
+    )
+    .unwrap();
+    let manager = ConfigManager::new(&config_path).unwrap();
+    let main_config = manager.load_main_config().unwrap();
+    let catalog = MessageCatalog::new(&main_config);
+
+    // The shipped file lists every message so modders can find and edit it,
+    // with the same text as the built-in fallback
+    for id in MessageId::ALL {
+        let section = match id.severity() {
+            Severity::Error => &main_config.pact_data.errors,
+            Severity::Warning => &main_config.pact_data.warnings,
+        };
+        assert!(section.contains_key(id.key()), "missing {}", id.key());
+        assert_eq!(catalog.text(id), id.default_text(), "{}", id.key());
+    }
+}
+
+#[test]
+fn test_language_setting_selects_bundled_catalog() {
+    use autoqac::i18n;
+
+    let (_temp_dir, config_path) = create_test_config_dir();
+    let manager = ConfigManager::new(&config_path).unwrap();
+
+    let mut user_config = manager.load_user_config().unwrap();
+    assert_eq!(user_config.pact_settings.language, "");
+    user_config.pact_settings.language = "de".to_string();
+    manager.save_user_config(&user_config).unwrap();
+
+    let loaded_config = manager.load_user_config().unwrap();
+    let language = i18n::resolve_language(&loaded_config.pact_settings.language);
+    assert_eq!(language, "de");
+
+    let catalog = i18n::bundled(language).expect("German catalog is bundled");
+    assert_eq!(
+        catalog.translate("GuiController", "Ready to clean ({}) - {} plugins"),
+        "Bereit zur Reinigung ({}) - {} Plugins"
+    );
+    assert_eq!(
+        catalog.translate("MainWindow", "Start Cleaning"),
+        "Reinigung starten"
+    );
+}
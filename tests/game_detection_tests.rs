@@ -37,9 +37,25 @@ fn test_detect_sse_from_executable() {
         detect_xedit_game("SSEEdit64.exe", None),
         Some("SSE".to_string())
     );
+}
+
+#[test]
+fn test_detect_skyrim_le_from_executable() {
     assert_eq!(
         detect_xedit_game("TES5Edit.exe", None),
-        Some("SSE".to_string())
+        Some("SkyrimLE".to_string())
+    );
+    assert_eq!(
+        detect_xedit_game("TES5Edit64.exe", None),
+        Some("SkyrimLE".to_string())
+    );
+}
+
+#[test]
+fn test_detect_oblivion_from_executable() {
+    assert_eq!(
+        detect_xedit_game("TES4Edit.exe", None),
+        Some("Oblivion".to_string())
     );
 }
 
@@ -79,11 +95,11 @@ fn test_detect_ttw_from_executable() {
 fn test_detect_fo4vr_from_executable() {
     assert_eq!(
         detect_xedit_game("FO4VREdit.exe", None),
-        Some("FO4".to_string())
+        Some("FO4VR".to_string())
     );
     assert_eq!(
         detect_xedit_game("FO4VREdit64.exe", None),
-        Some("FO4".to_string())
+        Some("FO4VR".to_string())
     );
 }
 
@@ -91,7 +107,23 @@ fn test_detect_fo4vr_from_executable() {
 fn test_detect_skyrimvr_from_executable() {
     assert_eq!(
         detect_xedit_game("SkyrimVREdit.exe", None),
-        Some("SSE".to_string())
+        Some("SkyrimVR".to_string())
+    );
+    assert_eq!(
+        detect_xedit_game("TES5VREdit.exe", None),
+        Some("SkyrimVR".to_string())
+    );
+}
+
+#[test]
+fn test_detect_starfield_from_executable() {
+    assert_eq!(
+        detect_xedit_game("SF1Edit.exe", None),
+        Some("Starfield".to_string())
+    );
+    assert_eq!(
+        detect_xedit_game("SF1Edit64.exe", None),
+        Some("Starfield".to_string())
     );
 }
 
@@ -157,6 +189,42 @@ fn test_detect_fnv_from_load_order() {
     assert_eq!(result, Some("FNV".to_string()));
 }
 
+#[test]
+fn test_detect_total_conversions_from_load_order() {
+    let cases = [
+        (["*FalloutNV.esm", "*TaleOfTwoWastelands.esm"], "TTW"),
+        (
+            ["*Skyrim.esm", "*Enderal - Forgotten Stories.esm"],
+            "Enderal",
+        ),
+        (["*Fallout4.esm", "*LondonWorldSpace.esm"], "London"),
+    ];
+
+    for (plugins, expected_game) in cases {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        for plugin in plugins {
+            writeln!(temp_file, "{}", plugin).unwrap();
+        }
+
+        let temp_path = Utf8Path::from_path(temp_file.path()).unwrap();
+        let result = detect_game_from_load_order(temp_path).unwrap();
+        assert_eq!(result, Some(expected_game.to_string()));
+    }
+}
+
+#[test]
+fn test_total_conversion_with_base_game_xedit() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    writeln!(temp_file, "*Fallout4.esm").unwrap();
+    writeln!(temp_file, "*LondonWorldSpace.esm").unwrap();
+
+    let temp_path = Utf8Path::from_path(temp_file.path()).unwrap();
+    assert_eq!(
+        detect_xedit_game("FO4Edit.exe", Some(temp_path)),
+        Some("London".to_string())
+    );
+}
+
 #[test]
 fn test_detect_fo3_from_load_order() {
     let mut temp_file = NamedTempFile::new().unwrap();
@@ -170,11 +238,49 @@ fn test_detect_fo3_from_load_order() {
     assert_eq!(result, Some("FO3".to_string()));
 }
 
+#[test]
+fn test_detect_fo4vr_from_load_order() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    writeln!(temp_file, "*Fallout4.esm").unwrap();
+    writeln!(temp_file, "*Fallout4_VR.esm").unwrap();
+    writeln!(temp_file, "*MyMod.esp").unwrap();
+
+    let temp_path = Utf8Path::from_path(temp_file.path()).unwrap();
+    let result = detect_game_from_load_order(temp_path).unwrap();
+
+    assert_eq!(result, Some("FO4VR".to_string()));
+}
+
+#[test]
+fn test_detect_oblivion_from_load_order() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    writeln!(temp_file, "Oblivion.esm").unwrap();
+    writeln!(temp_file, "DLCShiveringIsles.esp").unwrap();
+
+    let temp_path = Utf8Path::from_path(temp_file.path()).unwrap();
+    let result = detect_game_from_load_order(temp_path).unwrap();
+
+    assert_eq!(result, Some("Oblivion".to_string()));
+}
+
+#[test]
+fn test_detect_starfield_from_load_order() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    writeln!(temp_file, "*Starfield.esm").unwrap();
+    writeln!(temp_file, "*Constellation.esm").unwrap();
+    writeln!(temp_file, "*OldMars.esm").unwrap();
+
+    let temp_path = Utf8Path::from_path(temp_file.path()).unwrap();
+    let result = detect_game_from_load_order(temp_path).unwrap();
+
+    assert_eq!(result, Some("Starfield".to_string()));
+}
+
 #[test]
 fn test_load_order_with_comments_and_whitespace() {
     let mut temp_file = NamedTempFile::new().unwrap();
     writeln!(temp_file, "# This is a comment").unwrap();
-    writeln!(temp_file, "").unwrap();
+    writeln!(temp_file).unwrap();
     writeln!(temp_file, "  ").unwrap();
     writeln!(temp_file, "*Skyrim.esm").unwrap();
     writeln!(temp_file, "# Another comment").unwrap();
@@ -260,8 +366,7 @@ fn test_xedit_detection_takes_precedence_over_load_order() {
 
 #[test]
 fn test_load_order_case_sensitivity() {
-    // NOTE: Current implementation is case-sensitive for master ESM names
-    // This matches typical load order files which preserve correct casing
+    // Master ESM names are matched without case, like Windows file names
     let mut temp_file = NamedTempFile::new().unwrap();
     writeln!(temp_file, "*Skyrim.esm").unwrap(); // Correct casing
     writeln!(temp_file, "*Update.esm").unwrap();
@@ -271,15 +376,14 @@ fn test_load_order_case_sensitivity() {
 
     assert_eq!(result, Some("SSE".to_string()));
 
-    // Lowercase version will not be detected (current limitation)
+    // Lowercase version is detected too
     let mut temp_file2 = NamedTempFile::new().unwrap();
     writeln!(temp_file2, "*skyrim.esm").unwrap(); // Lowercase
 
     let temp_path2 = Utf8Path::from_path(temp_file2.path()).unwrap();
     let result2 = detect_game_from_load_order(temp_path2).unwrap();
 
-    // This is expected behavior - case-sensitive matching
-    assert_eq!(result2, None);
+    assert_eq!(result2, Some("SSE".to_string()));
 }
 
 #[test]
@@ -344,12 +448,14 @@ fn test_all_supported_xedit_variants() {
         ("FNVEdit64.exe", "FNV"),
         ("FO4Edit.exe", "FO4"),
         ("FO4Edit64.exe", "FO4"),
-        ("FO4VREdit.exe", "FO4"),
+        ("FO4VREdit.exe", "FO4VR"),
         ("SSEEdit.exe", "SSE"),
         ("SSEEdit64.exe", "SSE"),
-        ("TES5Edit.exe", "SSE"),
-        ("SkyrimVREdit.exe", "SSE"),
+        ("TES5Edit.exe", "SkyrimLE"),
+        ("TES4Edit.exe", "Oblivion"),
+        ("SkyrimVREdit.exe", "SkyrimVR"),
         ("TTWEdit.exe", "TTW"),
+        ("EnderalSEEdit.exe", "Enderal"),
     ];
 
     for (executable, expected_game) in test_cases {
@@ -2,10 +2,11 @@
 //!
 //! These tests verify:
 //! - Command building for various scenarios
-//! - Log file path generation
+//! - Session log folders and exception log archiving
 //! - Integration with StateManager
 //! - Error handling workflows
 
+use autoqac::models::GameRegistry;
 use autoqac::services::CleaningService;
 use camino::Utf8Path;
 
@@ -32,17 +33,18 @@ fn test_build_cleaning_command_basic() {
 #[test]
 fn test_build_cleaning_command_with_game_mode() {
     let service = CleaningService::new();
+    let registry = GameRegistry::builtin();
 
     let command = service.build_cleaning_command(
         Utf8Path::new("C:\\Tools\\xEdit.exe"), // Universal xEdit
         "MyPlugin.esp",
-        Some("FO4"), // Specify game mode
+        registry.get("FO4"), // Game definition supplies the mode flag
         None,
         false,
     );
 
     // Should contain game mode flag
-    assert!(command.contains("-FO4"));
+    assert!(command.contains("-fo4"));
 }
 
 #[test]
@@ -83,18 +85,19 @@ fn test_build_cleaning_command_with_partial_forms() {
 #[test]
 fn test_build_cleaning_command_all_options() {
     let service = CleaningService::new();
+    let registry = GameRegistry::builtin();
 
     let command = service.build_cleaning_command(
         Utf8Path::new("C:\\Tools\\xEdit.exe"),
         "MyPlugin.esp",
-        Some("SSE"),                                      // Game mode
+        registry.get("SSE"),                              // Game mode
         Some(Utf8Path::new("C:\\MO2\\ModOrganizer.exe")), // MO2
         true,                                             // Partial forms
     );
 
     // Verify all expected strings present
     assert!(command.contains("run"));
-    assert!(command.contains("-SSE"));
+    assert!(command.contains("-sse"));
     assert!(command.contains("-QAC"));
     assert!(command.contains("-autoexit"));
     assert!(command.contains("-autoload"));
@@ -103,43 +106,135 @@ fn test_build_cleaning_command_all_options() {
 }
 
 #[test]
-fn test_get_log_paths() {
-    let service = CleaningService::new();
+fn test_session_logs_redirect_xedit_log() {
+    use autoqac::services::{
+        CleaningSession, CommandOptions, CommandValues, ExceptionLogWatch, XEditException,
+    };
+    use camino::Utf8PathBuf;
+    use std::fs;
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let root = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+    let xedit_dir = root.join("xEdit");
+    fs::create_dir_all(&xedit_dir).unwrap();
+    fs::write(xedit_dir.join("SSEEdit_log.txt"), "the user's own log").unwrap();
 
-    let (main_log, exception_log) = service
-        .get_log_paths(
-            Utf8Path::new("C:\\Tools\\FO4Edit.exe"),
-            None, // No game type (specific xEdit)
+    let service = CleaningService::new();
+    let session = CleaningSession::create(&root.join("sessions")).unwrap();
+    let logs = session.plugin_logs("MyPlugin.esp");
+    let watch = ExceptionLogWatch::start(&xedit_dir);
+
+    // xEdit is told where to write the main log, so no log name has to be guessed
+    let values = CommandValues {
+        log: Some(logs.main_log.to_string()),
+        ..Default::default()
+    };
+    let command = service
+        .build_command(
+            &xedit_dir.join("SSEEdit.exe"),
+            "MyPlugin.esp",
+            GameRegistry::builtin().get("SSE"),
+            None,
+            &CommandOptions::default(),
+            &values,
         )
         .unwrap();
+    assert!(command.contains(&format!("-R:\"{}\"", logs.main_log)));
+
+    // Simulate the run: main log in the session folder, a new exception log next to xEdit
+    fs::write(&logs.main_log, "Removing: [00012345] <MyPlugin.esp>\n").unwrap();
+    fs::write(
+        xedit_dir.join("SSEEditException.log"),
+        "Plugin requires master which can not be found\n",
+    )
+    .unwrap();
+
+    assert!(watch.collect(&logs.exception_log).unwrap());
+    assert!(matches!(
+        service.check_exception_log(&logs.exception_log).unwrap(),
+        Some(XEditException::MissingMaster { master: None })
+    ));
+    assert_eq!(service.parse_log_file(&logs.main_log).unwrap().removed, 1);
+
+    // The user's logs in the xEdit folder are left alone
+    assert_eq!(
+        fs::read_to_string(xedit_dir.join("SSEEdit_log.txt")).unwrap(),
+        "the user's own log"
+    );
+    assert!(xedit_dir.join("SSEEditException.log").exists());
+}
 
-    // Main log file should be in same directory as xEdit
-    assert!(main_log.starts_with("C:\\Tools\\"));
-
-    // Log file should contain xEdit name
-    assert!(main_log.as_str().contains("FO4EDIT"));
+#[test]
+fn test_starfield_universal_mode() {
+    let service = CleaningService::new();
+    let registry = GameRegistry::builtin();
+    let starfield = registry.get("Starfield");
 
-    // Exception log should also be in same directory
-    assert!(exception_log.starts_with("C:\\Tools\\"));
-    assert!(exception_log.as_str().contains("FO4EDIT"));
-    assert!(exception_log.as_str().contains("Exception"));
+    let command = service.build_cleaning_command(
+        Utf8Path::new("C:/Tools/xEdit64.exe"),
+        "MyStarfieldMod.esm",
+        starfield,
+        None,
+        false,
+    );
+    assert!(command.contains("-sf1"));
 }
 
 #[test]
-fn test_get_log_paths_with_game_type() {
-    let service = CleaningService::new();
+fn test_build_command_with_user_options() {
+    use autoqac::StateManager;
+    use autoqac::services::{CleaningError, CommandValues};
 
-    let (main_log, exception_log) = service
-        .get_log_paths(
+    let service = CleaningService::new();
+    let registry = GameRegistry::builtin();
+
+    // Options come from the user config through the state manager
+    let mut user_config = autoqac::UserConfig::default();
+    user_config.pact_settings.xedit_arguments = "-cache".to_string();
+    user_config
+        .pact_settings
+        .xedit_game_arguments
+        .insert("SSE".to_string(), r#"-D:"{data}""#.to_string());
+    let state = StateManager::new();
+    state.load_from_user_config(&user_config);
+    let options = state.read(|s| s.command_options.clone());
+
+    let values = CommandValues {
+        data: Some("C:\\Games\\Skyrim Special Edition\\Data".to_string()),
+        ..Default::default()
+    };
+    let command = service
+        .build_command(
             Utf8Path::new("C:\\Tools\\xEdit.exe"),
-            Some("SSE"), // Universal xEdit with game mode
+            "My Plugin.esp",
+            registry.get("SSE"),
+            Some(Utf8Path::new("C:\\MO2\\ModOrganizer.exe")),
+            &options,
+            &values,
         )
         .unwrap();
+    assert_eq!(
+        command,
+        r#""C:\MO2\ModOrganizer.exe" run "C:\Tools\xEdit.exe -sse" -QAC -autoexit -autoload -cache -D:"C:\Games\Skyrim Special Edition\Data" "My Plugin.esp""#
+    );
 
-    // Log files should use game-specific names
-    // Format: SSEEdit_log.txt (game type uppercase + "Edit")
-    assert!(main_log.as_str().contains("SSEEdit_log.txt"));
-    assert!(exception_log.as_str().contains("SSEEditException.log"));
+    // An invalid template is reported as a cleaning error with its own message
+    user_config.pact_settings.command_template = r#"-QAC -R:"{log}" "{plugin}""#.to_string();
+    state.load_from_user_config(&user_config);
+    let options = state.read(|s| s.command_options.clone());
+    let error = service
+        .build_command(
+            Utf8Path::new("C:\\Tools\\SSEEdit.exe"),
+            "My Plugin.esp",
+            registry.get("SSE"),
+            None,
+            &options,
+            &values,
+        )
+        .map_err(CleaningError::from)
+        .unwrap_err();
+    assert_eq!(error.message_id().key(), "Invalid_XEdit_Command");
+    assert!(error.to_string().contains("-autoexit"));
 }
 
 #[test]
@@ -165,7 +260,7 @@ fn test_integration_with_state_manager() {
     use std::sync::Arc;
 
     let state = Arc::new(StateManager::new());
-    let service = CleaningService::new();
+    let _service = CleaningService::new();
 
     // Start cleaning workflow
     state.start_cleaning(vec!["plugin1.esp".to_string(), "plugin2.esp".to_string()]);
@@ -361,3 +456,65 @@ fn test_statistics_tracking_workflow() {
     assert_eq!(snapshot.total_skipped, 1); // 1 + 0
     assert_eq!(snapshot.total_partial_forms, 1); // 0 + 1
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_idle_process_stopped_as_hung() {
+    use autoqac::services::{CleaningError, HangDetector, Runner};
+    use std::time::{Duration, Instant};
+
+    let service = CleaningService::new();
+    let started = Instant::now();
+
+    let error = service
+        .execute_cleaning_command(
+            "sleep 60",
+            &Runner::default(),
+            Duration::from_secs(60),
+            HangDetector::new(5, Duration::from_secs(1)),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error.downcast_ref::<CleaningError>(),
+        Some(CleaningError::Hung { threshold: 5, .. })
+    ));
+    assert!(started.elapsed() < Duration::from_secs(30));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_timeout_without_hang_detection() {
+    use autoqac::services::{CleaningError, HangDetector, Runner};
+    use std::time::Duration;
+
+    let service = CleaningService::new();
+
+    let error = service
+        .execute_cleaning_command(
+            "sleep 60",
+            &Runner::default(),
+            Duration::from_secs(1),
+            HangDetector::new(0, Duration::from_secs(1)),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error.downcast_ref::<CleaningError>(),
+        Some(CleaningError::Timeout(_))
+    ));
+
+    // A process that exits on its own keeps its exit code
+    let exit_code = service
+        .execute_cleaning_command(
+            "exit 3",
+            &Runner::default(),
+            Duration::from_secs(10),
+            HangDetector::new(5, Duration::from_secs(60)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(exit_code, 3);
+}
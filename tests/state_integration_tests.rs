@@ -390,3 +390,90 @@ async fn test_statistics_aggregation() {
         "Total records should be sum of all"
     );
 }
+
+#[test]
+fn test_retry_policy_loaded_from_user_config() {
+    use autoqac::models::{PactSettings, RetryReason, UserConfig};
+    use autoqac::services::AttemptFailure;
+
+    let state = StateManager::new();
+    let config = UserConfig {
+        pact_settings: PactSettings {
+            retry_attempts: 2,
+            retry_delay: 5,
+            retry_on: vec![RetryReason::Timeout],
+            ..Default::default()
+        },
+    };
+    state.load_from_user_config(&config);
+
+    let policy = state.read(|s| s.retry_policy.clone());
+    assert_eq!(policy.max_attempts, 2);
+    assert_eq!(policy.delay_after(2), Duration::from_secs(10));
+
+    let timeout = AttemptFailure::Timeout(Duration::from_secs(300));
+    assert!(policy.should_retry(&timeout, 1));
+    assert!(!policy.should_retry(&timeout, 2));
+    assert!(!policy.should_retry(&AttemptFailure::ExitCode(1), 1));
+}
+
+#[test]
+fn test_cleaning_plan_follows_skip_unchanged() {
+    use autoqac::ConfigManager;
+    use autoqac::models::{GameRegistry, IgnoreConfig};
+    use autoqac::services::{Exclusion, plugin_crc};
+    use camino::Utf8PathBuf;
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let root = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+    let data = root.join("Data");
+    std::fs::create_dir_all(&data).unwrap();
+    std::fs::write(data.join("Cleaned.esp"), "already cleaned").unwrap();
+    let load_order = root.join("plugins.txt");
+    std::fs::write(&load_order, "*Skyrim.esm\n*Cleaned.esp\nOff.esp\n").unwrap();
+
+    let main_config = ConfigManager::new(&root)
+        .unwrap()
+        .load_main_config()
+        .unwrap();
+    let registry = GameRegistry::from_main_config(&main_config);
+    let ignore_config = IgnoreConfig::default();
+    let crc = plugin_crc(&data.join("Cleaned.esp")).unwrap();
+
+    let state = StateManager::new();
+    state.update(|s| {
+        s.data_path = Some(data.clone());
+        s.skip_unchanged = true;
+        s.cleaning_history.record_cleaned("Cleaned.esp", crc);
+    });
+    let plan = |state: &StateManager| {
+        state
+            .cleaning_plan(
+                &registry,
+                &main_config,
+                &ignore_config,
+                registry.get("SSE"),
+                &load_order,
+            )
+            .unwrap()
+    };
+
+    let skipping = plan(&state);
+    assert!(skipping.plugins_to_clean().is_empty());
+    assert_eq!(
+        skipping.entries[1].exclusion,
+        Some(Exclusion::Unchanged),
+        "Plugin with the recorded checksum should be left out"
+    );
+    assert_eq!(skipping.entries[2].exclusion, Some(Exclusion::Inactive));
+
+    // In MO2 mode the Data folder copy is not the one xEdit cleaned
+    state.update(|s| s.mo2_mode = true);
+    assert_eq!(plan(&state).plugins_to_clean(), ["Cleaned.esp"]);
+
+    state.update(|s| {
+        s.mo2_mode = false;
+        s.skip_unchanged = false;
+    });
+    assert_eq!(plan(&state).plugins_to_clean(), ["Cleaned.esp"]);
+}